@@ -1,4 +1,7 @@
 use crate::hcm::basicfreeways::BasicFreeways as LibBasicFreeways;
+use crate::hcm::basicfreeways::managed_lanes::PairedManagedLaneSegment;
+use crate::hcm::basicfreeways::multilane::MultilaneFacility;
+use crate::hcm::basicfreeways::service_volume_tables::ServiceVolumeTableSpec;
 use crate::hcm::common::CityType;
 use crate::hcm::managed_lanes::{ManagedLaneSegment as LibManagedLaneSegment, ManagedLaneType};
 use pyo3::exceptions::PyValueError;
@@ -373,8 +376,91 @@ impl ManagedLanes {
     }
 }
 
+/// Generalized service volume table for basic freeway, multilane, weaving or ramp segments.
+///
+/// Args:
+///     config_json: JSON `ServiceVolumeTableSpec`: `segment` (`"BasicFreeway"`,
+///         `"Multilane"`, `{"Weaving": {"template": ..., "split": ...}}` or
+///         `{"Ramp": {"template": ..., "basis": ...}}`), lists of `ffs`, `lanes`,
+///         `terrains` (`"Level"` / `"Rolling"`) and `factors` (`{"k", "d"}` pairs),
+///         and the local `heavy_vehicle_pct` (decimal) and `phf`.
+///     csv: return CSV, one line per row and LOS, instead of JSON.
+///
+/// Returns:
+///     str: JSON object with `rows`, one per terrain, FFS, lane count and K/D pair, each
+///     with `f_hv` and per-LOS `sfi`, `sf`, `sv` and `dsv` (null where that LOS cannot be
+///     reached); or the same as CSV.
+///
+/// Raises:
+///     ValueError: if the config is malformed, an input is out of range, terrain is
+///         mountainous, or an FFS falls outside Exhibit 12-37/12-38.
+#[pyfunction]
+#[pyo3(signature = (config_json, csv=false))]
+pub fn service_volume_table(config_json: &str, csv: bool) -> PyResult<String> {
+    let spec = ServiceVolumeTableSpec::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid service volume table config: {e}")))?;
+    let table = spec.generate().map_err(PyValueError::new_err)?;
+    if csv {
+        return Ok(table.to_csv());
+    }
+    serde_json::to_string(&table).map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// Analyze a multilane highway corridor, one direction of travel, segment by segment.
+///
+/// Args:
+///     config_json: JSON `MultilaneFacility`: `segments` in travel order, each with
+///         `length` (mi), `lanes`, `bffs` or `speed_limit` (mi/h), `median`
+///         (`"Undivided"`, `"TWLTL"`, `"Raised"`, `"Depressed"` or `"Barrier"`),
+///         `demand` (veh/h), `phf`, `heavy_vehicle_pct` (decimal) and `terrain`
+///         (`"Level"`, `"Rolling"` or `{"SpecificGrade": {"grade", "sut_percentage"}}`),
+///         and optionally `lane_width`, `lc_right`, `lc_left` (ft),
+///         `access_point_density` (points/mi), `saf` and `caf`.
+///
+/// Returns:
+///     str: JSON object with per-segment `segments` (FFS, capacity, f_HV, v_p, speed,
+///     density, LOS), the facility `speed`, `travel_time`, `density` and `los`, and the
+///     `governing_segment`. Speeds and densities are null where demand exceeds capacity.
+///
+/// Raises:
+///     ValueError: if the config is malformed or a segment input is out of range.
+#[pyfunction]
+pub fn analyze_multilane_facility(config_json: &str) -> PyResult<String> {
+    let facility = MultilaneFacility::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid multilane facility config: {e}")))?;
+    let result = facility.analyze().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result).map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// Analyze a managed lane segment together with the general purpose lanes beside it.
+///
+/// Args:
+///     config_json: JSON `PairedManagedLaneSegment`: `separation` (`"ContinuousAccess"`,
+///         `"Buffer"` or `"Barrier"`), `ml_lanes`, `gp_lanes`, `ml_ffs` and `gp_ffs`
+///         (mi/h), `ml_demand` and `gp_demand` (veh/h), `phf`, `heavy_vehicle_pct`
+///         (decimal), `terrain` (`"Level"` or `"Rolling"`), and optionally `saf` and `caf`.
+///
+/// Returns:
+///     str: JSON object with the Exhibit 12-9 `lane_type`, `e_t`, `f_hv`, `gp` and `ml`
+///     lane-group results (v_p, breakpoint, capacity_adj, vc_ratio, speed, density, LOS;
+///     speed and density null above capacity), and whether the GP friction is active.
+///
+/// Raises:
+///     ValueError: if the config is malformed, an input is out of range, terrain is
+///         mountainous, or the separation and lane count match no Exhibit 12-9 type.
+#[pyfunction]
+pub fn analyze_paired_managed_lane(config_json: &str) -> PyResult<String> {
+    let segment = PairedManagedLaneSegment::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid managed lane config: {e}")))?;
+    let result = segment.analyze().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result).map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
 pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<BasicFreeways>()?;
     m.add_class::<ManagedLanes>()?;
+    m.add_function(wrap_pyfunction!(service_volume_table, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_multilane_facility, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_paired_managed_lane, m)?)?;
     Ok(())
 }
@@ -24,7 +24,9 @@ impl FreewayFacility {
     ///         tests/ExampleCases/hcm/FreewayFacilities fixtures): ordered
     ///         `segments` (seg_type Basic/Merge/Diverge/Weaving/
     ///         OverlappingRamp, length_ft, lanes, ramp demands per period,
-    ///         weaving attributes, CAF/SAF hooks, optional work_zone),
+    ///         weaving attributes, CAF/SAF hooks, optional work_zone,
+    ///         optional `grade` {grade, sut_share} on any segment for the
+    ///         mixed-flow model, required throughout in Mountainous terrain),
     ///         `mainline_demand` per 15-min analysis period (veh/h), `ffs`,
     ///         `heavy_vehicle_pct`, `terrain`, `city_type`, and the global
     ///         parameters `jam_density_pc` and `queue_discharge_drop`.
@@ -191,7 +193,8 @@ impl ManagedLaneFacility {
     ///         tests/ExampleCases/hcm/FreewayFacilities/ml_case1.json
     ///         fixture): `gp` (a FreewayFacility), `ml` (a list parallel to
     ///         the GP segments, each entry `null` or an ML segment with
-    ///         lane_type/lanes/ffs/caf/saf and ML ramp demands),
+    ///         lane_type/lanes/ffs/caf/saf and ML ramp demands, plus an
+    ///         optional `ramp` direct-access junction geometry),
     ///         `ml_entry_demand` per period (veh/h), `ml_ffs`, and optional
     ///         per-GP-segment `cross_weave` entries (Step A-9).
     #[new]
@@ -260,6 +263,26 @@ impl ManagedLaneFacility {
         self.inner.ml_friction_active.clone()
     }
 
+    /// Direct-access ML ramp junction LOS [segment][period]; `None` where
+    /// the ML segment has no ramp junction.
+    pub fn ml_ramp_los(&self) -> Vec<Vec<Option<String>>> {
+        self.inner
+            .ml_ramp
+            .iter()
+            .map(|row| row.iter().map(|r| r.as_ref().map(|r| r.los.to_string())).collect())
+            .collect()
+    }
+
+    /// Direct-access ML ramp influence area density [segment][period],
+    /// pc/mi/ln; `None` where the ML segment has no ramp junction.
+    pub fn ml_ramp_density(&self) -> Vec<Vec<Option<f64>>> {
+        self.inner
+            .ml_ramp
+            .iter()
+            .map(|row| row.iter().map(|r| r.as_ref().map(|r| r.influence_density_pc)).collect())
+            .collect()
+    }
+
     /// Combined facility space mean speed for one period, mi/h.
     pub fn facility_speed(&self, period: usize) -> f64 {
         self.inner.get_facility_speed(period)
@@ -2,6 +2,8 @@
 
 use crate::hcm::freeway_reliability::reliability::ReliabilityAnalysis as LibReliabilityAnalysis;
 use crate::hcm::freeway_reliability::exhibits;
+use crate::hcm::freeway_reliability::interchange::{self, ScenarioSetFile};
+use crate::hcm::freeway_reliability::planning_batch::{self, PlanningBatchConfig};
 use crate::hcm::common::atdm;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -139,6 +141,41 @@ impl FreewayReliability {
         }
     }
 
+    /// Versioned scenario set file (JSON) for the last run: configuration,
+    /// scenarios, per-scenario results and reliability measures. Requires
+    /// `run()` first.
+    pub fn scenario_set_file_json(&self) -> PyResult<String> {
+        ScenarioSetFile::from_analysis(&self.inner)
+            .and_then(|f| f.to_json())
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Evaluate the scenarios of a saved scenario set file (JSON) against
+    /// this analysis's facility instead of generating new ones.
+    pub fn run_scenario_set_file(&mut self, json: &str) -> PyResult<()> {
+        let file = ScenarioSetFile::from_json(json).map_err(PyValueError::new_err)?;
+        self.inner
+            .run_scenario_set(file.scenario_set)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Scenario table (CSV) for the last run, one row per scenario event
+    /// with FREEVAL's 1-based numbering. Requires `run()` first.
+    pub fn export_scenario_table(&self) -> PyResult<String> {
+        match &self.inner.scenario_set {
+            Some(s) => Ok(interchange::export_scenario_table(s)),
+            None => Err(PyValueError::new_err("run() must be called first")),
+        }
+    }
+
+    /// Import a FREEVAL-style scenario table (CSV) and evaluate exactly
+    /// those scenarios against this analysis's facility.
+    pub fn run_scenario_table(&mut self, csv: &str) -> PyResult<()> {
+        let set = interchange::import_scenario_table(csv, self.inner.facility.ffs)
+            .map_err(PyValueError::new_err)?;
+        self.inner.run_scenario_set(set).map_err(PyValueError::new_err)
+    }
+
     pub fn __repr__(&self) -> String {
         format!(
             "FreewayReliability(scenarios={}, observations={}, fftt={:.2} min)",
@@ -175,6 +212,34 @@ pub fn planning_reliability(
     )
 }
 
+/// Chapter 11 planning-level reliability for a batch of facilities.
+///
+/// Args:
+///     csv: CSV text with one facility per row. Required columns are
+///         `id, aadt, lanes, ffs, k_factor, d_factor, length_mi`; optional
+///         columns are `crash_rate, phf, heavy_vehicle_pct, capacity,
+///         peak_speed, lane_width_ft`.
+///     config_json: optional JSON `PlanningBatchConfig` (keys
+///         `unreliable_tti_95`, `annual_days`, `default_phf`).
+///
+/// Returns:
+///     str: JSON object with `results` (one entry per valid row: v/c,
+///     peak speed, RDR/IDR, TTI_mean, TTI_95, PT_45, peak-hour and annual
+///     VHD, and the over-capacity/unreliable flags) and `errors` (one
+///     entry per rejected row, with its CSV line number).
+#[pyfunction]
+#[pyo3(signature = (csv, config_json=None))]
+pub fn planning_reliability_batch(csv: &str, config_json: Option<&str>) -> PyResult<String> {
+    let config: PlanningBatchConfig = match config_json {
+        Some(j) => serde_json::from_str(j)
+            .map_err(|e| PyValueError::new_err(format!("Invalid planning batch config: {}", e)))?,
+        None => PlanningBatchConfig::default(),
+    };
+    let results =
+        planning_batch::run_planning_batch_csv(csv, &config).map_err(PyValueError::new_err)?;
+    serde_json::to_string(&results).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
 /// HCM Chapter 37, Section 3 shoulder/median lane strategy: total-segment
 /// capacity adjustment factor (CAF) for opening a shoulder/median lane
 /// (Equation 37-1), for use in a `WorkZoneEvent`'s `caf` field (or a
@@ -281,6 +346,7 @@ pub fn alinea_metering_rate(
 pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<FreewayReliability>()?;
     m.add_function(wrap_pyfunction!(planning_reliability, m)?)?;
+    m.add_function(wrap_pyfunction!(planning_reliability_batch, m)?)?;
     m.add_function(wrap_pyfunction!(shoulder_lane_caf, m)?)?;
     m.add_function(wrap_pyfunction!(shoulder_lane_default_capacity_veh_h_ln, m)?)?;
     m.add_function(wrap_pyfunction!(ramp_metered_merge_caf, m)?)?;
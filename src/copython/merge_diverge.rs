@@ -1,6 +1,7 @@
 //! Python bindings for HCM Chapter 14 (Freeway Merge and Diverge Segments).
 
 use crate::hcm::common::HcmVersion;
+use crate::hcm::merge_diverge::design::RampDesignSpec;
 use crate::hcm::merge_diverge::merge_diverge::{
     ramp_service_flow_rate_ideal as lib_ramp_sfi, ramp_service_volumes as lib_ramp_sv,
     AdjacentRampType, RampLanes, RampSegment as LibRampSegment, RampSide, RampType,
@@ -448,9 +449,36 @@ pub fn py_ramp_service_volumes(sfi: f64, f_hv: f64, f_p: f64, phf: f64) -> (f64,
     lib_ramp_sv(sfi, f_hv, f_p, phf)
 }
 
+/// Search acceleration or deceleration lane lengths for a target LOS and v/c.
+///
+/// Args:
+///     config_json: JSON object with "template" (an on-ramp or off-ramp in the JSON form of the
+///         example-case fixtures) and optional "target_los" (default "D"), "target_vc",
+///         "versions" (default ["7", "7.1"]), "ramp_lanes" (e.g. ["OneLane", "TwoLane"]),
+///         "min_length" (default 100 ft), "max_length" (default 1,500 ft) and "length_step"
+///         (default 50 ft). A two-lane candidate holds the template's second lane length.
+///
+/// Returns:
+///     JSON string with one candidate per edition and ramp lane count: the sensitivity curve
+///     (density, speed, v/c and LOS at every length tried) and the shortest length meeting the
+///     target ("design"), or null when none does.
+///
+/// Raises:
+///     ValueError: if the config is malformed, the ramp is a major merge or diverge, the target
+///         is F, a two-lane candidate has no second lane length, or a length or PHF is out of
+///         range.
+#[pyfunction]
+pub fn design_ramp_lane(config_json: &str) -> PyResult<String> {
+    let spec = RampDesignSpec::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid ramp design config: {e}")))?;
+    let result = spec.search().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result).map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
 pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<RampSegment>()?;
     m.add_function(wrap_pyfunction!(py_ramp_service_flow_rate_ideal, m)?)?;
     m.add_function(wrap_pyfunction!(py_ramp_service_volumes, m)?)?;
+    m.add_function(wrap_pyfunction!(design_ramp_lane, m)?)?;
     Ok(())
 }
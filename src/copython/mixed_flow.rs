@@ -1,10 +1,11 @@
 //! Python bindings for the HCM Chapter 25/26 mixed-flow model.
 //!
-//! Both entry points are JSON in, JSON out rather than classes, because the inputs are small
+//! The entry points are JSON in, JSON out rather than classes, because the inputs are small
 //! plain records and the outputs are wide result structs that serde already describes. That
 //! keeps the binding a single line each and means new fields on the Rust side reach Python
 //! without any change here.
 
+use crate::hcm::basicfreeways::climbing_lane::ClimbingLaneStudy;
 use crate::hcm::basicfreeways::composite_grade::CompositeGrade;
 use crate::hcm::basicfreeways::mixed_flow::MixedFlowSegment;
 use pyo3::exceptions::PyValueError;
@@ -62,8 +63,37 @@ pub fn analyze_composite_grade(config_json: &str) -> PyResult<String> {
         .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
 }
 
+/// Locate and evaluate a truck climbing lane on a composite grade.
+///
+/// Args:
+///     config_json: JSON object with grade, a composite-grade config as taken by
+///         analyze_composite_grade; lanes, the mixed-flow lanes; optionally
+///         speed_reduction, the truck speed loss that warrants a climbing lane (mi/h,
+///         default 10); and optionally climbing_lane, an object with start and end
+///         stations (mi from the foot of the grade).
+///
+/// Returns:
+///     JSON object with the entry and threshold speeds, the warranted station ranges,
+///     and the capacity, speed and density of each section without the climbing lane
+///     and, when one was given, with it.
+///
+/// Raises:
+///     ValueError: if the config is malformed, the inputs are out of range, or the
+///         chain reaches a grade or entry speed outside the digitised truck curves.
+#[pyfunction]
+pub fn analyze_climbing_lane(config_json: &str) -> PyResult<String> {
+    let study = ClimbingLaneStudy::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid climbing-lane config: {e}")))?;
+    let result = study
+        .analyze()
+        .map_err(|e| PyValueError::new_err(format!("climbing-lane analysis failed: {e}")))?;
+    serde_json::to_string(&result)
+        .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
 pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(analyze_mixed_flow, m)?)?;
     m.add_function(wrap_pyfunction!(analyze_composite_grade, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_climbing_lane, m)?)?;
     Ok(())
 }
@@ -51,10 +51,21 @@ fn transportations_library(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()>
           two-lane and multilane highways)\n\
         - BasicFreeways, ManagedLanes: HCM Chapter 12 (basic freeway, multilane\n\
           highway, and basic managed lane segments)\n\
+        - analyze_paired_managed_lane: HCM Chapter 12 managed lane with the adjacent\n\
+          general purpose lanes and their friction effect\n\
+        - analyze_multilane_facility: HCM Chapter 12 multilane highway corridors\n\
+          (typed median, TWLTL, access points, changing cross-sections)\n\
+        - service_volume_table: HCM Chapter 12 generalized service volume tables\n\
+          (basic freeway, multilane, weaving and ramp segments)\n\
         - analyze_mixed_flow, analyze_composite_grade: HCM Chapters 26 and 25\n\
           (mixed-flow model for single and composite grades)\n\
+        - analyze_climbing_lane: truck climbing lanes on composite grades\n\
         - WeavingSegment: HCM Chapter 13 (freeway weaving segments)\n\
+        - design_weaving_segment: HCM Chapter 13 weaving design search (length, lanes\n\
+          and configuration for a target LOS)\n\
         - RampSegment: HCM Chapter 14 (freeway merge and diverge segments)\n\
+        - design_ramp_lane: HCM Chapter 14 acceleration and deceleration lane design\n\
+          (shortest lane for a target LOS or v/c, with its sensitivity curve)\n\
         - FreewayFacility: HCM Chapter 10 (freeway facilities core methodology)\n\
         - FreewayReliability: HCM Chapter 11 (freeway reliability analysis)\n\
         - UrbanFacility: HCM Chapter 16 (urban street facilities)\n\
@@ -64,6 +75,10 @@ fn transportations_library(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()>
         - Twsc: HCM Chapter 20 (two-way STOP-controlled intersections)\n\
         - Awsc: HCM Chapter 21 (all-way STOP-controlled intersections)\n\
         - Roundabouts: HCM Chapter 22 (roundabouts)\n\
+        - MultiLegRoundabout: HCM Chapter 22 roundabouts with any number of legs\n\
+          (O-D demand matrix; three, five or six legs)\n\
+        - design_roundabout_lanes: HCM Chapter 22 entry lane-use design (lane count,\n\
+          lane assignment and bypass lane minimizing delay or balancing lane v/c)\n\
         - Interchange: HCM Chapter 23 (interchange ramp terminals)\n\
         - ExclusivePedestrianFacility, SharedUsePathPedestrian, OffStreetBicycleFacility:\n\
           HCM Chapter 24 (off-street pedestrian and bicycle facilities)\n\n\
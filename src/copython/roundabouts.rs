@@ -1,5 +1,7 @@
 //! Python bindings for HCM Chapter 22 (Roundabouts).
 
+use crate::hcm::roundabouts::design::LaneUseDesignSpec;
+use crate::hcm::roundabouts::multileg::MultiLegRoundabout as LibMultiLegRoundabout;
 use crate::hcm::roundabouts::roundabouts::{
     Leg, RoundaboutApproach, RoundaboutLaneResult, Roundabouts as LibRoundabouts,
 };
@@ -90,7 +92,7 @@ impl Roundabouts {
         &self,
         entry: &str,
         lane: usize,
-    ) -> PyResult<(f64, f64, f64, f64, String, f64)> {
+    ) -> PyResult<LaneTuple> {
         let a = self.approach(entry)?;
         let l = a
             .lanes
@@ -103,7 +105,7 @@ impl Roundabouts {
     pub fn get_bypass_result(
         &self,
         entry: &str,
-    ) -> PyResult<Option<(f64, f64, f64, f64, String, f64)>> {
+    ) -> PyResult<Option<LaneTuple>> {
         Ok(self.approach(entry)?.bypass_lane.as_ref().map(lane_tuple))
     }
 
@@ -129,6 +131,14 @@ impl Roundabouts {
         self.inner.intersection_los.map(|c| c.to_string())
     }
 
+    /// The equivalent N-leg roundabout (legs "NB", "WB", "SB", "EB" in
+    /// circulating order), not yet analyzed.
+    pub fn to_multileg(&self) -> MultiLegRoundabout {
+        MultiLegRoundabout {
+            inner: self.inner.to_multileg(),
+        }
+    }
+
     /// Full analysis (inputs + results) as JSON.
     pub fn to_json(&self) -> PyResult<String> {
         self.inner
@@ -144,7 +154,10 @@ impl Roundabouts {
     }
 }
 
-fn lane_tuple(l: &RoundaboutLaneResult) -> (f64, f64, f64, f64, String, f64) {
+/// (flow veh/h, capacity veh/h, v/c, delay s/veh, LOS, Q95 veh).
+type LaneTuple = (f64, f64, f64, f64, String, f64);
+
+fn lane_tuple(l: &RoundaboutLaneResult) -> LaneTuple {
     (
         l.flow_veh,
         l.capacity_veh,
@@ -161,7 +174,157 @@ impl Roundabouts {
     }
 }
 
+/// HCM Chapter 22 analysis of a roundabout with any number of legs.
+///
+/// Construct from a JSON configuration with `legs` (in the order
+/// circulating traffic passes them; each a `name` plus the per-entry
+/// fields of the four-leg format except the movement volumes) and
+/// `demand`, the origin-destination matrix in veh/h (diagonal = U-turns).
+/// Legs are addressed by name, or by position as a string ("0", "1", ...).
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct MultiLegRoundabout {
+    pub inner: LibMultiLegRoundabout,
+}
+
+#[pymethods]
+impl MultiLegRoundabout {
+    /// Create an N-leg roundabout analysis from a JSON configuration string.
+    #[new]
+    pub fn new(config_json: &str) -> PyResult<Self> {
+        let inner = LibMultiLegRoundabout::from_json(config_json)
+            .map_err(|e| PyValueError::new_err(format!("invalid roundabout JSON: {e}")))?;
+        Ok(MultiLegRoundabout { inner })
+    }
+
+    /// Validate the inputs and run the complete HCM Chapter 22 procedure.
+    pub fn analyze(&mut self) -> PyResult<()> {
+        self.inner.analyze().map_err(PyValueError::new_err)
+    }
+
+    /// Leg names in circulating order.
+    #[getter]
+    pub fn get_leg_names(&self) -> Vec<String> {
+        self.inner.legs.iter().map(|l| l.name.clone()).collect()
+    }
+
+    /// Conflicting circulating flow in front of a leg's entry, pc/h
+    /// (generalized Equation 22-11).
+    pub fn get_circulating_flow_pce(&self, leg: &str) -> PyResult<Option<f64>> {
+        Ok(self.approach(leg)?.circulating_flow_pce)
+    }
+
+    /// Total flow leaving on a leg, pc/h (generalized Equation 22-12).
+    pub fn get_exiting_flow_pce(&self, leg: &str) -> PyResult<Option<f64>> {
+        let i = self.index(leg)?;
+        Ok(self.inner.exiting_flow_pce.get(i).copied())
+    }
+
+    /// (flow veh/h, capacity veh/h, v/c, delay s/veh, LOS, Q95 veh) of an
+    /// entry lane (0 = left/only lane).
+    pub fn get_lane_result(
+        &self,
+        leg: &str,
+        lane: usize,
+    ) -> PyResult<LaneTuple> {
+        let l = self
+            .approach(leg)?
+            .lanes
+            .get(lane)
+            .ok_or_else(|| PyValueError::new_err(format!("no lane {lane} on {leg}")))?;
+        Ok(lane_tuple(l))
+    }
+
+    /// Bypass-lane result tuple, if the leg has a bypass lane.
+    pub fn get_bypass_result(
+        &self,
+        leg: &str,
+    ) -> PyResult<Option<LaneTuple>> {
+        Ok(self.approach(leg)?.bypass_lane.as_ref().map(lane_tuple))
+    }
+
+    /// Approach control delay, s/veh (Equation 22-18, bypass included).
+    pub fn get_approach_delay(&self, leg: &str) -> PyResult<Option<f64>> {
+        Ok(self.approach(leg)?.control_delay)
+    }
+
+    /// Approach LOS letter (Exhibit 22-8).
+    pub fn get_approach_los(&self, leg: &str) -> PyResult<Option<String>> {
+        Ok(self.approach(leg)?.los.map(|c| c.to_string()))
+    }
+
+    /// Intersection control delay, s/veh (Equation 22-19).
+    #[getter]
+    pub fn get_intersection_delay(&self) -> Option<f64> {
+        self.inner.intersection_delay
+    }
+
+    /// Intersection LOS letter (Exhibit 22-8).
+    #[getter]
+    pub fn get_intersection_los(&self) -> Option<String> {
+        self.inner.intersection_los.map(|c| c.to_string())
+    }
+
+    /// Full analysis (inputs + results) as JSON.
+    pub fn to_json(&self) -> PyResult<String> {
+        self.inner
+            .to_json()
+            .map_err(|e| PyValueError::new_err(format!("serialize roundabout: {e}")))
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "MultiLegRoundabout(legs={}, intersection_delay={:?}, los={:?})",
+            self.inner.n_legs(),
+            self.inner.intersection_delay,
+            self.inner.intersection_los
+        )
+    }
+}
+
+impl MultiLegRoundabout {
+    fn index(&self, leg: &str) -> PyResult<usize> {
+        self.inner
+            .leg_index(leg)
+            .or_else(|| leg.parse::<usize>().ok().filter(|&i| i < self.inner.n_legs()))
+            .ok_or_else(|| PyValueError::new_err(format!("no leg {leg}")))
+    }
+
+    fn approach(&self, leg: &str) -> PyResult<&RoundaboutApproach> {
+        Ok(&self.inner.legs[self.index(leg)?].approach)
+    }
+}
+
+/// Search the lane count, lane assignment and bypass lane of every roundabout entry.
+///
+/// Args:
+///     config_json: JSON object with "template" (an N-leg roundabout in the `MultiLegRoundabout`
+///         JSON form; a four-leg one comes from `Roundabouts.to_multileg().to_json()`) and
+///         optional "objective" ("MinDelay", the default, or "BalanceVc"), "entry_lanes" (e.g.
+///         [1, 2]; default: each entry's own), "lane_assignments" (default all five of
+///         Exhibit 22-14), "bypass_types" (default ["None", "Yielding", "NonYielding"]) and
+///         "max_vc" (default 1.0).
+///
+/// Returns:
+///     JSON string with one entry per leg: every candidate configuration with its lane results,
+///     approach delay, highest lane v/c and feasibility, and the index of the best feasible one
+///     ("best", null when none is feasible). "design" holds the analyzed roundabout with every
+///     entry at its best, or null.
+///
+/// Raises:
+///     ValueError: if the config is malformed, the template is invalid, a lane count is not 1
+///         or 2, a list is empty where a candidate needs it, or max_vc is not positive.
+#[pyfunction]
+pub fn design_roundabout_lanes(config_json: &str) -> PyResult<String> {
+    let spec = LaneUseDesignSpec::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid roundabout design config: {e}")))?;
+    let result = spec.search().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result).map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
 pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Roundabouts>()?;
+    m.add_class::<MultiLegRoundabout>()?;
+    m.add_function(wrap_pyfunction!(design_roundabout_lanes, m)?)?;
     Ok(())
 }
@@ -1,8 +1,18 @@
+use crate::hcm::basicfreeways::BasicFreeways as LibBasicFreeways;
+use crate::hcm::utils::geometric::HorizontalCurve as LibHorizontalCurve;
 use crate::hcm::twolanehighways::{
+    BicycleConditions as LibBicycleConditions,
+    BicycleFacility as LibBicycleFacility,
     BicycleLOS as LibBicycleLOS,
+    BidirectionalTwoLane as LibBidirectionalTwoLane,
+    PassingLaneOptimizer as LibPassingLaneOptimizer,
+    PlacedCurve as LibPlacedCurve,
     Segment as LibSegment,
     SubSegment as LibSubSegment,
+    TwoLaneAlignment as LibTwoLaneAlignment,
+    TwoLaneDemandProfile as LibTwoLaneDemandProfile,
     TwoLaneHighways as LibTwoLaneHighways,
+    TwoLaneReliability as LibTwoLaneReliability,
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -373,6 +383,40 @@ impl Segment {
         self.inner.get_hor_class()
     }
 
+    /// Replace the subsegments with tangents and the given horizontal curves.
+    ///
+    /// Each curve's Exhibit 15-22 class comes from its radius and
+    /// superelevation, and `is_hc` is set so the curves slow the segment.
+    ///
+    /// Args:
+    ///     curves_json: JSON list of curves, each `{"start_ft", "radius", "length",
+    ///         "superelevation"}` in feet and percent, with `start_ft` measured
+    ///         from the start of the segment and optional `spiral_length`.
+    ///
+    /// Returns:
+    ///     JSON list of `CurveSpeedFlag`s, one per curve below the Green Book
+    ///     minimum radius at the posted speed limit (`curve`, `start_ft`,
+    ///     `radius_ft`, `min_radius_ft`, `speed_limit`).
+    ///
+    /// Raises:
+    ///     ValueError: if a curve is malformed, overlaps another or runs past the segment.
+    pub fn set_curves(&mut self, curves_json: &str) -> PyResult<String> {
+        let curves: Vec<CurveInput> = serde_json::from_str(curves_json)
+            .map_err(|e| PyValueError::new_err(format!("invalid curves: {e}")))?;
+        let curves = curves
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| {
+                LibHorizontalCurve::new(c.radius, c.length, c.superelevation, c.spiral_length)
+                    .map(|curve| LibPlacedCurve { start_ft: c.start_ft, curve })
+                    .map_err(|e| PyValueError::new_err(format!("invalid curve {i}: {e}")))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let flags = self.inner.set_curves(&curves).map_err(PyValueError::new_err)?;
+        serde_json::to_string(&flags)
+            .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+    }
+
     pub fn __repr__(&self) -> String {
         format!(
             "Segment(passing_type={}, length={:.2}, grade={:.1}, volume={:.0}, capacity={})",
@@ -528,6 +572,67 @@ impl TwoLaneHighways {
         self.inner.determine_facility_los(fd, s_pl)
     }
 
+    /// Run Chapter 15 Steps 1-11 over every segment.
+    ///
+    /// Stores the computed values on the segments, as the step methods do.
+    ///
+    /// Returns:
+    ///     JSON `TwoLaneFacilityResult` - facility `total_length`, `travel_time_h`,
+    ///     `average_speed`, `follower_density`, `posted_speed_limit`, `over_capacity`
+    ///     and `los`, plus `segments` with each segment's Step 1-10 values
+    ///     (`flow_rate`, `capacity`, `ffs`, `avg_speed`, `percent_followers`,
+    ///     `follower_density`, `follower_density_mid`, `adjusted_follower_density`,
+    ///     `los`, ...).
+    pub fn analyze(&mut self) -> PyResult<String> {
+        serde_json::to_string(&self.inner.analyze())
+            .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+    }
+
+    /// Check every input against its HCM domain, then run Steps 1-11.
+    ///
+    /// Returns:
+    ///     JSON `TwoLaneCheckedResult` - `result` (as from `analyze`) and
+    ///     `defaults`, each assumed input as `{"segment", "subsegment",
+    ///     "field", "value"}` with `segment` null for facility inputs.
+    ///
+    /// Raises:
+    ///     ValueError: naming the segment, the field and the HCM exhibit whose
+    ///         domain the input is outside.
+    pub fn try_analyze(&mut self) -> PyResult<String> {
+        let checked = self.inner.try_analyze().map_err(|e| PyValueError::new_err(e.to_string()))?;
+        serde_json::to_string(&checked)
+            .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+    }
+
+    /// Run Chapter 15 Steps 1-11 for every period of a demand profile.
+    ///
+    /// Each period scales every segment's `volume` and `volume_op` by its
+    /// entering flow rate over the first segment's `volume / phf`. The
+    /// facility itself is not modified.
+    ///
+    /// Args:
+    ///     profile_json: JSON `TwoLaneDemandProfile` - `period` (`{"duration_h",
+    ///         "num_periods"}`), `demand` (`{"period_volumes": [...]}`, veh per period
+    ///         entering the facility), and optional `opposing` (same shape) and
+    ///         `start_h`.
+    ///
+    /// Returns:
+    ///     JSON `TwoLaneProfileResults` - `periods` (each with `start_h`,
+    ///     `entry_flow_rate`, `demand_multiplier`, `vmt`, `vht` and the facility
+    ///     `result`), `total_vmt`, `total_vht`, `average_speed`,
+    ///     `vmt_weighted_follower_density`, `vmt_weighted_percent_followers`,
+    ///     `hours_by_los` (A-F), `hours_over_capacity` and `worst_period`.
+    ///
+    /// Raises:
+    ///     ValueError: if the profile is malformed or fails validation.
+    pub fn analyze_periods(&self, profile_json: &str) -> PyResult<String> {
+        let profile: LibTwoLaneDemandProfile = serde_json::from_str(profile_json)
+            .map_err(|e| PyValueError::new_err(format!("invalid two-lane demand profile: {e}")))?;
+        let results = self.inner.analyze_periods(&profile).map_err(PyValueError::new_err)?;
+        serde_json::to_string(&results)
+            .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+    }
+
 
     pub fn __repr__(&self) -> String {
         format!(
@@ -688,8 +793,11 @@ impl BicycleLOS {
     }
 
     /// The Exhibit 15-7 LOS letter for this score.
-    pub fn determine_bicycle_los(&self) -> char {
-        self.inner.determine_bicycle_los()
+    ///
+    /// Raises:
+    ///     ValueError: if `speed_limit` is 20 mi/h or below, or another input is out of range.
+    pub fn determine_bicycle_los(&self) -> PyResult<char> {
+        self.inner.determine_bicycle_los().map_err(PyValueError::new_err)
     }
 
     /// Run the whole Section 4 chain and return the result as JSON.
@@ -700,8 +808,12 @@ impl BicycleLOS {
     /// Returns:
     ///     JSON `BicycleLOSResult` - `flow_rate_outside_lane`, `effective_width`,
     ///     `effective_speed_factor`, `blos_score` and the Exhibit 15-7 `los` letter.
+    ///
+    /// Raises:
+    ///     ValueError: if `speed_limit` is 20 mi/h or below, or another input is out of range.
     pub fn analyze(&self) -> PyResult<String> {
-        serde_json::to_string(&self.inner.analyze())
+        let result = self.inner.analyze().map_err(PyValueError::new_err)?;
+        serde_json::to_string(&result)
             .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
     }
 
@@ -718,12 +830,13 @@ impl BicycleLOS {
     }
 
     pub fn __str__(&self) -> String {
-        format!(
-            "Bicycle LOS: score {:.2}, LOS {} ({:.1} ft effective width)",
-            self.calculate_blos_score(),
-            self.determine_bicycle_los(),
-            self.calculate_effective_width()
-        )
+        match self.inner.analyze() {
+            Ok(r) => format!(
+                "Bicycle LOS: score {:.2}, LOS {} ({:.1} ft effective width)",
+                r.blos_score, r.los, r.effective_width
+            ),
+            Err(e) => format!("Bicycle LOS: {e}"),
+        }
     }
 }
 
@@ -747,12 +860,186 @@ impl BicycleLOS {
 ///     15-7 LOS letter.
 ///
 /// Raises:
-///     ValueError: if the config is malformed or a field is missing.
+///     ValueError: if the config is malformed, a field is missing, or `speed_limit` is
+///         20 mi/h or below (where Equation 15-46 has no value) or another input is out of range.
 #[pyfunction]
 pub fn analyze_bicycle_los(config_json: &str) -> PyResult<String> {
     let blos: LibBicycleLOS = serde_json::from_str(config_json)
         .map_err(|e| PyValueError::new_err(format!("invalid bicycle LOS config: {e}")))?;
-    serde_json::to_string(&blos.analyze())
+    let result = blos.analyze().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result)
+        .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// A horizontal curve for `Segment.set_curves`, class derived on construction.
+#[derive(serde::Deserialize)]
+struct CurveInput {
+    start_ft: f64,
+    radius: f64,
+    length: f64,
+    superelevation: f64,
+    #[serde(default)]
+    spiral_length: f64,
+}
+
+/// Where a bicycle facility's segments come from.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BicycleFacilitySource {
+    TwoLane(LibTwoLaneHighways),
+    Multilane(Vec<LibBasicFreeways>),
+}
+
+#[derive(serde::Deserialize)]
+struct BicycleFacilityConfig {
+    #[serde(flatten)]
+    source: BicycleFacilitySource,
+    conditions: Vec<LibBicycleConditions>,
+}
+
+/// Bicycle LOS over a sequence of two-lane or multilane highway segments.
+///
+/// Lane and shoulder width, posted speed, directional lanes, volume, PHF and heavy
+/// vehicle share come from the motorized inputs; the facility score is the
+/// length-weighted mean of the segment scores.
+///
+/// Args:
+///     config_json: JSON with either `two_lane` (the `TwoLaneHighways` fixture format) or
+///         `multilane` (a list of Chapter 12 `BasicFreeways` segments with `highway_type`
+///         `"multilane"`), and `conditions` - one `{"pavement_condition",
+///         "pct_on_highway_parking"}` for the whole facility or one per segment.
+///
+/// Returns:
+///     JSON `BicycleFacilityResult` - `total_length`, the length-weighted `blos_score`,
+///     the Exhibit 15-7 `los`, and each segment's `BicycleLOSResult`.
+///
+/// Raises:
+///     ValueError: if the config is malformed, or a segment's inputs are out of range
+///         (including a posted speed of 20 mi/h or below).
+#[pyfunction]
+pub fn analyze_bicycle_facility(config_json: &str) -> PyResult<String> {
+    let config: BicycleFacilityConfig = serde_json::from_str(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid bicycle facility config: {e}")))?;
+    let facility = match &config.source {
+        BicycleFacilitySource::TwoLane(h) => LibBicycleFacility::from_two_lane(h, &config.conditions),
+        BicycleFacilitySource::Multilane(s) => LibBicycleFacility::from_multilane(s, &config.conditions),
+    }
+    .map_err(PyValueError::new_err)?;
+    let result = facility.analyze().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result)
+        .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// Travel time reliability of a two-lane highway facility over a reporting period.
+///
+/// Generates demand (Exhibit 11-19 rural ratios), weather, incident and work zone
+/// scenarios and evaluates each with Chapter 15 Steps 1-11.
+///
+/// Args:
+///     config_json: JSON `TwoLaneReliability` - `highway` (the `TwoLaneHighways` fixture
+///         format) and an optional `config` with `seed_month`, `seed_day`, `months`,
+///         `days`, `weather` (`[{"condition", "probability"}]`), `incidents`,
+///         `work_zones` and `analysis_period_h`.
+///
+/// Returns:
+///     JSON `TwoLaneReliabilityResults` - every scenario, the VMT-weighted TTI
+///     metrics, the follower density distribution, and the share of the period
+///     over capacity.
+///
+/// Raises:
+///     ValueError: if the config is malformed or fails validation.
+#[pyfunction]
+pub fn analyze_two_lane_reliability(config_json: &str) -> PyResult<String> {
+    let rel: LibTwoLaneReliability = serde_json::from_str(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid two-lane reliability config: {e}")))?;
+    let results = rel.run().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&results)
+        .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// Search passing lane placements on a two-lane highway facility.
+///
+/// Every combination of candidate segments and lengths within the budget is
+/// evaluated with Chapter 15 Steps 1-11 and ranked.
+///
+/// Args:
+///     config_json: JSON `PassingLaneOptimizer` - `highway` (the `TwoLaneHighways`
+///         fixture format), `candidates` (`[{"segment", "lengths"}]`, lengths in mi),
+///         `budget` (`{"Count": n}` or `{"LaneMiles": x}`), and optional `objective`
+///         (`"FollowerDensity"` or `"LevelOfService"`) and `constraints`
+///         (`min_spacing_mi`, `max_evaluations`, `max_plans`).
+///
+/// Returns:
+///     JSON `PassingLaneResults` - the `baseline` facility result, the ranked
+///     `plans` (placements, lane-miles, follower density reduction, LOS letters
+///     gained and the full facility result), and the number `evaluated`.
+///
+/// Raises:
+///     ValueError: if the config is malformed or fails validation.
+#[pyfunction]
+pub fn optimize_passing_lanes(config_json: &str) -> PyResult<String> {
+    let opt: LibPassingLaneOptimizer = serde_json::from_str(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid passing lane optimizer config: {e}")))?;
+    let results = opt.run().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&results)
+        .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// Cut a two-lane highway into Chapter 15 segments from alignment data.
+///
+/// Boundaries go at passing type and demand changes and vertical class
+/// changes; short pieces are combined, long ones divided, and horizontal
+/// curves become subsegments.
+///
+/// Args:
+///     config_json: JSON `TwoLaneAlignment` - `length_ft`, station-referenced
+///         `grades` (`[{"start_ft", "grade"}]`), `curves` (`[{"start_ft", "end_ft",
+///         "radius_ft", "superelevation"}]`), `passing` (`[{"start_ft", "end_ft",
+///         "passing_type"}]`, 1 = zone, 2 = lane), `demand` (`[{"start_ft", "volume",
+///         "opposing_volume", "phf", "phv"}]`), `spl`, and optional `phf`, `phv`,
+///         `lane_width`, `shoulder_width`, `apd` and `pmhvfl`.
+///
+/// Returns:
+///     JSON `TwoLaneSegmentation` - the `highway` in the `TwoLaneHighways`
+///     fixture format, the `segments` as cut (stations, grade, class, curves),
+///     and the `decisions` taken (`station_ft`, `rule`, `detail`).
+///
+/// Raises:
+///     ValueError: if the config is malformed or fails validation.
+#[pyfunction]
+pub fn segment_two_lane_alignment(config_json: &str) -> PyResult<String> {
+    let alignment: LibTwoLaneAlignment = serde_json::from_str(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid two-lane alignment config: {e}")))?;
+    let segmentation = alignment.build().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&segmentation)
+        .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
+/// Analyze both directions of a two-lane highway over one alignment.
+///
+/// Each direction's opposing flow comes from the other direction's demand,
+/// and a passing zone facing an opposing passing lane is analyzed as
+/// Passing Constrained.
+///
+/// Args:
+///     config_json: JSON `BidirectionalTwoLane` - `length_ft`, `grades` and `curves`
+///         as in `segment_two_lane_alignment` (stations and grades for the increasing
+///         direction), `increasing` and `decreasing` (each `{"passing", "demand",
+///         "spl"}` on the same stationing, `opposing_volume` left at 0), `spl`, and
+///         optional `phf`, `phv`, `lane_width`, `shoulder_width`, `apd` and `pmhvfl`.
+///
+/// Returns:
+///     JSON `BidirectionalResults` - `increasing` and `decreasing`, each with its
+///     `segmentation` (in its own direction of travel) and facility `result`.
+///
+/// Raises:
+///     ValueError: if the config is malformed or fails validation.
+#[pyfunction]
+pub fn analyze_two_lane_bidirectional(config_json: &str) -> PyResult<String> {
+    let corridor: LibBidirectionalTwoLane = serde_json::from_str(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid two-lane bidirectional config: {e}")))?;
+    let results = corridor.run().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&results)
         .map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
 }
 
@@ -762,5 +1049,10 @@ pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<TwoLaneHighways>()?;
     m.add_class::<BicycleLOS>()?;
     m.add_function(wrap_pyfunction!(analyze_bicycle_los, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_bicycle_facility, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_two_lane_reliability, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize_passing_lanes, m)?)?;
+    m.add_function(wrap_pyfunction!(segment_two_lane_alignment, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_two_lane_bidirectional, m)?)?;
     Ok(())
 }
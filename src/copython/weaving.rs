@@ -1,6 +1,7 @@
 //! Python bindings for HCM Chapter 13 (Freeway Weaving Segments).
 
 use crate::hcm::common::HcmVersion;
+use crate::hcm::weaving::design::WeavingDesignSpec;
 use crate::hcm::weaving::weaving::{
     cross_weave_gp_capacity as lib_cross_weave_gp_capacity, service_flow_rate_ideal as lib_sfi,
     service_volumes as lib_service_volumes, DemandSplit, FacilityType, TerrainType,
@@ -446,10 +447,37 @@ pub fn py_service_volumes(sfi: f64, f_hv: f64, phf: f64, k: f64, d: f64) -> (f64
     (s.sfi, s.sf, s.sv, s.dsv)
 }
 
+/// Search weaving configurations, lane counts and lengths for a target LOS (Chapter 27,
+/// Example Problem 4 design use).
+///
+/// Args:
+///     config_json: JSON object with "template" (a weaving segment in the JSON form of the
+///         example-case fixtures), "lanes" (candidate lane counts) and optional "target_los"
+///         (default "D"), "versions" (default ["7", "7.1"]), "configurations", "min_length"
+///         (default 300 ft) and "length_step" (default 50 ft).
+///
+/// Returns:
+///     JSON string with the configurations tried and one candidate per edition, configuration
+///     and lane count: its L_MAX, the shortest length meeting the target ("design"), the
+///     longest length tried when none meets it ("target_not_reached"), and whether L_MAX is so
+///     short that it is to be analyzed as a separate merge and diverge ("merge_diverge").
+///
+/// Raises:
+///     ValueError: if the config is malformed, the target is F, or a lane count, N_WL, length
+///         or PHF is out of range.
+#[pyfunction]
+pub fn design_weaving_segment(config_json: &str) -> PyResult<String> {
+    let spec = WeavingDesignSpec::from_json(config_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid weaving design config: {e}")))?;
+    let result = spec.search().map_err(PyValueError::new_err)?;
+    serde_json::to_string(&result).map_err(|e| PyValueError::new_err(format!("serialize error: {e}")))
+}
+
 pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<WeavingSegment>()?;
     m.add_function(wrap_pyfunction!(py_cross_weave_gp_capacity, m)?)?;
     m.add_function(wrap_pyfunction!(py_service_flow_rate_ideal, m)?)?;
     m.add_function(wrap_pyfunction!(py_service_volumes, m)?)?;
+    m.add_function(wrap_pyfunction!(design_weaving_segment, m)?)?;
     Ok(())
 }
@@ -1,5 +1,5 @@
 use crate::utils::math;
-use crate::hcm::common::{CommonSegment, LevelOfService, CityType};
+use crate::hcm::common::{CommonSegment, LevelOfService, CityType, MedianType};
 use crate::hcm::common::los_tables::los_basic_freeway;
 use serde::{Deserialize, Serialize};
 use crate::hcm::common::pce_table::PceTable;
@@ -54,6 +54,12 @@ pub fn basic_segment_capacity(ffs: f64) -> f64 {
     (2200.0 + 10.0 * (ffs - 50.0)).min(MAX_CAPACITY_BASIC_FREEWAY)
 }
 
+/// Equation 12-7 multilane highway capacity (pc/h/ln), capped at the Exhibit 12-4 maximum of
+/// 2,300 pc/h/ln. Reads the unadjusted FFS for the same reason as [`basic_segment_capacity`].
+pub fn multilane_segment_capacity(ffs: f64) -> f64 {
+    (1900.0 + 20.0 * (ffs - 45.0)).min(MAX_CAPACITY_MULTILANE)
+}
+
 /// Equation 12-1 speed-flow relationship for a basic segment (mi/h).
 ///
 /// ```text
@@ -103,6 +109,60 @@ pub fn bffs_from_speed_limit(speed_limit: f64) -> f64 {
     speed_limit + if speed_limit >= 50.0 { 5.0 } else { 7.0 }
 }
 
+/// Exhibit 12-20 FFS adjustment for average lane width (mi/h). Lanes narrower than 10 ft are
+/// outside the exhibit.
+pub fn lane_width_adjustment(lane_width: f64) -> Result<f64, String> {
+    match lane_width {
+        lw if lw >= 12.0 => Ok(0.0),
+        lw if (11.0..12.0).contains(&lw) => Ok(1.9),
+        lw if (10.0..11.0).contains(&lw) => Ok(6.6),
+        lw => Err(format!("Average lane width is infeasible {lw}")),
+    }
+}
+
+/// Exhibit 12-22 FFS adjustment for total lateral clearance on a multilane highway (mi/h),
+/// interpolated between the tabulated clearances. The four-lane column applies to two lanes
+/// in the direction of travel and the six-lane column to more.
+pub fn total_lateral_clearance_adjustment(tlc: f64, lanes: u32) -> f64 {
+    // Four-Lane Highways (2 lanes in one direction) - Exhibit 12-22
+    // TLC: 12→0.0, 10→0.4, 8→0.9, 6→1.3, 4→1.8, 2→3.6, 0→5.4
+    let four_lane_values: [(f64, f64); 7] = [
+        (12.0, 0.0), (10.0, 0.4), (8.0, 0.9), (6.0, 1.3),
+        (4.0, 1.8), (2.0, 3.6), (0.0, 5.4)
+    ];
+
+    // Six-Lane Highways (3 lanes in one direction) - Exhibit 12-22
+    // TLC: 12→0.0, 10→0.4, 8→0.9, 6→1.3, 4→1.7, 2→2.8, 0→3.9
+    let six_lane_values: [(f64, f64); 7] = [
+        (12.0, 0.0), (10.0, 0.4), (8.0, 0.9), (6.0, 1.3),
+        (4.0, 1.7), (2.0, 2.8), (0.0, 3.9)
+    ];
+
+    let values = if lanes <= 2 {
+        &four_lane_values
+    } else {
+        &six_lane_values
+    };
+
+    BasicFreeways::interpolate_adjustment(tlc, values)
+}
+
+/// Exhibit 12-23 FFS adjustment for median type (mi/h): 1.6 undivided, 0.0 for a TWLTL or
+/// any divided median.
+pub fn median_type_adjustment(median: MedianType) -> f64 {
+    match median {
+        MedianType::Undivided => 1.6,
+        _ => 0.0,
+    }
+}
+
+/// Exhibit 12-24 FFS adjustment for access point density (mi/h): 0.25 mi/h per access point
+/// per mile, reaching 10.0 at 40 points/mi. The exhibit's rows lie on this line, so it is the
+/// interpolation the exhibit note asks for.
+pub fn access_point_density_adjustment(apd: f64) -> f64 {
+    (0.25 * apd).min(10.0)
+}
+
 /// Default values from Exhibit 12-18
 /// Required Input Data, Potential Data Sources, and Default Values
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -483,10 +543,8 @@ impl BasicFreeways {
         // let avg_lw = self.segments.iter().map(|s| s.lane_width.unwrap_or(12.0)).sum::<f64>() / self.segments.len() as f64;
 
         match self.lw {
-            Some(lw) if lw >= 12.0 => Ok(0.0),
-            Some(lw) if lw >= 11.0 && lw < 12.0 => Ok(1.9),
-            Some(lw) if lw >= 10.0 && lw < 11.0 => Ok(6.6),
-            _ => Err(format!("Average lane width is infeasible {:?}", self.lw)),
+            Some(lw) => lane_width_adjustment(lw),
+            None => Err("Average lane width is infeasible None".to_string()),
         }
     }
 
@@ -513,28 +571,7 @@ impl BasicFreeways {
         let lc_l = self.lc_l.min(6.0);
         let tlc = lc_r + lc_l;
 
-        // Four-Lane Highways (2 lanes in one direction) - Exhibit 12-22
-        // TLC: 12→0.0, 10→0.4, 8→0.9, 6→1.3, 4→1.8, 2→3.6, 0→5.4
-        let four_lane_values: [(f64, f64); 7] = [
-            (12.0, 0.0), (10.0, 0.4), (8.0, 0.9), (6.0, 1.3),
-            (4.0, 1.8), (2.0, 3.6), (0.0, 5.4)
-        ];
-
-        // Six-Lane Highways (3 lanes in one direction) - Exhibit 12-22
-        // TLC: 12→0.0, 10→0.4, 8→0.9, 6→1.3, 4→1.7, 2→2.8, 0→3.9
-        let six_lane_values: [(f64, f64); 7] = [
-            (12.0, 0.0), (10.0, 0.4), (8.0, 0.9), (6.0, 1.3),
-            (4.0, 1.7), (2.0, 2.8), (0.0, 3.9)
-        ];
-
-        let values = if self.lane_count <= 2 {
-            &four_lane_values
-        } else {
-            &six_lane_values
-        };
-
-        // Interpolate for the given TLC value
-        Ok(Self::interpolate_adjustment(tlc, values))
+        Ok(total_lateral_clearance_adjustment(tlc, self.lane_count))
     }
 
     /// Linear interpolation helper for adjustment factors
@@ -608,9 +645,7 @@ impl BasicFreeways {
     /// Each access point per mile decreases FFS by approximately 0.25 mi/h
     /// Supports interpolation as recommended in the exhibit note
     fn adjustment_access_point_density(&mut self) -> f64 {
-        // Formula: f_A = 0.25 × APD, capped at 10.0 mi/h for APD ≥ 40
-        let apd = self.apd as f64;
-        (0.25 * apd).min(10.0)
+        access_point_density_adjustment(f64::from(self.apd))
     }
 
     /// Alternative access point density adjustment using table lookup with interpolation
@@ -745,7 +780,7 @@ impl BasicFreeways {
         // SAF reaches capacity only through CAF in `estimate_adjusted_capacity`.
         self.capacity = match self.highway_type.as_str() {
             "basic" => basic_segment_capacity(self.ffs),
-            "multilane" => (1900.0 + 20.0 * (self.ffs - 45.0)).min(MAX_CAPACITY_MULTILANE),
+            "multilane" => multilane_segment_capacity(self.ffs),
             _ => 2000.0,
         };
 
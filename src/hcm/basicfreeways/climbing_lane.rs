@@ -0,0 +1,457 @@
+//! Truck climbing lanes on a composite grade.
+//!
+//! `composite_grade` says how fast each class crosses each segment, but not where a climbing
+//! lane belongs or what one would do. This module answers both from the same truck curves:
+//!
+//! - **Warrant.** Trucks enter the grade at the free-flow speed (Chapter 25, Step 3) and their
+//!   spot speeds are followed along the Exhibit 25-20/25-21 curves, station by station rather
+//!   than only at segment ends. Wherever either truck class is more than `speed_reduction`
+//!   below the entry speed is reported as a warranted range. The 10 mi/h default is the Green
+//!   Book criterion for a climbing lane; the HCM itself has no freeway climbing lane method.
+//! - **Effect.** A climbing lane over a station range adds one lane there and moves every
+//!   truck into it. The through lanes then carry an automobile-only stream, at the Equation
+//!   26-10 auto-only speed, and the trucks keep their kinematic space rates from the chain.
+//!   Capacity, the all-vehicle space mean speed and density are reported with and without the
+//!   lane on the same sections.
+//!
+//! The segments are cut at the climbing lane's ends so both cases cover the same sections.
+//! VERIFY-HCM: Equation 25-55 reads each segment's own length, so cutting a grade raises the
+//! capacity of the pieces above that of the uncut grade; the cut applies to both cases alike.
+//!
+//! VERIFY-HCM: the climbing lane's capacity is Equation 25-53 evaluated for an all-truck
+//! stream (`p_T = 1`), beyond the truck shares the mixed-flow model was fitted to, and the
+//! trucks in it see no traffic interaction term.
+
+use serde::{Deserialize, Serialize};
+
+use super::composite_grade::{CompositeGrade, CompositeGradeResult, GradeSegment};
+use super::mixed_flow::{
+    branch_for, breakpoint_auto_only, caf_grade, caf_trucks, capacity_auto_only,
+    speed_auto_only,
+};
+use crate::hcm::common::truck_curves::{self, TruckClass};
+
+/// Truck speed reduction below the entry speed that warrants a climbing lane (mi/h).
+pub const DEFAULT_SPEED_REDUCTION: f64 = 10.0;
+
+/// Stations closer than this are taken as equal (mi, about 5 ft).
+const STATION_TOL_MI: f64 = 0.001;
+
+/// A climbing lane study: where one is warranted on a composite grade, and what one does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClimbingLaneStudy {
+    /// The grade. Its `v_mix` is the demand per mixed-flow lane without a climbing lane.
+    pub grade: CompositeGrade,
+    /// Mixed-flow lanes in the direction of travel.
+    pub lanes: u32,
+    /// Truck speed reduction below the entry speed that warrants a climbing lane (mi/h).
+    #[serde(default = "default_speed_reduction")]
+    pub speed_reduction: f64,
+    /// Climbing lane to evaluate, if any.
+    #[serde(default)]
+    pub climbing_lane: Option<StationRange>,
+}
+
+fn default_speed_reduction() -> f64 {
+    DEFAULT_SPEED_REDUCTION
+}
+
+/// A range of stations measured from the foot of the grade (mi).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StationRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+/// A range where a truck class is slower than the warrant threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarrantedRange {
+    /// Where the first truck class falls below the threshold (mi).
+    pub start: f64,
+    /// Where the last truck class recovers, or the top of the grade (mi).
+    pub end: f64,
+    /// Slowest truck spot speed in the range (mi/h).
+    pub min_speed: f64,
+}
+
+/// One section of the grade, with or without a climbing lane.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClimbingLaneSection {
+    /// Start and end stations (mi).
+    pub start: f64,
+    pub end: f64,
+    /// Grade (percent).
+    pub grade: f64,
+    /// Whether the section has the climbing lane.
+    pub climbing_lane: bool,
+    /// Lanes, including the climbing lane.
+    pub lanes: u32,
+    /// Total demand at which the first lane group reaches capacity (veh/h).
+    pub capacity: f64,
+    /// All-vehicle space mean speed (mi/h).
+    pub speed: f64,
+    /// Density averaged over all lanes (veh/mi/ln).
+    pub density: f64,
+    /// Space mean speeds, autos / SUTs / TTs (mi/h).
+    pub space_speeds: [f64; 3],
+    /// True when demand exceeds the section's capacity.
+    pub oversaturated: bool,
+}
+
+/// The grade's sections under one design.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClimbingLaneScenario {
+    pub sections: Vec<ClimbingLaneSection>,
+    /// Sum of the section travel times (s).
+    pub travel_time: f64,
+    /// Overall all-vehicle space mean speed (mi/h).
+    pub speed: f64,
+    /// True when any section is oversaturated.
+    pub oversaturated: bool,
+}
+
+/// Everything a climbing lane study produces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClimbingLaneResult {
+    /// Truck speed entering the grade (mi/h).
+    pub entry_speed: f64,
+    /// Truck spot speed below which a climbing lane is warranted (mi/h).
+    pub threshold_speed: f64,
+    /// Ranges where either truck class is below the threshold, in station order.
+    pub warrants: Vec<WarrantedRange>,
+    /// The grade as it is.
+    pub without: ClimbingLaneScenario,
+    /// The grade with the climbing lane, when one was given.
+    pub with: Option<ClimbingLaneScenario>,
+}
+
+impl ClimbingLaneStudy {
+    /// Find the warranted ranges and, if a climbing lane is given, compare the grade with and
+    /// without it.
+    pub fn analyze(&self) -> Result<ClimbingLaneResult, String> {
+        self.validate()?;
+        let g = &self.grade;
+        let entry_speed = g.ffs;
+        let threshold_speed = entry_speed - self.speed_reduction;
+        let warrants = self.warrants(&g.analyze()?, threshold_speed)?;
+
+        // Cut the grade at the climbing lane's ends so both cases share the sections.
+        let mut cuts = Vec::new();
+        let mut station = 0.0;
+        let mut sections = Vec::new();
+        for s in &g.segments {
+            let end = station + s.length;
+            let mut from = station;
+            for cut in self.climbing_lane.iter().flat_map(|r| [r.start, r.end]) {
+                if cut > from + STATION_TOL_MI && cut < end - STATION_TOL_MI {
+                    sections.push((from, cut, s.grade));
+                    from = cut;
+                }
+            }
+            sections.push((from, end, s.grade));
+            station = end;
+        }
+        cuts.extend(sections.iter().map(|&(a, b, grade)| GradeSegment { length: b - a, grade }));
+        let cut_grade = CompositeGrade { segments: cuts, ..g.clone() };
+        let chain = cut_grade.analyze()?;
+
+        let n = f64::from(self.lanes);
+        let without = scenario(
+            sections
+                .iter()
+                .zip(&chain.segments)
+                .map(|(&(start, end, grade), r)| ClimbingLaneSection {
+                    start,
+                    end,
+                    grade,
+                    climbing_lane: false,
+                    lanes: self.lanes,
+                    capacity: r.capacity_mix * n,
+                    speed: r.s_mix,
+                    density: g.v_mix / r.s_mix,
+                    space_speeds: r.space_speeds,
+                    oversaturated: g.v_mix > r.capacity_mix,
+                })
+                .collect(),
+        );
+
+        let with = match self.climbing_lane {
+            None => None,
+            Some(lane) => {
+                let mut out = Vec::with_capacity(sections.len());
+                for (k, section) in without.sections.iter().enumerate() {
+                    let mid = 0.5 * (section.start + section.end);
+                    if mid > lane.start && mid < lane.end {
+                        out.push(self.with_lane(section, &chain, k)?);
+                    } else {
+                        out.push(section.clone());
+                    }
+                }
+                Some(scenario(out))
+            }
+        };
+
+        Ok(ClimbingLaneResult { entry_speed, threshold_speed, warrants, without, with })
+    }
+
+    /// Section `k` with every truck in the climbing lane and the automobiles in the through
+    /// lanes.
+    fn with_lane(
+        &self,
+        section: &ClimbingLaneSection,
+        chain: &CompositeGradeResult,
+        k: usize,
+    ) -> Result<ClimbingLaneSection, String> {
+        let g = &self.grade;
+        let r = &chain.segments[k];
+        let n = f64::from(self.lanes);
+        let p_t = g.p_sut + g.p_tt;
+        let p_a = 1.0 - p_t;
+        let demand = g.v_mix * n;
+
+        // Through lanes: an automobile-only stream, Equation 26-10 with CAF_mix = CAF_ao.
+        let capacity_ao = capacity_auto_only(g.ffs);
+        let through_cap = capacity_ao * g.caf_ao;
+        let v_a = g.v_mix * p_a;
+        let bp_ao = breakpoint_auto_only(g.ffs, g.caf_ao);
+        let s_a = speed_auto_only(g.ffs, v_a.min(through_cap), g.caf_ao, capacity_ao, bp_ao);
+
+        // Climbing lane: Equation 25-53 for an all-truck stream.
+        let caf_lane = g.caf_ao - caf_trucks(1.0) - caf_grade(1.0, section.grade, section.end - section.start);
+        if caf_lane <= 0.0 {
+            return Err(format!(
+                "climbing lane CAF came out at {caf_lane:.4} on the {}% grade, which is not a \
+                 usable capacity adjustment",
+                section.grade
+            ));
+        }
+        let lane_cap = capacity_ao * caf_lane;
+        let capacity = if p_t > 0.0 {
+            (n * through_cap / p_a).min(lane_cap / p_t)
+        } else {
+            n * through_cap
+        };
+
+        let tau = p_a * 3600.0 / s_a + g.p_sut * r.tau_s_sut_kin + g.p_tt * r.tau_s_tt_kin;
+        let speed = 3600.0 / tau;
+        Ok(ClimbingLaneSection {
+            climbing_lane: true,
+            lanes: self.lanes + 1,
+            capacity,
+            speed,
+            density: demand / (n + 1.0) / speed,
+            space_speeds: [s_a, 3600.0 / r.tau_s_sut_kin, 3600.0 / r.tau_s_tt_kin],
+            oversaturated: v_a > through_cap || demand * p_t > lane_cap,
+            ..section.clone()
+        })
+    }
+
+    /// Ranges where either truck class's spot speed is below `threshold_speed`.
+    ///
+    /// Within a segment a truck is either decelerating or accelerating along one monotone
+    /// curve, so it crosses the threshold at most once there, and the crossing is found by
+    /// bisection on the curve.
+    fn warrants(
+        &self,
+        chain: &CompositeGradeResult,
+        threshold_speed: f64,
+    ) -> Result<Vec<WarrantedRange>, String> {
+        let ffs_rate = 3600.0 / self.grade.ffs;
+        let threshold = 3600.0 / threshold_speed;
+        let mut ranges: Vec<WarrantedRange> = Vec::new();
+        for class in [TruckClass::Sut, TruckClass::Tt] {
+            let mut station = 0.0;
+            for (j, seg) in self.grade.segments.iter().enumerate() {
+                let entry = match (j, class) {
+                    (0, _) => ffs_rate,
+                    (_, TruckClass::Sut) => chain.segments[j - 1].tau_f_sut_kin,
+                    (_, TruckClass::Tt) => chain.segments[j - 1].tau_f_tt_kin,
+                };
+                let branch = branch_for(class, seg.grade, entry)?;
+                let x0 = truck_curves::spot_distance(class, seg.grade, branch, entry)?;
+                let rate = |x: f64| -> Result<f64, String> {
+                    Ok(truck_curves::spot_rate(class, seg.grade, branch, x0 + x * 5280.0)?
+                        .max(ffs_rate))
+                };
+                let (r0, r1) = (rate(0.0)?, rate(seg.length)?);
+                let (slow0, slow1) = (r0 > threshold, r1 > threshold);
+                if slow0 || slow1 {
+                    let (mut lo, mut hi) = (0.0, seg.length);
+                    if slow0 != slow1 {
+                        for _ in 0..60 {
+                            let mid = 0.5 * (lo + hi);
+                            if (rate(mid)? > threshold) == slow0 {
+                                lo = mid;
+                            } else {
+                                hi = mid;
+                            }
+                        }
+                    }
+                    let (a, b) = match (slow0, slow1) {
+                        (true, true) => (0.0, seg.length),
+                        (false, true) => (hi, seg.length),
+                        _ => (0.0, lo),
+                    };
+                    ranges.push(WarrantedRange {
+                        start: station + a,
+                        end: station + b,
+                        min_speed: 3600.0 / r0.max(r1),
+                    });
+                }
+                station += seg.length;
+            }
+        }
+
+        ranges.sort_by(|a, b| a.start.total_cmp(&b.start));
+        let mut merged: Vec<WarrantedRange> = Vec::new();
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end + STATION_TOL_MI => {
+                    last.end = last.end.max(r.end);
+                    last.min_speed = last.min_speed.min(r.min_speed);
+                }
+                _ => merged.push(r),
+            }
+        }
+        Ok(merged)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.lanes == 0 {
+            return Err("a climbing lane study needs at least one mixed-flow lane".to_string());
+        }
+        if !self.speed_reduction.is_finite()
+            || self.speed_reduction <= 0.0
+            || self.speed_reduction >= self.grade.ffs
+        {
+            return Err(format!(
+                "speed_reduction must be positive and below the FFS, got {} mi/h",
+                self.speed_reduction
+            ));
+        }
+        if let Some(lane) = self.climbing_lane {
+            let total: f64 = self.grade.segments.iter().map(|s| s.length).sum();
+            if !lane.start.is_finite()
+                || !lane.end.is_finite()
+                || lane.start < 0.0
+                || lane.end <= lane.start + STATION_TOL_MI
+                || lane.end > total + STATION_TOL_MI
+            {
+                return Err(format!(
+                    "climbing lane must run forwards within the {total} mi grade, got {} to {} mi",
+                    lane.start, lane.end
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
+fn scenario(sections: Vec<ClimbingLaneSection>) -> ClimbingLaneScenario {
+    let length: f64 = sections.iter().map(|s| s.end - s.start).sum();
+    let travel_time: f64 = sections.iter().map(|s| 3600.0 * (s.end - s.start) / s.speed).sum();
+    ClimbingLaneScenario {
+        oversaturated: sections.iter().any(|s| s.oversaturated),
+        speed: 3600.0 * length / travel_time,
+        travel_time,
+        sections,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chapter 25 Example Problem 11: 1.5 mi at 3%, 2.0 mi at 2%, 1.0 mi at 5%, three lanes.
+    fn ep11(climbing_lane: Option<StationRange>) -> ClimbingLaneStudy {
+        ClimbingLaneStudy {
+            grade: CompositeGrade {
+                ffs: 65.0,
+                v_mix: 1500.0,
+                p_sut: 0.05,
+                p_tt: 0.10,
+                segments: vec![
+                    GradeSegment { length: 1.5, grade: 3.0 },
+                    GradeSegment { length: 2.0, grade: 2.0 },
+                    GradeSegment { length: 1.0, grade: 5.0 },
+                ],
+                caf_ao: 1.0,
+            },
+            lanes: 3,
+            speed_reduction: DEFAULT_SPEED_REDUCTION,
+            climbing_lane,
+        }
+    }
+
+    #[test]
+    fn without_a_lane_the_sections_are_the_composite_grade() {
+        let r = ep11(None).analyze().unwrap();
+        let chain = ep11(None).grade.analyze().unwrap();
+        assert!(r.with.is_none());
+        for (s, c) in r.without.sections.iter().zip(&chain.segments) {
+            assert_eq!(s.speed, c.s_mix);
+            assert_eq!(s.capacity, 3.0 * c.capacity_mix);
+        }
+        assert!((r.without.speed - chain.s_mix_overall).abs() < 1e-9);
+    }
+
+    #[test]
+    fn warrants_follow_the_truck_spot_speeds() {
+        let study = ep11(None);
+        let r = study.analyze().unwrap();
+        // TTs slow below 55 mi/h part way up the 3%, recover on the 2%, and slow again on
+        // the 5%: two ranges, the first starting and ending inside segments.
+        assert_eq!(r.warrants.len(), 2, "{:?}", r.warrants);
+        let first = &r.warrants[0];
+        assert!(first.start > 0.0 && first.start < 1.5, "{first:?}");
+        assert!(first.end > 1.5 && first.end < 3.5, "{first:?}");
+        let chain = study.grade.analyze().unwrap();
+        // TTs leave the 5% segment well under 55 mi/h, so the top of the grade is warranted,
+        // and the slowest speed there is the TTs' kinematic exit speed.
+        let top = r.warrants.last().unwrap();
+        assert!((top.end - 4.5).abs() < 1e-9);
+        let tt_exit = 3600.0 / chain.segments[2].tau_f_tt_kin;
+        assert!((top.min_speed - tt_exit).abs() < 1e-9, "{} vs {tt_exit}", top.min_speed);
+        for w in &r.warrants {
+            assert!(w.min_speed < r.threshold_speed);
+        }
+        // A 35 mi/h criterion is never reached on this grade.
+        let lenient = ClimbingLaneStudy { speed_reduction: 35.0, ..study };
+        assert!(lenient.analyze().unwrap().warrants.is_empty());
+    }
+
+    #[test]
+    fn a_climbing_lane_speeds_the_grade_and_adds_capacity() {
+        let lane = StationRange { start: 3.0, end: 4.5 };
+        let r = ep11(Some(lane)).analyze().unwrap();
+        let with = r.with.unwrap();
+        assert_eq!(with.sections.len(), r.without.sections.len());
+        assert_eq!(r.without.sections.len(), 4, "the 2% segment is cut at 3.0 mi");
+        for (w, o) in with.sections.iter().zip(&r.without.sections) {
+            assert_eq!((w.start, w.end), (o.start, o.end));
+            if w.climbing_lane {
+                assert_eq!(w.lanes, 4);
+                assert!(w.speed > o.speed, "{} vs {}", w.speed, o.speed);
+                assert!(w.density < o.density);
+                assert!(w.space_speeds[0] > o.space_speeds[0], "autos leave the trucks behind");
+            } else {
+                assert_eq!(w.speed, o.speed);
+            }
+        }
+        assert!(with.speed > r.without.speed);
+        assert!(with.sections[3].capacity > r.without.sections[3].capacity);
+    }
+
+    #[test]
+    fn a_lane_off_the_grade_is_rejected() {
+        let err = ep11(Some(StationRange { start: 4.0, end: 5.0 })).analyze().unwrap_err();
+        assert!(err.contains("within the 4.5 mi grade"), "{err}");
+    }
+}
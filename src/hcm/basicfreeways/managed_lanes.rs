@@ -13,9 +13,19 @@
 //! - Equation 12-17: Speed drop in curvilinear portion (S2)
 //! - Equation 12-18: Friction indicator (Ic)
 //! - Equation 12-19: Additional speed drop due to GP friction (S3)
+//!
+//! [`PairedManagedLaneSegment`] runs the general purpose lanes beside the managed lane
+//! through Equation 12-1 first and hands their density to Equation 12-18, so the friction
+//! indicator follows from the two demands rather than from a density set by hand.
 
 use serde::{Deserialize, Serialize};
 use crate::hcm::common::LevelOfService;
+use crate::hcm::common::los_tables::los_basic_freeway;
+use crate::hcm::freeway_facilities::Terrain;
+use super::basicfreeways::{
+    basic_segment_breakpoint, basic_segment_capacity, basic_segment_speed,
+    DENSITY_AT_CAPACITY, EXPONENT_BASIC_FREEWAY,
+};
 
 /// Managed lane segment types from Exhibit 12-9
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +42,45 @@ pub enum ManagedLaneType {
     Barrier2,
 }
 
+/// How a managed lane is separated from the general purpose lanes (Exhibit 12-9), before the
+/// lane count picks the segment type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ManagedLaneSeparation {
+    /// Skip-stripe or solid single line, crossable along its length
+    ContinuousAccess,
+    /// Painted buffer, crossable only at access points
+    Buffer,
+    /// Physical barrier
+    Barrier,
+}
+
+impl ManagedLaneType {
+    /// Exhibit 12-9 segment type for a separation and a number of managed lanes. Exhibit 12-9
+    /// has continuous access as a single lane only.
+    pub fn from_separation(separation: ManagedLaneSeparation, lanes: u32) -> Result<Self, String> {
+        match (separation, lanes) {
+            (_, 0) => Err("a managed lane segment needs at least one managed lane".to_string()),
+            (ManagedLaneSeparation::ContinuousAccess, 1) => Ok(Self::ContinuousAccess),
+            (ManagedLaneSeparation::ContinuousAccess, n) => Err(format!(
+                "Exhibit 12-9 has no continuous access type with {n} managed lanes"
+            )),
+            (ManagedLaneSeparation::Buffer, 1) => Ok(Self::Buffer1),
+            (ManagedLaneSeparation::Buffer, _) => Ok(Self::Buffer2),
+            (ManagedLaneSeparation::Barrier, 1) => Ok(Self::Barrier1),
+            (ManagedLaneSeparation::Barrier, _) => Ok(Self::Barrier2),
+        }
+    }
+
+    /// Separation of this segment type.
+    pub fn separation(self) -> ManagedLaneSeparation {
+        match self {
+            Self::ContinuousAccess => ManagedLaneSeparation::ContinuousAccess,
+            Self::Buffer1 | Self::Buffer2 => ManagedLaneSeparation::Buffer,
+            Self::Barrier1 | Self::Barrier2 => ManagedLaneSeparation::Barrier,
+        }
+    }
+}
+
 /// Parameters for managed lane segment analysis from Exhibit 12-30
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ManagedLaneParams {
@@ -389,6 +438,182 @@ impl ManagedLaneSegment {
     }
 }
 
+fn unit_factor() -> f64 {
+    1.0
+}
+
+/// A basic managed lane segment analyzed together with the general purpose lanes beside it.
+///
+/// Both lane groups share the PHF, heavy vehicle share, terrain and adjustment factors, as in
+/// Chapter 26 Example Problem 7. The general purpose lanes are a basic freeway segment at
+/// their own FFS (Equations 12-6 and 12-1); their density sets the Equation 12-18 friction
+/// indicator for the managed lane. General purpose demand above capacity has no density, and
+/// is taken as the Exhibit 12-6 density at capacity, which is past the friction threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PairedManagedLaneSegment {
+    pub separation: ManagedLaneSeparation,
+    /// Managed lanes in the direction of travel.
+    pub ml_lanes: u32,
+    /// General purpose lanes in the direction of travel.
+    pub gp_lanes: u32,
+    /// Managed lane free-flow speed (mi/h).
+    pub ml_ffs: f64,
+    /// General purpose free-flow speed (mi/h).
+    pub gp_ffs: f64,
+    /// Managed lane demand (veh/h).
+    pub ml_demand: f64,
+    /// General purpose demand (veh/h).
+    pub gp_demand: f64,
+    pub phf: f64,
+    /// Heavy vehicle share, decimal.
+    pub heavy_vehicle_pct: f64,
+    /// Exhibit 12-25 terrain. Mountainous has no PCE and is refused.
+    pub terrain: Terrain,
+    #[serde(default = "unit_factor")]
+    pub saf: f64,
+    #[serde(default = "unit_factor")]
+    pub caf: f64,
+}
+
+/// Results for one lane group of a [`PairedManagedLaneSegment`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairedLaneGroupResult {
+    /// Demand flow rate (pc/h/ln).
+    pub v_p: f64,
+    /// Breakpoint (pc/h/ln).
+    pub breakpoint: f64,
+    /// Adjusted capacity (pc/h/ln).
+    pub capacity_adj: f64,
+    pub vc_ratio: f64,
+    /// Space mean speed (mi/h). `None` when demand exceeds capacity.
+    pub speed: Option<f64>,
+    /// Density (pc/mi/ln). `None` when demand exceeds capacity.
+    pub density: Option<f64>,
+    pub los: LevelOfService,
+}
+
+/// Results for a [`PairedManagedLaneSegment`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairedManagedLaneResult {
+    /// Exhibit 12-9 type from the separation and managed lane count.
+    pub lane_type: ManagedLaneType,
+    pub e_t: f64,
+    pub f_hv: f64,
+    pub gp: PairedLaneGroupResult,
+    pub ml: PairedLaneGroupResult,
+    /// Whether the Equation 12-18 friction indicator is 1.
+    pub friction_active: bool,
+}
+
+impl PairedManagedLaneSegment {
+    /// Run the general purpose lanes, then the managed lane with their density.
+    pub fn analyze(&self) -> Result<PairedManagedLaneResult, String> {
+        let lane_type = ManagedLaneType::from_separation(self.separation, self.ml_lanes)?;
+        self.validate()?;
+
+        let e_t = self.terrain.pce();
+        // Equation 12-10
+        let f_hv = 1.0 / (1.0 + self.heavy_vehicle_pct * (e_t - 1.0));
+        let flow_rate = |demand: f64, lanes: u32| demand / (self.phf * f64::from(lanes) * f_hv);
+
+        let gp_v_p = flow_rate(self.gp_demand, self.gp_lanes);
+        let gp_ffs_adj = self.gp_ffs * self.saf;
+        let gp_capacity_adj = basic_segment_capacity(self.gp_ffs) * self.caf;
+        let gp_breakpoint = basic_segment_breakpoint(gp_ffs_adj, self.caf);
+        let gp_over = gp_v_p > gp_capacity_adj;
+        let (gp_speed, gp_density) = if gp_over {
+            (None, None)
+        } else {
+            let s = basic_segment_speed(
+                gp_v_p,
+                gp_ffs_adj,
+                gp_capacity_adj,
+                gp_breakpoint,
+                EXPONENT_BASIC_FREEWAY,
+            );
+            (Some(s), Some(gp_v_p / s))
+        };
+        let gp = PairedLaneGroupResult {
+            v_p: gp_v_p,
+            breakpoint: gp_breakpoint,
+            capacity_adj: gp_capacity_adj,
+            vc_ratio: gp_v_p / gp_capacity_adj,
+            speed: gp_speed,
+            density: gp_density,
+            los: los_basic_freeway(gp_density.unwrap_or(f64::INFINITY), gp_over),
+        };
+
+        let mut ml = ManagedLaneSegment::new(lane_type, self.ml_ffs);
+        ml.set_saf(self.saf);
+        ml.set_caf(self.caf);
+        ml.set_demand(flow_rate(self.ml_demand, self.ml_lanes));
+        ml.set_gp_density(gp_density.unwrap_or(DENSITY_AT_CAPACITY));
+        let ml_los = ml.run_analysis();
+        let ml_over = ml.v_p > ml.capacity_adj;
+        let ml_result = PairedLaneGroupResult {
+            v_p: ml.v_p,
+            breakpoint: ml.breakpoint,
+            capacity_adj: ml.capacity_adj,
+            vc_ratio: ml.v_p / ml.capacity_adj,
+            speed: (!ml_over).then_some(ml.speed),
+            density: (!ml_over).then_some(ml.density),
+            los: ml_los,
+        };
+
+        Ok(PairedManagedLaneResult {
+            lane_type,
+            e_t,
+            f_hv,
+            gp,
+            friction_active: ml.is_friction_active(),
+            ml: ml_result,
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.gp_lanes == 0 {
+            return Err("the general purpose lane group needs at least one lane".to_string());
+        }
+        if self.terrain == Terrain::Mountainous {
+            return Err(
+                "Exhibit 12-25 gives no PCE for mountainous terrain; use the mixed-flow model"
+                    .to_string(),
+            );
+        }
+        for (name, ffs) in [("managed lane FFS", self.ml_ffs), ("general purpose FFS", self.gp_ffs)] {
+            if !ffs.is_finite() || ffs <= 0.0 {
+                return Err(format!("{name} must be positive, got {ffs} mi/h"));
+            }
+        }
+        for (name, demand) in [("managed lane", self.ml_demand), ("general purpose", self.gp_demand)] {
+            if !demand.is_finite() || demand < 0.0 {
+                return Err(format!("{name} demand must be non-negative, got {demand} veh/h"));
+            }
+        }
+        if self.phf.is_nan() || self.phf <= 0.0 || self.phf > 1.0 {
+            return Err(format!("PHF must be in (0, 1], got {}", self.phf));
+        }
+        if !(0.0..=1.0).contains(&self.heavy_vehicle_pct) {
+            return Err(format!(
+                "heavy_vehicle_pct is a decimal in [0, 1], got {}",
+                self.heavy_vehicle_pct
+            ));
+        }
+        for (name, f) in [("SAF", self.saf), ("CAF", self.caf)] {
+            if f.is_nan() || f <= 0.0 {
+                return Err(format!("{name} must be positive, got {f}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
 /// Estimated lane capacities for managed lane segments from Exhibit 12-11
 /// Returns capacity in pc/h/ln based on FFS and segment type
 pub fn get_estimated_capacity(lane_type: ManagedLaneType, ffs: u32) -> Option<u32> {
@@ -456,4 +681,39 @@ mod tests {
         segment.set_gp_density(40.0);
         assert!(segment.is_friction_active());
     }
+
+    #[test]
+    fn test_separation_and_lane_count_pick_the_type() {
+        use ManagedLaneSeparation::*;
+        assert_eq!(ManagedLaneType::from_separation(Buffer, 2), Ok(ManagedLaneType::Buffer2));
+        assert_eq!(ManagedLaneType::from_separation(Barrier, 1), Ok(ManagedLaneType::Barrier1));
+        assert!(ManagedLaneType::from_separation(ContinuousAccess, 2).is_err());
+        assert!(ManagedLaneType::from_separation(Barrier, 0).is_err());
+        assert_eq!(ManagedLaneType::Buffer2.separation(), Buffer);
+    }
+
+    #[test]
+    fn test_congested_gp_lanes_activate_friction() {
+        let segment = PairedManagedLaneSegment {
+            separation: ManagedLaneSeparation::Buffer,
+            ml_lanes: 1,
+            gp_lanes: 2,
+            ml_ffs: 60.0,
+            gp_ffs: 60.0,
+            ml_demand: 1300.0,
+            gp_demand: 5000.0,
+            phf: 0.92,
+            heavy_vehicle_pct: 0.075,
+            terrain: Terrain::Level,
+            saf: 1.0,
+            caf: 1.0,
+        };
+        let r = segment.analyze().unwrap();
+        assert_eq!(r.gp.los, LevelOfService::F);
+        assert!(r.gp.density.is_none());
+        assert!(r.friction_active);
+
+        let barrier = PairedManagedLaneSegment { separation: ManagedLaneSeparation::Barrier, ..segment };
+        assert!(!barrier.analyze().unwrap().friction_active);
+    }
 }
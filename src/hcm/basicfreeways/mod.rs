@@ -5,17 +5,26 @@
 //! in modules of their own: `mixed_flow` is the Chapter 26 single-grade mixed-flow model, and
 //! `composite_grade` is the Chapter 25 chaining of it across consecutive grades. Both analyse
 //! basic segments, and Chapter 26 is itself titled "Basic Freeway and Highway Segments:
-//! Supplemental".
+//! Supplemental". `climbing_lane` builds on the composite grade to locate and evaluate truck
+//! climbing lanes, and `service_volume_tables` sweeps the Chapter 12 planning
+//! equations into generalized service volume tables. `multilane` is the typed multilane
+//! highway segment and the facility that chains them.
 
 pub mod basicfreeways;
+pub mod climbing_lane;
 pub mod composite_grade;
 pub mod managed_lanes;
 pub mod mixed_flow;
+pub mod multilane;
+pub mod service_volume_tables;
 
 pub use basicfreeways::*;
+pub use climbing_lane::*;
 pub use composite_grade::*;
 pub use managed_lanes::*;
 pub use mixed_flow::*;
+pub use multilane::*;
+pub use service_volume_tables::*;
 
 pub const CHAPTER: u8 = 12;
 pub const TITLE: &str = "Basic Freeway and Multilane Highway Segments";
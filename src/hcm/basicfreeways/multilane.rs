@@ -0,0 +1,426 @@
+//! Typed multilane highway segments and facilities (HCM Chapter 12).
+//!
+//! `BasicFreeways` reaches multilane highways through its `highway_type`, `median_type` and
+//! `terrain_type` strings, one segment at a time. [`MultilaneSegment`] is the same Chapter 12
+//! method with the choices typed, and [`MultilaneFacility`] chains segments whose cross-section
+//! changes along the corridor, one direction of travel at a time.
+//!
+//! Per segment:
+//!
+//! - FFS, Equation 12-3: `FFS = BFFS - f_LW - f_TLC - f_M - f_A`. BFFS is given, or taken from
+//!   the speed limit. On an undivided highway or one with a TWLTL there is no left-side
+//!   obstruction to measure, so LC_L is 6 ft, and the median adjustment alone separates the
+//!   two: 1.6 mi/h undivided, none for a TWLTL.
+//! - Capacity, Equation 12-7, and the Exhibit 12-6 multilane speed-flow curve.
+//! - f_HV, Equation 12-10, with the Exhibit 12-25 PCE for level or rolling terrain or the
+//!   Exhibits 12-26 to 12-28 PCE for a specific grade the length of the segment. Mountainous
+//!   terrain has no Chapter 12 PCE and is not offered.
+//! - Density `v_p / S` and LOS from Exhibit 12-15. Demand above capacity is LOS F with no
+//!   speed or density, as in Chapter 12.
+//!
+//! The facility adds no queueing: each segment is evaluated on its own demand, and a segment
+//! whose demand exceeds its capacity is reported, not propagated upstream. Facility speed is
+//! Equation 25-2 and facility density the Chapter 10 lane-mile weighting. VERIFY-HCM:
+//! Chapter 12 defines LOS for segments only; the facility letter applies Exhibit 12-15 to the
+//! weighted density, and is F when any segment is.
+
+use serde::{Deserialize, Serialize};
+
+use super::basicfreeways::{
+    access_point_density_adjustment, basic_segment_speed, bffs_from_speed_limit,
+    lane_width_adjustment, median_type_adjustment, multilane_segment_capacity,
+    total_lateral_clearance_adjustment, BREAKPOINT_MULTILANE, EXPONENT_MULTILANE,
+};
+use crate::hcm::common::los_tables::los_basic_freeway;
+use crate::hcm::common::pce_table::PceTable;
+use crate::hcm::common::{LevelOfService, MedianType};
+use crate::hcm::freeway_facilities::exhibits::{facility_density, facility_space_mean_speed};
+
+/// Lateral clearance beyond which Exhibit 12-22 gives no further benefit, per side (ft).
+const MAX_LATERAL_CLEARANCE: f64 = 6.0;
+
+/// Terrain for the heavy vehicle PCE.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MultilaneTerrain {
+    /// Exhibit 12-25, E_T = 2.0.
+    Level,
+    /// Exhibit 12-25, E_T = 3.0.
+    Rolling,
+    /// Exhibits 12-26 to 12-28 for a grade (percent, negative downhill) the length of the
+    /// segment, with SUTs making up `sut_percentage` (30, 50 or 70) of the trucks.
+    SpecificGrade { grade: f64, sut_percentage: u32 },
+}
+
+fn default_lane_width() -> f64 {
+    12.0
+}
+
+fn default_clearance() -> f64 {
+    MAX_LATERAL_CLEARANCE
+}
+
+fn unit_factor() -> f64 {
+    1.0
+}
+
+/// One multilane highway segment in one direction of travel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultilaneSegment {
+    /// Segment length (mi).
+    pub length: f64,
+    /// Lanes in the direction of travel.
+    pub lanes: u32,
+    /// Base free-flow speed (mi/h). When absent it is taken from `speed_limit`.
+    #[serde(default)]
+    pub bffs: Option<f64>,
+    /// Posted speed limit (mi/h), used only when `bffs` is absent.
+    #[serde(default)]
+    pub speed_limit: Option<f64>,
+    /// Average lane width (ft).
+    #[serde(default = "default_lane_width")]
+    pub lane_width: f64,
+    /// Right-side lateral clearance (ft).
+    #[serde(default = "default_clearance")]
+    pub lc_right: f64,
+    /// Left-side lateral clearance (ft). Read only for a divided median.
+    #[serde(default = "default_clearance")]
+    pub lc_left: f64,
+    pub median: MedianType,
+    /// Access points on the right side in the direction of travel, per mile.
+    #[serde(default)]
+    pub access_point_density: f64,
+    /// Demand volume (veh/h).
+    pub demand: f64,
+    pub phf: f64,
+    /// Heavy vehicle share, decimal.
+    pub heavy_vehicle_pct: f64,
+    pub terrain: MultilaneTerrain,
+    #[serde(default = "unit_factor")]
+    pub saf: f64,
+    #[serde(default = "unit_factor")]
+    pub caf: f64,
+}
+
+/// Results for one segment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultilaneSegmentResult {
+    /// Base free-flow speed used (mi/h).
+    pub bffs: f64,
+    /// Lateral clearance reading Exhibit 12-22 (ft).
+    pub total_lateral_clearance: f64,
+    /// Equation 12-3 free-flow speed (mi/h).
+    pub ffs: f64,
+    /// FFS × SAF (mi/h).
+    pub ffs_adj: f64,
+    /// Equation 12-7 capacity (pc/h/ln).
+    pub capacity: f64,
+    /// Capacity × CAF (pc/h/ln).
+    pub capacity_adj: f64,
+    pub e_t: f64,
+    pub f_hv: f64,
+    /// Demand flow rate (pc/h/ln).
+    pub v_p: f64,
+    pub vc_ratio: f64,
+    /// Space mean speed (mi/h). `None` when demand exceeds capacity.
+    pub speed: Option<f64>,
+    /// Density (pc/mi/ln). `None` when demand exceeds capacity.
+    pub density: Option<f64>,
+    pub los: LevelOfService,
+}
+
+impl MultilaneSegment {
+    /// Run the Chapter 12 operational analysis.
+    pub fn analyze(&self) -> Result<MultilaneSegmentResult, String> {
+        self.validate()?;
+        let bffs = match (self.bffs, self.speed_limit) {
+            (Some(bffs), _) => bffs,
+            (None, Some(limit)) => bffs_from_speed_limit(limit),
+            (None, None) => unreachable!("checked in validate"),
+        };
+
+        // An undivided cross-section or a TWLTL has nothing on the left to clear.
+        let lc_left = match self.median {
+            MedianType::Undivided | MedianType::TWLTL => MAX_LATERAL_CLEARANCE,
+            _ => self.lc_left.min(MAX_LATERAL_CLEARANCE),
+        };
+        let tlc = self.lc_right.min(MAX_LATERAL_CLEARANCE) + lc_left;
+
+        // Equation 12-3
+        let ffs = bffs
+            - lane_width_adjustment(self.lane_width)?
+            - total_lateral_clearance_adjustment(tlc, self.lanes)
+            - median_type_adjustment(self.median)
+            - access_point_density_adjustment(self.access_point_density);
+        let ffs_adj = ffs * self.saf;
+
+        let capacity = multilane_segment_capacity(ffs);
+        let capacity_adj = capacity * self.caf;
+
+        let e_t = match self.terrain {
+            MultilaneTerrain::Level => 2.0,
+            MultilaneTerrain::Rolling => 3.0,
+            MultilaneTerrain::SpecificGrade { grade, sut_percentage } => {
+                PceTable::for_sut_percentage(sut_percentage)?.lookup(
+                    grade,
+                    self.length,
+                    self.heavy_vehicle_pct,
+                )?
+            }
+        };
+        // Equation 12-10
+        let f_hv = 1.0 / (1.0 + self.heavy_vehicle_pct * (e_t - 1.0));
+        let v_p = self.demand / (self.phf * f64::from(self.lanes) * f_hv);
+
+        let over = v_p > capacity_adj;
+        let (speed, density) = if over {
+            (None, None)
+        } else {
+            let s = basic_segment_speed(
+                v_p,
+                ffs_adj,
+                capacity_adj,
+                BREAKPOINT_MULTILANE,
+                EXPONENT_MULTILANE,
+            );
+            (Some(s), Some(v_p / s))
+        };
+
+        Ok(MultilaneSegmentResult {
+            bffs,
+            total_lateral_clearance: tlc,
+            ffs,
+            ffs_adj,
+            capacity,
+            capacity_adj,
+            e_t,
+            f_hv,
+            v_p,
+            vc_ratio: v_p / capacity_adj,
+            speed,
+            density,
+            los: los_basic_freeway(density.unwrap_or(f64::INFINITY), over),
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.length.is_nan() || self.length <= 0.0 {
+            return Err(format!("length must be positive, got {} mi", self.length));
+        }
+        if self.lanes < 2 {
+            return Err(format!(
+                "a multilane highway has at least two lanes in each direction, got {}",
+                self.lanes
+            ));
+        }
+        match (self.bffs, self.speed_limit) {
+            (Some(b), _) if !b.is_finite() || b <= 0.0 => {
+                return Err(format!("BFFS must be positive, got {b} mi/h"))
+            }
+            (None, Some(l)) if !l.is_finite() || l <= 0.0 => {
+                return Err(format!("speed limit must be positive, got {l} mi/h"))
+            }
+            (None, None) => {
+                return Err(
+                    "either bffs or speed_limit is needed: Chapter 12 gives multilane \
+                     highways no default BFFS"
+                        .to_string(),
+                )
+            }
+            _ => {}
+        }
+        for (side, lc) in [("right", self.lc_right), ("left", self.lc_left)] {
+            if !lc.is_finite() || lc < 0.0 {
+                return Err(format!("{side}-side lateral clearance must be non-negative, got {lc} ft"));
+            }
+        }
+        if !self.access_point_density.is_finite() || self.access_point_density < 0.0 {
+            return Err(format!(
+                "access point density must be non-negative, got {} points/mi",
+                self.access_point_density
+            ));
+        }
+        if !self.demand.is_finite() || self.demand < 0.0 {
+            return Err(format!("demand must be non-negative, got {} veh/h", self.demand));
+        }
+        if self.phf.is_nan() || self.phf <= 0.0 || self.phf > 1.0 {
+            return Err(format!("PHF must be in (0, 1], got {}", self.phf));
+        }
+        if !(0.0..=1.0).contains(&self.heavy_vehicle_pct) {
+            return Err(format!(
+                "heavy_vehicle_pct is a decimal in [0, 1], got {}",
+                self.heavy_vehicle_pct
+            ));
+        }
+        for (name, f) in [("SAF", self.saf), ("CAF", self.caf)] {
+            if f.is_nan() || f <= 0.0 {
+                return Err(format!("{name} must be positive, got {f}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A multilane highway corridor in one direction of travel, segments in travel order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultilaneFacility {
+    pub segments: Vec<MultilaneSegment>,
+}
+
+/// Results for a multilane facility.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultilaneFacilityResult {
+    pub segments: Vec<MultilaneSegmentResult>,
+    /// Total length (mi).
+    pub length: f64,
+    /// Segment with the highest v/c ratio.
+    pub governing_segment: usize,
+    /// Equation 25-2 space mean speed (mi/h). `None` when any segment is over capacity.
+    pub speed: Option<f64>,
+    /// Travel time at `speed` (s).
+    pub travel_time: Option<f64>,
+    /// Lane-mile weighted density (pc/mi/ln). `None` when any segment is over capacity.
+    pub density: Option<f64>,
+    pub los: LevelOfService,
+}
+
+impl MultilaneFacility {
+    /// Analyze every segment and aggregate along the corridor.
+    pub fn analyze(&self) -> Result<MultilaneFacilityResult, String> {
+        if self.segments.is_empty() {
+            return Err("a multilane facility needs at least one segment".to_string());
+        }
+        let segments = self
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s.analyze().map_err(|e| format!("segment {i}: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let length: Vec<f64> = self.segments.iter().map(|s| s.length).collect();
+        let total: f64 = length.iter().sum();
+        let governing_segment = segments
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.vc_ratio.total_cmp(&b.1.vc_ratio))
+            .map(|(i, _)| i)
+            .expect("segments is non-empty");
+
+        let speeds: Option<Vec<f64>> = segments.iter().map(|r| r.speed).collect();
+        let densities: Option<Vec<f64>> = segments.iter().map(|r| r.density).collect();
+        let (speed, density) = match (speeds, densities) {
+            (Some(speeds), Some(densities)) => {
+                let flow: Vec<f64> = self.segments.iter().map(|s| s.demand).collect();
+                let lanes: Vec<f64> = self.segments.iter().map(|s| f64::from(s.lanes)).collect();
+                (
+                    Some(facility_space_mean_speed(&flow, &length, &speeds)),
+                    Some(facility_density(&densities, &length, &lanes)),
+                )
+            }
+            _ => (None, None),
+        };
+        let los = match density {
+            Some(d) => los_basic_freeway(d, false),
+            None => LevelOfService::F,
+        };
+
+        Ok(MultilaneFacilityResult {
+            segments,
+            length: total,
+            governing_segment,
+            travel_time: speed.map(|s| 3600.0 * total / s),
+            speed,
+            density,
+            los,
+        })
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment() -> MultilaneSegment {
+        MultilaneSegment {
+            length: 1.0,
+            lanes: 2,
+            bffs: Some(60.0),
+            speed_limit: None,
+            lane_width: 12.0,
+            lc_right: 6.0,
+            lc_left: 6.0,
+            median: MedianType::Raised,
+            access_point_density: 0.0,
+            demand: 2000.0,
+            phf: 0.95,
+            heavy_vehicle_pct: 0.05,
+            terrain: MultilaneTerrain::Level,
+            saf: 1.0,
+            caf: 1.0,
+        }
+    }
+
+    #[test]
+    fn twltl_and_undivided_ignore_the_left_clearance() {
+        let narrow = |median| MultilaneSegment { median, lc_left: 0.0, ..segment() };
+        let divided = narrow(MedianType::Raised).analyze().unwrap();
+        let twltl = narrow(MedianType::TWLTL).analyze().unwrap();
+        let undivided = narrow(MedianType::Undivided).analyze().unwrap();
+        assert_eq!(divided.total_lateral_clearance, 6.0);
+        assert_eq!(twltl.total_lateral_clearance, 12.0);
+        assert_eq!(twltl.ffs, 60.0);
+        // Exhibit 12-23: only the undivided cross-section pays the 1.6 mi/h median penalty.
+        assert!((undivided.ffs - 58.4).abs() < 1e-9);
+        // Exhibit 12-22, four-lane column at TLC = 6 ft.
+        assert!((divided.ffs - (60.0 - 1.3)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn access_points_and_narrow_lanes_lower_ffs() {
+        let r = MultilaneSegment { access_point_density: 20.0, lane_width: 11.0, ..segment() }
+            .analyze()
+            .unwrap();
+        assert!((r.ffs - (60.0 - 1.9 - 5.0)).abs() < 1e-9);
+        let err = MultilaneSegment { lane_width: 9.5, ..segment() }.analyze().unwrap_err();
+        assert!(err.contains("lane width"), "{err}");
+    }
+
+    #[test]
+    fn checks_reject_what_chapter_12_does_not_cover() {
+        let err = MultilaneSegment { lanes: 1, ..segment() }.analyze().unwrap_err();
+        assert!(err.contains("two lanes"), "{err}");
+        let err = MultilaneSegment { bffs: None, ..segment() }.analyze().unwrap_err();
+        assert!(err.contains("speed_limit"), "{err}");
+        let err = MultilaneSegment { lc_right: -1.0, ..segment() }.analyze().unwrap_err();
+        assert!(err.contains("right-side"), "{err}");
+    }
+
+    #[test]
+    fn a_lane_drop_governs_the_facility() {
+        let facility = MultilaneFacility {
+            segments: vec![
+                MultilaneSegment { lanes: 3, demand: 4000.0, ..segment() },
+                MultilaneSegment { lanes: 2, demand: 4000.0, ..segment() },
+            ],
+        };
+        let r = facility.analyze().unwrap();
+        assert_eq!(r.governing_segment, 1);
+        assert_eq!(r.segments[1].los, LevelOfService::F);
+        assert!(r.segments[0].speed.is_some());
+        assert_eq!(r.los, LevelOfService::F);
+        assert!(r.speed.is_none() && r.travel_time.is_none());
+
+        let err = MultilaneFacility {
+            segments: vec![segment(), MultilaneSegment { phf: 0.0, ..segment() }],
+        }
+        .analyze()
+        .unwrap_err();
+        assert!(err.starts_with("segment 1: "), "{err}");
+    }
+}
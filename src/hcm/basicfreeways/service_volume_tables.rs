@@ -0,0 +1,428 @@
+//! Generalized service volume tables for freeway and multilane highway segments.
+//!
+//! `determine_basic_max_service_flow_rate`, `determine_multilane_max_service_flow_rate`,
+//! `weaving::service_volumes` and `merge_diverge::ramp_service_volumes` each give one cell.
+//! This module sweeps them over every LOS, FFS, lane count, terrain and K/D pair a caller asks
+//! for, under the caller's own heavy vehicle share and PHF, and returns the result as rows.
+//!
+//! Every cell follows the same chain, Equations 12-24 to 12-26:
+//!
+//! ```text
+//! SF_i  = SFI_i × f_HV          SFI_i: ideal service flow rate, pc/h
+//! SV_i  = SF_i × PHF
+//! DSV_i = SV_i / (K × D)
+//! ```
+//!
+//! with `f_HV` from Equation 12-10 and the Exhibit 12-25 general terrain PCE. Only the source
+//! of SFI differs:
+//!
+//! - **Basic freeway / multilane**: `MSF_i × N`, from Exhibit 12-37 or 12-38 at the FFS
+//!   rounded to the nearest 5 mi/h.
+//! - **Weaving**: the Chapter 27 Example Problem 5 search on the given geometry, at the
+//!   Exhibit 13-6 density thresholds for A-D and at capacity for E. The FFS and lane count of
+//!   each cell replace the template's, and `c_IFL` follows the FFS (Equation 12-6 or 12-7).
+//! - **Ramp**: the Chapter 28 Example Problem 5 search, with SFI expressed as whichever flow
+//!   the basis varies. LOS E is the lower of the freeway and ramp roadway capacity limits. A
+//!   LOS the junction cannot reach at any demand is left empty.
+//!
+//! Mountainous terrain is refused: Exhibit 12-25 gives no PCE for it, and a generalized table
+//! has no grade to hand the Chapter 25/26 mixed-flow model.
+
+use serde::{Deserialize, Serialize};
+
+use super::basicfreeways::{basic_segment_capacity, multilane_segment_capacity, BasicFreeways};
+use crate::hcm::common::LevelOfService;
+use crate::hcm::freeway_facilities::freeway_facilities::Terrain;
+use crate::hcm::merge_diverge::merge_diverge::{
+    ramp_service_flow_rate_ideal, RampSegment, RampType, ServiceDemandBasis,
+};
+use crate::hcm::utils::csv::join_line;
+use crate::hcm::weaving::weaving::{
+    service_flow_rate_ideal, service_volumes, DemandSplit, FacilityType, ServiceVolumes,
+    WeavingSegment,
+};
+
+/// The levels a table covers. LOS F has no service volume.
+pub const TABLE_LEVELS: [LevelOfService; 5] = [
+    LevelOfService::A,
+    LevelOfService::B,
+    LevelOfService::C,
+    LevelOfService::D,
+    LevelOfService::E,
+];
+
+/// The segment a table describes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TableSegment {
+    /// Basic freeway segment, Exhibit 12-37.
+    BasicFreeway,
+    /// Multilane highway segment, Exhibit 12-38.
+    Multilane,
+    /// Weaving segment of the template's geometry and the given demand split.
+    Weaving { template: WeavingSegment, split: DemandSplit },
+    /// Merge or diverge of the template's geometry, varying demand as `basis` says.
+    Ramp { template: RampSegment, basis: ServiceDemandBasis },
+}
+
+/// A K-factor and D-factor pair.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KdFactors {
+    /// Proportion of AADT in the analysis hour.
+    pub k: f64,
+    /// Proportion of analysis-hour volume in the peak direction.
+    pub d: f64,
+}
+
+/// What to tabulate, and under which local defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceVolumeTableSpec {
+    pub segment: TableSegment,
+    /// Free-flow speeds (mi/h).
+    pub ffs: Vec<f64>,
+    /// Lanes in one direction.
+    pub lanes: Vec<u32>,
+    pub terrains: Vec<Terrain>,
+    /// Heavy vehicle share, decimal.
+    pub heavy_vehicle_pct: f64,
+    pub phf: f64,
+    pub factors: Vec<KdFactors>,
+}
+
+/// One LOS of one row. `volumes` is `None` where the segment cannot operate at that LOS.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceVolumeLevel {
+    pub los: LevelOfService,
+    pub volumes: Option<ServiceVolumes>,
+}
+
+/// Service volumes for one combination of terrain, FFS, lanes and K/D.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceVolumeRow {
+    pub terrain: Terrain,
+    pub ffs: f64,
+    pub lanes: u32,
+    pub k_factor: f64,
+    pub d_factor: f64,
+    /// Equation 12-10 heavy vehicle adjustment factor.
+    pub f_hv: f64,
+    /// LOS A to E, in order.
+    pub levels: Vec<ServiceVolumeLevel>,
+}
+
+/// A generated table, one row per terrain, FFS, lane count and K/D pair, in that nesting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceVolumeTable {
+    pub rows: Vec<ServiceVolumeRow>,
+}
+
+impl ServiceVolumeTable {
+    /// The table as CSV, one line per row and LOS. Unreachable levels have empty volumes.
+    pub fn to_csv(&self) -> String {
+        let mut out = join_line(&[
+            "terrain", "ffs", "lanes", "k_factor", "d_factor", "f_hv", "los", "sfi", "sf", "sv",
+            "dsv",
+        ]);
+        out.push('\n');
+        for r in &self.rows {
+            for level in &r.levels {
+                let mut fields = vec![
+                    format!("{:?}", r.terrain),
+                    r.ffs.to_string(),
+                    r.lanes.to_string(),
+                    r.k_factor.to_string(),
+                    r.d_factor.to_string(),
+                    r.f_hv.to_string(),
+                    format!("{:?}", level.los),
+                ];
+                match level.volumes {
+                    Some(v) => fields.extend([v.sfi, v.sf, v.sv, v.dsv].map(|x| x.to_string())),
+                    None => fields.extend(std::iter::repeat_n(String::new(), 4)),
+                }
+                out.push_str(&join_line(&fields));
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl ServiceVolumeTableSpec {
+    /// Generate the table.
+    pub fn generate(&self) -> Result<ServiceVolumeTable, String> {
+        self.validate()?;
+
+        // SFI depends only on the segment, FFS and lanes; the rest is arithmetic on top.
+        let mut ideal = Vec::with_capacity(self.ffs.len() * self.lanes.len());
+        for &ffs in &self.ffs {
+            for &lanes in &self.lanes {
+                ideal.push(self.ideal_flow_rates(ffs, lanes)?);
+            }
+        }
+
+        let mut rows = Vec::new();
+        for &terrain in &self.terrains {
+            let f_hv = 1.0 / (1.0 + self.heavy_vehicle_pct * (terrain.pce() - 1.0));
+            for (i, &ffs) in self.ffs.iter().enumerate() {
+                for (j, &lanes) in self.lanes.iter().enumerate() {
+                    let sfi = &ideal[i * self.lanes.len() + j];
+                    for kd in &self.factors {
+                        rows.push(ServiceVolumeRow {
+                            terrain,
+                            ffs,
+                            lanes,
+                            k_factor: kd.k,
+                            d_factor: kd.d,
+                            f_hv,
+                            levels: TABLE_LEVELS
+                                .iter()
+                                .zip(sfi)
+                                .map(|(&los, s)| ServiceVolumeLevel {
+                                    los,
+                                    volumes: s.map(|s| service_volumes(s, f_hv, self.phf, kd.k, kd.d)),
+                                })
+                                .collect(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(ServiceVolumeTable { rows })
+    }
+
+    /// Ideal service flow rates (pc/h) for LOS A to E.
+    fn ideal_flow_rates(&self, ffs: f64, lanes: u32) -> Result<[Option<f64>; 5], String> {
+        let mut out = [None; 5];
+        match &self.segment {
+            TableSegment::BasicFreeway | TableSegment::Multilane => {
+                let mut seg = BasicFreeways::new();
+                seg.ffs_adj = ffs;
+                for (k, &los) in TABLE_LEVELS.iter().enumerate() {
+                    seg.los = Some(los);
+                    let msf = match self.segment {
+                        TableSegment::BasicFreeway => seg.determine_basic_max_service_flow_rate()?,
+                        _ => seg.determine_multilane_max_service_flow_rate()?,
+                    };
+                    out[k] = Some(msf * f64::from(lanes));
+                }
+            }
+            TableSegment::Weaving { template, split } => {
+                let mut t = template.clone();
+                t.ffs = ffs;
+                t.num_lanes = lanes;
+                t.f_hv = None;
+                let (c_ifl, thresholds) = match t.facility_type {
+                    FacilityType::Freeway => (basic_segment_capacity(ffs), [10.0, 20.0, 28.0, 35.0]),
+                    FacilityType::MultilaneOrCD => {
+                        (multilane_segment_capacity(ffs), [12.0, 24.0, 32.0, 36.0])
+                    }
+                };
+                t.basic_freeway_capacity = c_ifl;
+
+                // LOS E is capacity, which depends on the split but not on the total flow.
+                let mut probe = t.clone();
+                probe.phf = 1.0;
+                probe.heavy_vehicle_pct = 0.0;
+                probe.caf = 1.0;
+                probe.saf = 1.0;
+                probe.v_ff = split.ff * 1000.0;
+                probe.v_rf = split.rf * 1000.0;
+                probe.v_fr = split.fr * 1000.0;
+                probe.v_rr = split.rr * 1000.0;
+                probe.run_analysis();
+                let capacity = probe.get_capacity();
+                for (k, &density) in thresholds.iter().enumerate() {
+                    out[k] = Some(service_flow_rate_ideal(&t, split, density).min(capacity));
+                }
+                out[4] = Some(capacity);
+            }
+            TableSegment::Ramp { template, basis } => {
+                if template.ramp_type == RampType::MajorMerge {
+                    return Err(
+                        "a major merge has no Chapter 14 density model, so it has no service \
+                         volumes for LOS A-D"
+                            .to_string(),
+                    );
+                }
+                let mut t = template.clone();
+                t.freeway_ffs = ffs;
+                t.freeway_lanes = lanes;
+
+                let mut probe = t.clone();
+                probe.phf = 1.0;
+                probe.heavy_vehicle_pct = 0.0;
+                probe.ramp_heavy_vehicle_pct = Some(0.0);
+                probe.caf = 1.0;
+                probe.saf = 1.0;
+                probe.freeway_demand = 0.0;
+                probe.ramp_demand = 0.0;
+                probe.run_analysis();
+                let (cap_f, cap_r) = (probe.get_capacity_freeway(), probe.get_capacity_ramp());
+                let capacity = match *basis {
+                    ServiceDemandBasis::ApproachingFreeway { ramp_fraction } => {
+                        let freeway = if t.is_on_ramp() { cap_f / (1.0 + ramp_fraction) } else { cap_f };
+                        if ramp_fraction > 0.0 { freeway.min(cap_r / ramp_fraction) } else { freeway }
+                    }
+                    ServiceDemandBasis::FixedFreeway { v_f } => {
+                        if t.is_on_ramp() {
+                            (cap_f - v_f).min(cap_r)
+                        } else if v_f <= cap_f {
+                            v_f.min(cap_r)
+                        } else {
+                            0.0
+                        }
+                    }
+                };
+                if capacity > 0.0 {
+                    for (k, density) in [10.0, 20.0, 28.0, 35.0].into_iter().enumerate() {
+                        out[k] = ramp_service_flow_rate_ideal(&t, basis, density)
+                            .map(|s| s.min(capacity));
+                    }
+                    out[4] = Some(capacity);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.ffs.is_empty() || self.lanes.is_empty() || self.terrains.is_empty() || self.factors.is_empty() {
+            return Err("a service volume table needs at least one FFS, lane count, terrain and K/D pair".to_string());
+        }
+        if let Some(ffs) = self.ffs.iter().find(|f| !f.is_finite() || **f <= 0.0) {
+            return Err(format!("FFS must be positive, got {ffs} mi/h"));
+        }
+        if self.lanes.contains(&0) {
+            return Err("lane counts must be at least 1".to_string());
+        }
+        if self.terrains.contains(&Terrain::Mountainous) {
+            return Err(
+                "Exhibit 12-25 provides no PCE for mountainous terrain; specific grades need the \
+                 Chapter 25/26 mixed-flow model"
+                    .to_string(),
+            );
+        }
+        if !(0.0..=1.0).contains(&self.heavy_vehicle_pct) {
+            return Err(format!(
+                "heavy_vehicle_pct is a decimal in [0, 1], got {}",
+                self.heavy_vehicle_pct
+            ));
+        }
+        if self.phf.is_nan() || self.phf <= 0.0 || self.phf > 1.0 {
+            return Err(format!("PHF must be in (0, 1], got {}", self.phf));
+        }
+        for kd in &self.factors {
+            if kd.k.is_nan() || kd.k <= 0.0 || kd.k > 1.0 || kd.d.is_nan() || kd.d <= 0.0 || kd.d > 1.0 {
+                return Err(format!("K and D must be in (0, 1], got K = {} and D = {}", kd.k, kd.d));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(segment: TableSegment) -> ServiceVolumeTableSpec {
+        ServiceVolumeTableSpec {
+            segment,
+            ffs: vec![65.0, 70.0],
+            lanes: vec![2, 3],
+            terrains: vec![Terrain::Level, Terrain::Rolling],
+            heavy_vehicle_pct: 0.10,
+            phf: 0.94,
+            factors: vec![KdFactors { k: 0.09, d: 0.55 }, KdFactors { k: 0.11, d: 0.60 }],
+        }
+    }
+
+    fn volumes(row: &ServiceVolumeRow) -> Vec<ServiceVolumes> {
+        row.levels.iter().map(|l| l.volumes.unwrap()).collect()
+    }
+
+    #[test]
+    fn basic_freeway_cells_match_the_single_cell_equations() {
+        let table = spec(TableSegment::BasicFreeway).generate().unwrap();
+        assert_eq!(table.rows.len(), 2 * 2 * 2 * 2);
+        // Level, FFS 65, 3 lanes, K = 0.09, D = 0.55.
+        let row = &table.rows[2];
+        assert_eq!((row.terrain, row.ffs, row.lanes, row.k_factor), (Terrain::Level, 65.0, 3, 0.09));
+        let mut seg = BasicFreeways::new();
+        seg.lane_count = 3;
+        seg.phf = 0.94;
+        seg.phv = 1.0 / 1.1;
+        seg.k_factor = 0.09;
+        seg.d_factor = 0.55;
+        // Exhibit 12-37, FFS 65: A 710, B 1,170, C 1,660, D 2,060, E 2,350.
+        for (v, msf) in volumes(row).iter().zip([710.0, 1170.0, 1660.0, 2060.0, 2350.0]) {
+            assert_eq!(v.sfi, msf * 3.0);
+            assert!((v.sf - seg.calculate_service_flow_rate(msf)).abs() < 1e-9);
+            assert!((v.sv - seg.calculate_service_volume(msf)).abs() < 1e-9);
+            assert!((v.dsv - seg.calculate_daily_service_volume(msf)).abs() < 1e-6);
+        }
+        // Rolling terrain lowers every volume by its f_HV.
+        let rolling = &table.rows[8 + 2];
+        assert_eq!(rolling.terrain, Terrain::Rolling);
+        assert!((rolling.f_hv - 1.0 / 1.2).abs() < 1e-12);
+        assert!(volumes(rolling)[4].sv < volumes(row)[4].sv);
+    }
+
+    #[test]
+    fn weaving_levels_rise_to_capacity() {
+        let template = WeavingSegment {
+            num_weaving_lanes: 2,
+            interchange_density: 1.0,
+            lc_rf: 0,
+            lc_fr: 2,
+            length_short: 1500.0,
+            ..Default::default()
+        };
+        let split = DemandSplit { ff: 0.65, rf: 0.15, fr: 0.12, rr: 0.08 };
+        let table = spec(TableSegment::Weaving { template, split }).generate().unwrap();
+        for row in &table.rows {
+            let sfi: Vec<f64> = volumes(row).iter().map(|v| v.sfi).collect();
+            assert!(sfi.windows(2).all(|w| w[0] <= w[1]), "{sfi:?}");
+        }
+        // More lanes carry more at every level.
+        let (two, three) = (volumes(&table.rows[0]), volumes(&table.rows[2]));
+        assert!(two.iter().zip(&three).all(|(a, b)| a.sfi < b.sfi));
+    }
+
+    #[test]
+    fn ramp_levels_out_of_reach_are_empty() {
+        let template = RampSegment {
+            ramp_type: RampType::OnRamp,
+            accel_lane_length: Some(800.0),
+            ..Default::default()
+        };
+        // A heavy fixed freeway flow puts LOS A and B out of reach whatever the ramp carries.
+        let basis = ServiceDemandBasis::FixedFreeway { v_f: 4000.0 };
+        let table = spec(TableSegment::Ramp { template, basis }).generate().unwrap();
+        let row = &table.rows[0];
+        assert!(row.levels[0].volumes.is_none());
+        assert!(row.levels[4].volumes.is_some());
+        let csv = table.to_csv();
+        assert_eq!(csv.lines().count(), 1 + table.rows.len() * 5);
+        assert!(csv.lines().nth(1).unwrap().ends_with("A,,,,"), "{csv}");
+    }
+
+    #[test]
+    fn mountainous_terrain_and_bad_factors_are_refused() {
+        let mut s = spec(TableSegment::Multilane);
+        s.ffs = vec![55.0];
+        s.terrains.push(Terrain::Mountainous);
+        assert!(s.generate().unwrap_err().contains("mountainous"));
+        s.terrains.pop();
+        s.factors[0].k = 0.0;
+        assert!(s.generate().unwrap_err().contains("K and D"));
+        // Outside Exhibit 12-38's rows the lookup's own error comes through.
+        let mut s = spec(TableSegment::Multilane);
+        s.factors.truncate(1);
+        assert!(s.generate().unwrap_err().contains("12-38"));
+    }
+}
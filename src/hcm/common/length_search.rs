@@ -0,0 +1,83 @@
+//! The length search behind the design modules (`weaving::design` for weaving segments,
+//! `merge_diverge::design` for acceleration and deceleration lanes): a target LOS, the HCM
+//! editions to design under, and a grid of lengths scanned from a shortest one in fixed steps.
+//!
+//! Each design spec keeps its own fields, so its JSON form and `deny_unknown_fields` stay as they
+//! are, and takes its serde defaults, grid and input checks from here.
+
+use serde::de::DeserializeOwned;
+
+use super::{HcmVersion, LevelOfService};
+
+pub(crate) fn default_target() -> LevelOfService {
+    LevelOfService::D
+}
+
+pub(crate) fn both_editions() -> Vec<HcmVersion> {
+    vec![HcmVersion::V7, HcmVersion::V7_1]
+}
+
+pub(crate) fn default_length_step() -> f64 {
+    50.0
+}
+
+/// `min_length`, `min_length + length_step`, ... without end. Each length is indexed rather than
+/// accumulated, so a long scan does not drift off the grid by rounding.
+pub(crate) fn length_grid(min_length: f64, length_step: f64) -> impl Iterator<Item = f64> {
+    (0u32..).map(move |i| min_length + f64::from(i) * length_step)
+}
+
+/// The checks every design spec shares. `phf` is the template segment's.
+pub(crate) fn validate_search(
+    target_los: LevelOfService,
+    versions: &[HcmVersion],
+    min_length: f64,
+    length_step: f64,
+    phf: f64,
+) -> Result<(), String> {
+    if target_los == LevelOfService::F {
+        return Err("the target LOS must be A to E".to_string());
+    }
+    if versions.is_empty() {
+        return Err("at least one HCM edition is needed".to_string());
+    }
+    if min_length.is_nan() || min_length <= 0.0 {
+        return Err(format!("min_length must be positive, got {min_length} ft"));
+    }
+    if length_step.is_nan() || length_step <= 0.0 {
+        return Err(format!("length_step must be positive, got {length_step} ft"));
+    }
+    if phf.is_nan() || phf <= 0.0 || phf > 1.0 {
+        return Err(format!("PHF must be in (0, 1], got {phf}"));
+    }
+    Ok(())
+}
+
+/// Parse a design spec from JSON, for the Python bindings.
+pub(crate) fn from_json<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+    serde_json::from_str(s).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_grid_stays_on_its_steps() {
+        let last = length_grid(100.0, 0.1).nth(10_000).unwrap();
+        assert_eq!(last, 100.0 + 10_000.0 * 0.1);
+        let first: Vec<f64> = length_grid(300.0, 50.0).take(3).collect();
+        assert_eq!(first, [300.0, 350.0, 400.0]);
+    }
+
+    #[test]
+    fn shared_inputs_are_checked() {
+        let both = both_editions();
+        assert!(validate_search(LevelOfService::D, &both, 100.0, 50.0, 0.95).is_ok());
+        assert!(validate_search(LevelOfService::F, &both, 100.0, 50.0, 0.95).is_err());
+        assert!(validate_search(LevelOfService::D, &[], 100.0, 50.0, 0.95).is_err());
+        assert!(validate_search(LevelOfService::D, &both, 0.0, 50.0, 0.95).is_err());
+        assert!(validate_search(LevelOfService::D, &both, 100.0, f64::NAN, 0.95).is_err());
+        assert!(validate_search(LevelOfService::D, &both, 100.0, 50.0, 1.2).is_err());
+    }
+}
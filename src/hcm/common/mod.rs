@@ -6,6 +6,7 @@ pub mod atdm;
 pub mod delay;
 pub mod gap_acceptance;
 pub mod intersection;
+pub(crate) mod length_search;
 pub mod los_tables;
 // Three published E_T values in the generated tables happen to equal 3.14 and trip clippy's
 // deny-level approx_constant lint (it reads them as sloppy pi). They are genuine exhibit
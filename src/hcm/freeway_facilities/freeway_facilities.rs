@@ -17,6 +17,15 @@
 //! - facility performance aggregation and LOS (Steps A-15/A-17; Equations
 //!   10-1, 25-2 through 25-5; Exhibit 10-6).
 //!
+//! Segments given a [`SpecificGrade`] are evaluated with the mixed-flow model
+//! instead of Exhibit 12-25 PCEs: an isolated grade with Chapter 26
+//! ([`MixedFlowSegment`]), a run of consecutive graded segments as a Chapter
+//! 25 composite grade ([`CompositeGrade`]). A graded ramp or weaving segment
+//! stays in the run, so trucks leave it at the speed they entered with plus
+//! its own grade; its Chapter 13/14 engine sees the trucks only through the
+//! mixed-flow capacity and speed ratios. Mountainous terrain has no PCE
+//! (Chapter 12), so a mountainous facility must grade every segment.
+//!
 //! Managed-lane facilities (Steps A-9/A-13/A-14) and the Chapter 25
 //! planning-level method are not implemented in this pass.
 //!
@@ -29,6 +38,8 @@ use crate::hcm::basicfreeways::basicfreeways::{
     basic_segment_breakpoint, basic_segment_capacity, basic_segment_speed, DENSITY_AT_CAPACITY,
     EXPONENT_BASIC_FREEWAY,
 };
+use crate::hcm::basicfreeways::composite_grade::{CompositeGrade, GradeSegment};
+use crate::hcm::basicfreeways::mixed_flow::{speed_auto_only, MixedFlowSegment};
 use crate::hcm::weaving::weaving::{
     FacilityType as WeaveFacility, TerrainType as WeaveTerrain, WeavingSegment,
     WeavingType,
@@ -117,6 +128,14 @@ impl Default for Terrain {
 impl Terrain {
     /// Passenger-car equivalent E_T (Exhibit 12-25: level 2.0, rolling 3.0).
     ///
+    /// A mountainous facility no longer reaches this value on its segments:
+    /// [`FreewayFacility::validate`] requires a [`SpecificGrade`] on every
+    /// segment, so segment capacities, speeds and densities come from the
+    /// mixed-flow model and the ramp and weaving engines run without trucks. The
+    /// value below survives only where the facility-wide f_HV is still read:
+    /// the oversaturated engine's jam density and on-ramp capacities, and
+    /// managed lanes.
+    ///
     /// VERIFY-HCM: Exhibit 12-25 tabulates level and rolling only, and the Chapter 12 text is
     /// explicit that "No PCE is provided for mountainous terrain" and that the Chapter 25/26
     /// mixed-flow model "must be used" instead. Chapter 10 does not disagree, it never offers
@@ -148,7 +167,7 @@ impl Terrain {
         }
     }
 
-    fn to_ramp(self) -> RampTerrain {
+    pub(crate) fn to_ramp(self) -> RampTerrain {
         match self {
             Terrain::Level => RampTerrain::Level,
             Terrain::Rolling => RampTerrain::Rolling,
@@ -157,6 +176,19 @@ impl Terrain {
     }
 }
 
+/// A specific grade on a segment (the Chapter 10 terrain input "specific
+/// grade"), analyzed with the Chapter 25/26 mixed-flow model. The
+/// grade runs the length of the segment; a longer or changing grade is coded
+/// as consecutive graded segments, which are chained as a composite grade.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpecificGrade {
+    /// Grade, percent (5.0 is a 5% upgrade).
+    pub grade: f64,
+    /// Single-unit trucks as a share of the facility's heavy vehicles,
+    /// decimal; the rest are tractor-trailers.
+    pub sut_share: f64,
+}
+
 /// One HCM analysis segment of a freeway facility. Demand-side vectors are
 /// indexed by analysis period.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -181,10 +213,18 @@ pub struct FacilitySegment {
     pub caf_schedule: Option<Vec<f64>>,
     /// Optional per-period SAF schedule (overrides `saf` where present).
     pub saf_schedule: Option<Vec<f64>>,
+    /// Optional per-period number of open mainline lanes (overrides
+    /// `lanes` where present). Used to model lane closures, e.g. an
+    /// incident blocking lanes for part of the study period; capacity,
+    /// per-lane density and queue storage all follow the open-lane count.
+    pub lanes_schedule: Option<Vec<u32>>,
     /// Work zone active on this segment (Chapter 10 Section 4); derives
     /// CAF_wz/SAF_wz via Equations 10-7 through 10-12 and multiplies the
     /// calibration factors.
     pub work_zone: Option<WorkZone>,
+    /// Specific grade. When present the segment is evaluated with the
+    /// mixed-flow model rather than Exhibit 12-25 PCEs.
+    pub grade: Option<SpecificGrade>,
 
     // ── Ramp attributes (Merge / Diverge / Weaving) ──────────────────────
     /// On-ramp demand by period, veh/h (Merge and Weaving segments).
@@ -227,7 +267,9 @@ impl Default for FacilitySegment {
             daf: 1.0,
             caf_schedule: None,
             saf_schedule: None,
+            lanes_schedule: None,
             work_zone: None,
+            grade: None,
             on_ramp_demand: Vec::new(),
             off_ramp_demand: Vec::new(),
             ramp_to_ramp_demand: Vec::new(),
@@ -249,6 +291,26 @@ impl FacilitySegment {
         self.length_ft / 5280.0
     }
 
+    /// Number of open mainline lanes in period `p` (`lanes_schedule` where
+    /// present, else `lanes`).
+    pub fn lanes_at(&self, p: usize) -> u32 {
+        self.lanes_schedule
+            .as_ref()
+            .and_then(|v| v.get(p).copied())
+            .unwrap_or(self.lanes)
+    }
+
+    /// Open lanes in period `p` as seen by the Chapter 13 and 14 engines,
+    /// which define no ramp junction or weaving model for a single-lane
+    /// freeway.
+    ///
+    /// VERIFY-HCM: a closure down to one open lane evaluates the merge,
+    /// diverge or weaving engine at two lanes; the closure still reaches
+    /// capacity and per-lane density through [`Self::lanes_at`].
+    fn engine_lanes_at(&self, p: usize) -> u32 {
+        self.lanes_at(p).max(2)
+    }
+
     fn on_demand(&self, p: usize) -> f64 {
         self.on_ramp_demand.get(p).copied().unwrap_or(0.0) * self.daf
     }
@@ -412,6 +474,18 @@ impl Default for FreewayFacility {
     }
 }
 
+/// Mixed-flow results for a graded segment at a given flow.
+struct GradeEval {
+    /// Mixed-flow capacity, veh/h/ln (Equation 26-5).
+    capacity: f64,
+    /// Mixed-flow capacity adjustment factor CAF_mix (Equation 26-1).
+    caf_mix: f64,
+    /// Mixed-flow speed, mi/h.
+    speed: f64,
+    /// Auto-only speed at the same flow, mi/h (Equation 26-10).
+    speed_ao: f64,
+}
+
 /// Result of a single-segment engine evaluation at a given served volume.
 struct EngineEval {
     /// Space mean speed across all segment lanes, mi/h.
@@ -446,6 +520,119 @@ impl FreewayFacility {
         1.0 / (1.0 + self.heavy_vehicle_pct * (self.terrain.pce() - 1.0))
     }
 
+    /// Heavy vehicle factor for segment `i`'s pc densities: 1.0 on graded
+    /// segments, whose mixed-flow capacities and speeds already carry the
+    /// trucks, [`Self::f_hv`] elsewhere.
+    ///
+    /// VERIFY-HCM: Chapter 26 reports the mixed-flow density in veh/mi/ln and
+    /// reads LOS from it against the Exhibit 12-15 thresholds without a PCE
+    /// conversion; this follows that reading.
+    fn seg_f_hv(&self, i: usize) -> f64 {
+        if self.graded(i) {
+            1.0
+        } else {
+            self.f_hv()
+        }
+    }
+
+    /// Whether segment `i` has a specific grade.
+    fn graded(&self, i: usize) -> bool {
+        self.segments.get(i).is_some_and(|s| s.grade.is_some())
+    }
+
+    /// Mixed-flow evaluation of graded segment `i` in period `p` at `v_mix`
+    /// veh/h/ln.
+    ///
+    /// A graded segment with no graded neighbor is a single grade (Chapter
+    /// 26). Otherwise it belongs to a composite grade (Chapter 25) starting at
+    /// the first graded segment of its run, whatever the segment types in it;
+    /// the trucks' kinematic state entering segment `i` depends only on the
+    /// grades upstream of it, so the composite is analyzed through `i` at
+    /// segment `i`'s own flow. The truck split, FFS and CAF are segment `i`'s.
+    /// Above capacity the speeds are held at their values at capacity, as
+    /// [`Self::basic_speed`] does.
+    fn grade_eval(&self, i: usize, p: usize, v_mix: f64) -> Result<GradeEval, String> {
+        let grade = self.segments[i].grade.expect("graded segment");
+        let ffs = self.seg_ffs(i);
+        let caf_ao = self.effective_caf(i, p);
+        let p_sut = self.heavy_vehicle_pct * grade.sut_share;
+        let p_tt = self.heavy_vehicle_pct - p_sut;
+        let start = (0..i).rev().take_while(|&j| self.graded(j)).last().unwrap_or(i);
+
+        if start == i && !self.graded(i + 1) {
+            let mut single = MixedFlowSegment {
+                ffs,
+                length: self.segments[i].length_mi(),
+                grade: grade.grade,
+                v_mix,
+                p_sut,
+                p_tt,
+                caf_ao,
+            };
+            let mut r = single.analyze()?;
+            if r.oversaturated {
+                single.v_mix = r.capacity_mix;
+                r = single.analyze()?;
+            }
+            return Ok(GradeEval {
+                capacity: r.capacity_mix,
+                caf_mix: r.caf_mix,
+                speed: r.s_mix.unwrap_or(r.s_calib_cap),
+                speed_ao: speed_auto_only(ffs, single.v_mix, r.caf_mix, r.capacity_ao, r.bp_ao),
+            });
+        }
+
+        let mut composite = CompositeGrade {
+            ffs,
+            v_mix,
+            p_sut,
+            p_tt,
+            segments: self.segments[start..=i]
+                .iter()
+                .map(|s| GradeSegment {
+                    length: s.length_mi(),
+                    grade: s.grade.map_or(0.0, |g| g.grade),
+                })
+                .collect(),
+            caf_ao,
+        };
+        let last = |c: &CompositeGrade| -> Result<GradeEval, String> {
+            let r = c.analyze()?;
+            let seg = r.segments.last().expect("at least one segment");
+            Ok(GradeEval {
+                capacity: seg.capacity_mix,
+                caf_mix: seg.caf_mix,
+                speed: seg.s_mix,
+                speed_ao: seg.s_ao,
+            })
+        };
+        let eval = last(&composite)?;
+        if v_mix <= eval.capacity {
+            return Ok(eval);
+        }
+        composite.v_mix = eval.capacity;
+        last(&composite)
+    }
+
+    /// Heavy vehicle share, terrain and CAF the Chapter 13/14 engines run
+    /// segment `i` with in period `p`.
+    ///
+    /// On a graded segment the trucks leave the engine, which then sees an
+    /// all-automobile stream (f_HV = 1), and return as the mixed-flow CAF_mix
+    /// on capacity and the ratio of mixed-flow to auto-only speed on speed
+    /// (see [`Self::engine_eval`]). VERIFY-HCM: Chapters 13 and 14 have no
+    /// mixed-flow form and Chapter 25 chains composite grades over basic
+    /// segments only; this carries the truck kinematics through the ramp or
+    /// weave so the grade downstream of it starts from the trucks' speeds.
+    fn engine_trucks(&self, i: usize, p: usize) -> Result<(f64, Terrain, f64), String> {
+        if self.graded(i) {
+            let eval = self.grade_eval(i, p, 0.0)?;
+            Ok((0.0, Terrain::Level, eval.caf_mix))
+        } else {
+            Ok((self.heavy_vehicle_pct, self.terrain, self.effective_caf(i, p)))
+        }
+    }
+
     fn seg_ffs(&self, i: usize) -> f64 {
         self.segments[i].ffs.unwrap_or(self.ffs)
     }
@@ -557,20 +744,26 @@ impl FreewayFacility {
     /// effects. Weaving capacities vary by period with the demand pattern
     /// (Chapter 13); other types are constant unless scheduled factors or
     /// work zones apply.
-    pub fn compute_capacities(&mut self) {
+    ///
+    /// Fails when a graded segment's mixed-flow model cannot be evaluated,
+    /// which [`Self::validate`] reports up front.
+    pub fn compute_capacities(&mut self) -> Result<(), String> {
         let n = self.num_segments();
         let p_count = self.num_periods();
-        let f_hv = self.f_hv();
         self.capacity = vec![vec![0.0; p_count]; n];
         for i in 0..n {
+            let f_hv = self.seg_f_hv(i);
             for p in 0..p_count {
                 // Capacity reads the unadjusted FFS throughout (Equations 12-6/12-7 as corrected
                 // December 2022); SAF reaches capacity only through CAF, and the weaving engine
                 // applies its own SAF internally.
                 let ffs = self.seg_ffs(i);
-                let caf = self.effective_caf(i, p);
-                let lanes = f64::from(self.segments[i].lanes);
+                let (_, _, caf) = self.engine_trucks(i, p)?;
+                let lanes = f64::from(self.segments[i].lanes_at(p));
                 let cap = match self.segments[i].seg_type {
+                    SegmentType::Basic | SegmentType::OverlappingRamp if self.graded(i) => {
+                        self.grade_eval(i, p, 0.0)?.capacity * lanes
+                    }
                     SegmentType::Basic | SegmentType::OverlappingRamp => {
                         self.base_capacity_pc(ffs) * caf * lanes * f_hv
                     }
@@ -580,7 +773,7 @@ impl FreewayFacility {
                         get_freeway_capacity_per_lane(ffs) * caf * lanes * f_hv
                     }
                     SegmentType::Weaving => {
-                        let mut weave = self.build_weave(i, p, self.demand[i][p]);
+                        let mut weave = self.build_weave(i, p, self.demand[i][p])?;
                         weave.determine_demand_flow();
                         weave.determine_configuration_characteristics();
                         weave.determine_max_weaving_length();
@@ -596,6 +789,7 @@ impl FreewayFacility {
                 self.capacity[i][p] = cap;
             }
         }
+        Ok(())
     }
 
     // ── Step A-10: demand-to-capacity ratios ─────────────────────────────
@@ -628,7 +822,7 @@ impl FreewayFacility {
     /// Build a Chapter 13 weaving engine for segment `i`, period `p`, with
     /// total segment volume `volume` (veh/h). Component flows follow the
     /// Chapter 10 Step A-3 split with the given ramp-to-ramp demand.
-    fn build_weave(&self, i: usize, p: usize, volume: f64) -> WeavingSegment {
+    fn build_weave(&self, i: usize, p: usize, volume: f64) -> Result<WeavingSegment, String> {
         let seg = &self.segments[i];
         let scale = if self.demand[i][p] > 0.0 {
             volume / self.demand[i][p]
@@ -640,37 +834,45 @@ impl FreewayFacility {
         let v_fr = (seg.off_demand(p) * scale - v_rr).max(0.0);
         let v_ff = (volume - v_rf - v_rr - v_fr).max(0.0);
         let ffs_unadjusted = self.seg_ffs(i); // SAF applied inside the engine
-        WeavingSegment {
+        let (heavy_vehicle_pct, terrain, caf) = self.engine_trucks(i, p)?;
+        Ok(WeavingSegment {
             weaving_type: WeavingType::OneSided,
             facility_type: WeaveFacility::Freeway,
             length_short: seg.short_length_ft.unwrap_or(seg.length_ft),
-            num_lanes: seg.lanes,
-            num_weaving_lanes: seg.num_weaving_lanes,
+            num_lanes: seg.engine_lanes_at(p),
+            num_weaving_lanes: seg.num_weaving_lanes.min(seg.engine_lanes_at(p)),
             ffs: ffs_unadjusted,
             v_ff,
             v_fr,
             v_rf,
             v_rr,
             phf: self.phf,
-            heavy_vehicle_pct: self.heavy_vehicle_pct,
-            terrain: self.terrain.to_weave(),
+            heavy_vehicle_pct,
+            terrain: terrain.to_weave(),
             lc_rf: seg.lc_rf,
             lc_fr: seg.lc_fr,
             lc_rr: 0,
             interchange_density: self.interchange_density.unwrap_or(self.total_ramp_density),
             basic_freeway_capacity: self.base_capacity_pc(self.seg_ffs(i)),
-            caf: self.effective_caf(i, p),
+            caf,
             saf: self.effective_saf(i, p),
             ..Default::default()
-        }
+        })
     }
 
     /// Build a Chapter 14 ramp engine for segment `i`, period `p`.
     /// `mainline` approaches the junction and `ramp` uses it, both veh/h.
-    fn build_ramp(&self, i: usize, p: usize, mainline: f64, ramp: f64) -> RampSegment {
+    fn build_ramp(
+        &self,
+        i: usize,
+        p: usize,
+        mainline: f64,
+        ramp: f64,
+    ) -> Result<RampSegment, String> {
         let seg = &self.segments[i];
         let is_merge = seg.seg_type == SegmentType::Merge;
-        RampSegment {
+        let (heavy_vehicle_pct, terrain, caf) = self.engine_trucks(i, p)?;
+        Ok(RampSegment {
             ramp_type: if is_merge {
                 RampType::OnRamp
             } else {
@@ -678,7 +880,7 @@ impl FreewayFacility {
             },
             ramp_side: RampSide::Right,
             ramp_lanes: RampLanes::OneLane,
-            freeway_lanes: seg.lanes,
+            freeway_lanes: seg.engine_lanes_at(p),
             freeway_ffs: self.seg_ffs(i),
             ramp_ffs: seg.ramp_ffs,
             accel_lane_length: Some(seg.accel_lane_ft),
@@ -686,15 +888,15 @@ impl FreewayFacility {
             freeway_demand: mainline,
             ramp_demand: ramp,
             phf: self.phf,
-            heavy_vehicle_pct: self.heavy_vehicle_pct,
+            heavy_vehicle_pct,
             ramp_heavy_vehicle_pct: None,
-            terrain: self.terrain.to_ramp(),
+            terrain: terrain.to_ramp(),
             adjacent_upstream: AdjacentRampType::None,
             adjacent_downstream: AdjacentRampType::None,
-            caf: self.effective_caf(i, p),
+            caf,
             saf: self.effective_saf(i, p),
             ..Default::default()
-        }
+        })
     }
 
     /// Space mean speed of a basic freeway segment at per-lane flow `v_p`
@@ -718,23 +920,50 @@ impl FreewayFacility {
     /// Evaluate segment `i` in period `p` with the appropriate Chapter
     /// 12/13/14 engine at served volume `volume` (veh/h) with ramp flows
     /// `onr`/`offr` (veh/h).
-    fn engine_eval(&self, i: usize, p: usize, volume: f64, onr: f64, offr: f64) -> EngineEval {
+    fn engine_eval(
+        &self,
+        i: usize,
+        p: usize,
+        volume: f64,
+        onr: f64,
+        offr: f64,
+    ) -> Result<EngineEval, String> {
         let seg = &self.segments[i];
         let f_hv = self.f_hv();
         let ffs = self.seg_ffs(i);
         let ffs_adj = ffs * self.effective_saf(i, p);
         let caf = self.effective_caf(i, p);
-        let basic = |v: f64| {
-            let v_p = v / (f64::from(seg.lanes) * f_hv * self.phf);
-            self.basic_speed(v_p, ffs, ffs_adj, caf)
+        let grade = |v: f64| {
+            let v_mix = v / (f64::from(seg.lanes_at(p)) * self.phf);
+            self.grade_eval(i, p, v_mix)
+        };
+        let basic = |v: f64| -> Result<f64, String> {
+            if self.graded(i) {
+                // VERIFY-HCM: the mixed-flow model has no SAF input; SAF is
+                // applied to its speed as Equation 10-4 applies it to FFS.
+                return Ok(grade(v)?.speed * self.effective_saf(i, p));
+            }
+            let v_p = v / (f64::from(seg.lanes_at(p)) * f_hv * self.phf);
+            Ok(self.basic_speed(v_p, ffs, ffs_adj, caf))
         };
-        match seg.seg_type {
+        // The Chapter 13/14 engines run a graded segment without its trucks
+        // (`engine_trucks`); they slow its stream by the same ratio the
+        // mixed-flow model slows the auto-only stream.
+        let trucks = |v: f64| -> Result<f64, String> {
+            if self.graded(i) {
+                let eval = grade(v)?;
+                Ok(eval.speed / eval.speed_ao)
+            } else {
+                Ok(1.0)
+            }
+        };
+        Ok(match seg.seg_type {
             SegmentType::Basic | SegmentType::OverlappingRamp => EngineEval {
-                speed: basic(volume),
+                speed: basic(volume)?,
                 influence_density_pc: None,
             },
             SegmentType::Merge => {
-                let mut ramp = self.build_ramp(i, p, (volume - onr).max(0.0), onr);
+                let mut ramp = self.build_ramp(i, p, (volume - onr).max(0.0), onr)?;
                 ramp.run_analysis();
                 // VERIFY-HCM: in the facility context, ramp segment speeds
                 // at high flows are additionally bounded by the Chapter 12
@@ -743,35 +972,35 @@ impl FreewayFacility {
                 // speed matrices (e.g., Exhibit 25-49, Segment 10, Analysis
                 // Period 3: 51.8 mi/h = the Equation 12-1 value).
                 EngineEval {
-                    speed: ramp.get_speed_avg().min(basic(volume)),
+                    speed: (ramp.get_speed_avg() * trucks(volume)?).min(basic(volume)?),
                     influence_density_pc: ramp.density,
                 }
             }
             SegmentType::Diverge => {
-                let mut ramp = self.build_ramp(i, p, volume, offr);
+                let mut ramp = self.build_ramp(i, p, volume, offr)?;
                 ramp.run_analysis();
                 // VERIFY-HCM: same basic speed–flow cap as merge (above).
                 EngineEval {
-                    speed: ramp.get_speed_avg().min(basic(volume)),
+                    speed: (ramp.get_speed_avg() * trucks(volume)?).min(basic(volume)?),
                     influence_density_pc: ramp.density,
                 }
             }
             SegmentType::Weaving => {
-                let mut weave = self.build_weave(i, p, volume);
+                let mut weave = self.build_weave(i, p, volume)?;
                 weave.run_analysis();
                 if weave.is_weaving_segment() {
                     EngineEval {
-                        speed: weave.get_speed_avg(),
+                        speed: weave.get_speed_avg() * trucks(volume)?,
                         influence_density_pc: None,
                     }
                 } else {
                     EngineEval {
-                        speed: basic(volume),
+                        speed: basic(volume)?,
                         influence_density_pc: None,
                     }
                 }
             }
-        }
+        })
     }
 
     /// Density-based LOS for segment `i` from its per-lane pc density and
@@ -843,20 +1072,18 @@ impl FreewayFacility {
         queued: &[bool],
         queued_speed: &[f64],
         queued_density_veh: &[f64],
-    ) {
+    ) -> Result<(), String> {
         let n = self.num_segments();
-        let f_hv = self.f_hv();
         let mut prev_speed: Option<(f64, f64)> = None; // (speed, midpoint ft)
         let mut midpoint_ft = 0.0;
 
         for i in 0..n {
             let seg_mid = midpoint_ft + self.segments[i].length_ft / 2.0;
-            let lanes = f64::from(self.segments[i].lanes);
+            let lanes = f64::from(self.segments[i].lanes_at(p));
             let (speed, influence) = if queued[i] {
                 (queued_speed[i], None)
             } else {
-                let eval = self.engine_eval(i, p, served[i], onr[i], offr[i
-                    + 1]);
+                let eval = self.engine_eval(i, p, served[i], onr[i], offr[i + 1])?;
                 // Equation 25-1: maximum achievable speed given the
                 // upstream segment's speed.
                 let capped = match prev_speed {
@@ -886,7 +1113,7 @@ impl FreewayFacility {
                 }
                 if i + 1 < n && self.segments[i + 1].seg_type == SegmentType::Diverge {
                     let next =
-                        self.engine_eval(i + 1, p, served[i + 1], onr[i + 1], offr[i + 2]);
+                        self.engine_eval(i + 1, p, served[i + 1], onr[i + 1], offr[i + 2])?;
                     worst = worst.min(next.speed);
                 }
                 worst
@@ -901,7 +1128,7 @@ impl FreewayFacility {
             } else {
                 0.0
             };
-            let density_pc = density_veh / f_hv;
+            let density_pc = density_veh / self.seg_f_hv(i);
 
             self.volume_served[i][p] = served[i];
             self.vc_ratio[i][p] = if self.capacity[i][p] > 0.0 {
@@ -922,32 +1149,32 @@ impl FreewayFacility {
             prev_speed = Some((speed, seg_mid));
             midpoint_ft += self.segments[i].length_ft;
         }
+        Ok(())
     }
 
     /// Step A-11: undersaturated evaluation of period `p` (volume served
     /// equals demand; Chapter 25 Section 3).
-    fn analyze_undersaturated_period(&mut self, p: usize) {
+    fn analyze_undersaturated_period(&mut self, p: usize) -> Result<(), String> {
         let n = self.num_segments();
         let served: Vec<f64> = (0..n).map(|i| self.demand[i][p]).collect();
         let onr = self.onrd_by_node(p);
         let offr = self.offrd_by_node(p);
         let queued = vec![false; n];
         let zeros = vec![0.0; n];
-        self.evaluate_period_chain(p, &served, &onr, &offr, &queued, &zeros, &zeros);
+        self.evaluate_period_chain(p, &served, &onr, &offr, &queued, &zeros, &zeros)
     }
 
     /// Step A-12: oversaturated evaluation from period `first` to the end
     /// of the study period (Chapter 25 Section 4 time-step engine).
-    fn analyze_oversaturated(&mut self, first: usize) {
+    fn analyze_oversaturated(&mut self, first: usize) -> Result<(), String> {
         let n = self.num_segments();
         let p_count = self.num_periods();
         let f_hv = self.f_hv();
-        let lanes: Vec<f64> = self.segments.iter().map(|s| f64::from(s.lanes)).collect();
         let lengths: Vec<f64> = self.segments.iter().map(|s| s.length_mi()).collect();
 
         let mut engine = OversaturatedEngine::new(
             lengths.clone(),
-            lanes.clone(),
+            self.segments.iter().map(|s| f64::from(s.lanes_at(first))).collect(),
             f_hv,
             self.jam_density_pc,
             self.queue_discharge_drop,
@@ -955,6 +1182,14 @@ impl FreewayFacility {
         );
 
         for p in first..p_count {
+            // Lane closures change the storage and background density of
+            // the affected segments for the periods they are active.
+            let lanes: Vec<f64> = self
+                .segments
+                .iter()
+                .map(|s| f64::from(s.lanes_at(p)))
+                .collect();
+            engine.lanes = lanes.clone();
             let onrd = self.onrd_by_node(p);
             let offrd = self.offrd_by_node(p);
             let capacity: Vec<f64> = (0..n).map(|i| self.capacity[i][p]).collect();
@@ -976,14 +1211,14 @@ impl FreewayFacility {
             let kb: Vec<f64> = (0..n)
                 .map(|i| {
                     let onr_ed = onrd[i].min(ed[i]);
-                    let eval = self.engine_eval(i, p, ed[i], onr_ed, offrd[i + 1]);
-                    if eval.speed > 0.0 {
+                    let eval = self.engine_eval(i, p, ed[i], onr_ed, offrd[i + 1])?;
+                    Ok(if eval.speed > 0.0 {
                         ed[i] / lanes[i] / eval.speed
                     } else {
                         ed[i] / lanes[i] / 1.0
-                    }
+                    })
                 })
-                .collect();
+                .collect::<Result<_, String>>()?;
 
             // Front-clearing-queue detection (Equation 25-12).
             let front_clearing: Vec<bool> = (0..n)
@@ -1060,7 +1295,7 @@ impl FreewayFacility {
                 &res.had_queue,
                 &queued_speed,
                 &res.density,
-            );
+            )?;
             for i in 0..n {
                 self.had_queue[i][p] = res.had_queue[i];
                 self.queue_length_ft[i][p] = res.queue_length_ft[i];
@@ -1068,6 +1303,7 @@ impl FreewayFacility {
             }
             self.unserved_entry_veh[p] = res.entry_queue_end[0];
         }
+        Ok(())
     }
 
     /// Off-ramp diverge percentages `OFRD(i, p) / SD(i − 1, p)` by node.
@@ -1096,18 +1332,18 @@ impl FreewayFacility {
     pub fn compute_facility_performance(&mut self) {
         let n = self.num_segments();
         let p_count = self.num_periods();
-        let f_hv = self.f_hv();
         self.facility_performance = Vec::with_capacity(p_count);
         for p in 0..p_count {
             let flows: Vec<f64> = (0..n).map(|i| self.volume_served[i][p]).collect();
             let lengths: Vec<f64> = self.segments.iter().map(|s| s.length_ft).collect();
             let speeds: Vec<f64> = (0..n).map(|i| self.speed[i][p]).collect();
             let dens_veh: Vec<f64> = (0..n).map(|i| self.density_veh[i][p]).collect();
-            let lanes: Vec<f64> = self.segments.iter().map(|s| f64::from(s.lanes)).collect();
+            let dens_pc: Vec<f64> = (0..n).map(|i| self.density_pc[i][p]).collect();
+            let lanes: Vec<f64> = self.segments.iter().map(|s| f64::from(s.lanes_at(p))).collect();
 
             let sms = exhibits::facility_space_mean_speed(&flows, &lengths, &speeds);
             let k_veh = exhibits::facility_density(&dens_veh, &lengths, &lanes);
-            let k_pc = k_veh / f_hv;
+            let k_pc = exhibits::facility_density(&dens_pc, &lengths, &lanes);
             let any_over = (0..n).any(|i| self.dc_ratio[i][p] > 1.0);
             let los = exhibits::los_freeway_facility(k_pc, any_over, self.city_type);
 
@@ -1168,7 +1404,7 @@ impl FreewayFacility {
         let mut den = 0.0;
         for p in 0..self.num_periods() {
             for i in 0..self.num_segments() {
-                let w = self.segments[i].length_ft * f64::from(self.segments[i].lanes);
+                let w = self.segments[i].length_ft * f64::from(self.segments[i].lanes_at(p));
                 num += self.density_veh[i][p] * w;
                 den += w;
             }
@@ -1183,7 +1419,9 @@ impl FreewayFacility {
     // ── Validation ───────────────────────────────────────────────────────
 
     /// Basic structural checks per the Chapter 10 guidance (first/last
-    /// segment basic; demand vectors sized to the study period).
+    /// segment basic; demand vectors sized to the study period), and the
+    /// specific-grade inputs: every segment of a mountainous facility graded,
+    /// and each grade inside the mixed-flow model's domain.
     pub fn validate(&self) -> Result<(), String> {
         if self.segments.is_empty() {
             return Err("facility has no segments".into());
@@ -1205,6 +1443,33 @@ impl FreewayFacility {
             if seg.lanes < 2 {
                 return Err(format!("segment {i} must have at least 2 lanes"));
             }
+            if let Some(schedule) = &seg.lanes_schedule {
+                if schedule.iter().any(|&l| l == 0 || l > seg.lanes) {
+                    return Err(format!(
+                        "segment {i} lanes_schedule must keep between 1 and {} lanes open",
+                        seg.lanes
+                    ));
+                }
+            }
+            if let Some(g) = &seg.grade {
+                if !g.grade.is_finite() || !(0.0..=1.0).contains(&g.sut_share) {
+                    return Err(format!(
+                        "segment {i} grade needs a finite percent and a sut_share in [0, 1]"
+                    ));
+                }
+            } else if self.terrain == Terrain::Mountainous {
+                return Err(format!(
+                    "segment {i} has no specific grade: Chapter 12 provides no PCE for \
+                     mountainous terrain, so each segment needs a grade for the mixed-flow \
+                     model (Chapters 25/26)"
+                ));
+            }
+        }
+        for i in (0..self.segments.len()).filter(|&i| self.graded(i)) {
+            for p in 0..self.num_periods() {
+                self.grade_eval(i, p, 0.0)
+                    .map_err(|e| format!("segment {i} grade: {e}"))?;
+            }
         }
         Ok(())
     }
@@ -1214,17 +1479,17 @@ impl FreewayFacility {
     pub fn run_analysis(&mut self) -> Result<(), String> {
         self.validate()?;
         self.compute_demands(); // Steps A-3/A-4
-        self.compute_capacities(); // Steps A-7/A-8
+        self.compute_capacities()?; // Steps A-7/A-8
         self.compute_dc_ratios(); // Step A-10
         self.alloc_results();
 
         let first = self.first_oversat_period;
         let undersat_until = first.unwrap_or(self.num_periods());
         for p in 0..undersat_until {
-            self.analyze_undersaturated_period(p); // Step A-11
+            self.analyze_undersaturated_period(p)?; // Step A-11
         }
         if let Some(f) = first {
-            self.analyze_oversaturated(f); // Step A-12
+            self.analyze_oversaturated(f)?; // Step A-12
         }
         self.compute_facility_performance(); // Steps A-15/A-17
         Ok(())
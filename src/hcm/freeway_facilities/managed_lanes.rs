@@ -17,6 +17,11 @@
 //!   12-18/12-19): an ML segment without a physical barrier loses speed when
 //!   the adjacent GP lane density exceeds 35 pc/mi/ln — evaluated by the
 //!   Chapter 12 ML segment engine ([`ManagedLaneSegment`]);
+//! - **direct-access ramp junctions** on the managed lane
+//!   ([`MlRampJunction`]): an on- or off-ramp connecting straight to the ML,
+//!   usually from the median, analyzed with the Chapter 14 merge/diverge
+//!   engine on the ML lane group, with the Exhibit 14-18 left-hand factors
+//!   and the ramp roadway capacity check;
 //! - facility-level **lane-group and combined aggregation** (Steps A-14/A-17;
 //!   Equations 10-1/25-2; Exhibit 10-6).
 //!
@@ -39,6 +44,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::hcm::basicfreeways::managed_lanes::{ManagedLaneSegment, ManagedLaneType};
 use crate::hcm::common::LevelOfService;
+use crate::hcm::merge_diverge::merge_diverge::{RampLanes, RampSegment, RampSide, RampType};
 
 use super::exhibits::{self, los_freeway_facility};
 use super::freeway_facilities::{FreewayFacility, PeriodPerformance};
@@ -99,6 +105,86 @@ impl CrossWeave {
     }
 }
 
+// ═════════════════════════════════════════════════════════════════════════
+// Managed-lane ramp junctions (Chapter 14 on the ML lane group)
+// ═════════════════════════════════════════════════════════════════════════
+
+/// A ramp that joins or leaves the managed lane directly (a direct-access
+/// ramp or direct connector), so its vehicles never cross the GP lanes.
+///
+/// The junction is analyzed with the Chapter 14 merge/diverge engine, with
+/// the ML lane group standing in for the freeway: its lanes, FFS, CAF and
+/// SAF, and the ML demand approaching the junction. Direct-access ramps
+/// usually enter from the median, so the ramp defaults to the left side and
+/// takes the Exhibit 14-18 factors on an ML of three or more lanes.
+///
+/// VERIFY-HCM: Chapter 14 is calibrated on GP ramp junctions and the HCM
+/// gives no separate method for ML ramp junctions, so applying it to the ML
+/// lane group is an extension; no example problem covers an ML ramp. A one-
+/// or two-lane ML carries all of its flow in Lanes 1 and 2 (P_FM = P_FD =
+/// 1.000, the four-lane freeway case).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MlRampJunction {
+    /// `OnRamp` (merge into the ML) or `OffRamp` (diverge from it). The ramp
+    /// demand is the ML segment's `on_ramp_demand` or `off_ramp_demand`.
+    pub ramp_type: RampType,
+    /// Side of the ML the ramp joins.
+    pub ramp_side: RampSide,
+    /// Ramp lanes at the junction.
+    pub ramp_lanes: RampLanes,
+    /// Ramp free-flow speed S_FR, mi/h.
+    pub ramp_ffs: f64,
+    /// Acceleration lane length L_A (first lane on a two-lane ramp), ft.
+    pub accel_lane_ft: f64,
+    /// Second acceleration lane length (two-lane on-ramp), ft.
+    pub accel_lane2_ft: Option<f64>,
+    /// Deceleration lane length L_D (first lane on a two-lane ramp), ft.
+    pub decel_lane_ft: f64,
+    /// Second deceleration lane length (two-lane off-ramp), ft.
+    pub decel_lane2_ft: Option<f64>,
+}
+
+impl Default for MlRampJunction {
+    fn default() -> Self {
+        Self {
+            ramp_type: RampType::OnRamp,
+            ramp_side: RampSide::Left,
+            ramp_lanes: RampLanes::OneLane,
+            ramp_ffs: 45.0,
+            accel_lane_ft: 800.0,
+            accel_lane2_ft: None,
+            decel_lane_ft: 400.0,
+            decel_lane2_ft: None,
+        }
+    }
+}
+
+/// Chapter 14 results for an ML ramp junction in one analysis period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MlRampPerformance {
+    /// ML demand approaching the junction, pc/h (Equation 14-1).
+    pub flow_ml_pc: f64,
+    /// Ramp demand, pc/h (Equation 14-1).
+    pub flow_ramp_pc: f64,
+    /// Ramp influence area density, pc/mi/ln (Equations 14-22/14-23).
+    pub influence_density_pc: f64,
+    /// Average speed of all ML vehicles at the junction, mi/h (Exhibit 14-15).
+    pub speed: f64,
+    /// Ramp roadway capacity, pc/h (Exhibit 14-12).
+    pub ramp_capacity_pc: f64,
+    /// Ramp roadway demand-to-capacity ratio.
+    pub ramp_dc: f64,
+    /// ML demand-to-capacity ratio at the junction's critical checkpoint
+    /// (downstream of a merge, upstream of a diverge), against the ML
+    /// capacity rather than the Exhibit 14-10 GP lane capacity.
+    pub ml_dc: f64,
+    /// Whether the ML or ramp roadway demand exceeds capacity.
+    pub demand_exceeds_capacity: bool,
+    /// Junction LOS (Exhibit 14-3).
+    pub los: LevelOfService,
+}
+
 // ═════════════════════════════════════════════════════════════════════════
 // Managed-lane segment input (parallel lane group)
 // ═════════════════════════════════════════════════════════════════════════
@@ -125,6 +211,9 @@ pub struct MlSegmentInput {
     pub on_ramp_demand: Vec<f64>,
     /// ML off-ramp demand by period, veh/h (ML diverge/access segments).
     pub off_ramp_demand: Vec<f64>,
+    /// Direct-access ramp junction on this segment, if any. Without one, ML
+    /// ramp demands only change the ML demand.
+    pub ramp: Option<MlRampJunction>,
 }
 
 impl Default for MlSegmentInput {
@@ -137,6 +226,7 @@ impl Default for MlSegmentInput {
             saf: 1.0,
             on_ramp_demand: Vec::new(),
             off_ramp_demand: Vec::new(),
+            ramp: None,
         }
     }
 }
@@ -209,6 +299,8 @@ pub struct ManagedLaneFacility {
     pub ml_los: Vec<Vec<LevelOfService>>,
     /// Whether the Step A-13 adjacent friction was active on the ML segment.
     pub ml_friction_active: Vec<Vec<bool>>,
+    /// Chapter 14 results where the ML segment has a direct-access ramp.
+    pub ml_ramp: Vec<Vec<Option<MlRampPerformance>>>,
 
     /// GP lane-group performance by period (Step A-14).
     pub gp_group_performance: Vec<LaneGroupPerformance>,
@@ -234,6 +326,7 @@ impl Default for ManagedLaneFacility {
             ml_density_pc: Vec::new(),
             ml_los: Vec::new(),
             ml_friction_active: Vec::new(),
+            ml_ramp: Vec::new(),
             gp_group_performance: Vec::new(),
             ml_group_performance: Vec::new(),
             facility_performance: Vec::new(),
@@ -314,6 +407,37 @@ impl ManagedLaneFacility {
         ml
     }
 
+    /// Build a Chapter 14 engine for the direct-access ramp on ML segment
+    /// `seg`, with `approaching` ML demand and `ramp` demand (veh/h).
+    fn ml_ramp_engine(
+        &self,
+        seg: &MlSegmentInput,
+        junction: &MlRampJunction,
+        approaching: f64,
+        ramp: f64,
+    ) -> RampSegment {
+        RampSegment {
+            ramp_type: junction.ramp_type,
+            ramp_side: junction.ramp_side,
+            ramp_lanes: junction.ramp_lanes,
+            freeway_lanes: seg.lanes.max(1),
+            freeway_ffs: self.ml_seg_ffs(seg),
+            ramp_ffs: junction.ramp_ffs,
+            accel_lane_length: Some(junction.accel_lane_ft),
+            accel_lane_length2: junction.accel_lane2_ft,
+            decel_lane_length: Some(junction.decel_lane_ft),
+            decel_lane_length2: junction.decel_lane2_ft,
+            freeway_demand: approaching,
+            ramp_demand: ramp,
+            phf: self.gp.phf,
+            heavy_vehicle_pct: self.gp.heavy_vehicle_pct,
+            terrain: self.gp.terrain.to_ramp(),
+            caf: seg.caf,
+            saf: seg.saf,
+            ..Default::default()
+        }
+    }
+
     /// ML segment capacities (veh/h) — the Chapter 12 adjusted per-lane
     /// capacity times the lane count and heavy-vehicle factor.
     fn compute_ml_capacities(&mut self) {
@@ -355,6 +479,19 @@ impl ManagedLaneFacility {
             ));
         }
 
+        for (i, seg) in self.ml.iter().enumerate() {
+            let Some(junction) = seg.as_ref().and_then(|s| s.ramp.as_ref()) else {
+                continue;
+            };
+            if !matches!(junction.ramp_type, RampType::OnRamp | RampType::OffRamp) {
+                return Err(format!(
+                    "ML segment {}: a direct-access ramp is an OnRamp or OffRamp, got {:?}",
+                    i + 1,
+                    junction.ramp_type
+                ));
+            }
+        }
+
         // Step A-9: cross-weave friction on GP capacity, then run GP group.
         self.apply_cross_weave();
         self.gp.run_analysis()?;
@@ -394,6 +531,7 @@ impl ManagedLaneFacility {
         self.ml_density_pc = vec![vec![0.0; p_count]; n];
         self.ml_los = vec![vec![LevelOfService::A; p_count]; n];
         self.ml_friction_active = vec![vec![false; p_count]; n];
+        self.ml_ramp = vec![vec![None; p_count]; n];
 
         for i in 0..n {
             let Some(seg) = self.ml[i].clone() else {
@@ -423,10 +561,86 @@ impl ManagedLaneFacility {
                 );
                 self.ml_friction_active[i][p] =
                     friction_capable && k_gp_pc > ADJACENT_FRICTION_THRESHOLD_PC;
+
+                if let Some(junction) = &seg.ramp {
+                    self.evaluate_ml_ramp(i, p, &seg, junction);
+                }
             }
         }
     }
 
+    /// Run the Chapter 14 engine on the direct-access ramp of ML segment `i`
+    /// in period `p`, and let it govern the segment's speed, density and LOS
+    /// as a GP merge or diverge segment does in the core methodology.
+    fn evaluate_ml_ramp(
+        &mut self,
+        i: usize,
+        p: usize,
+        seg: &MlSegmentInput,
+        junction: &MlRampJunction,
+    ) {
+        let on = seg.on_ramp_demand.get(p).copied().unwrap_or(0.0);
+        let off = seg.off_ramp_demand.get(p).copied().unwrap_or(0.0);
+        let volume = self.ml_demand[i][p];
+        let approaching = volume - on + off;
+        let ramp_demand = match junction.ramp_type {
+            RampType::OnRamp => on,
+            _ => off,
+        };
+        let mut ramp = self.ml_ramp_engine(seg, junction, approaching, ramp_demand);
+        ramp.run_analysis();
+
+        let flow_ramp_pc = ramp.get_flow_ramp();
+        let ramp_capacity_pc = ramp.get_capacity_ramp();
+        let ramp_dc = if ramp_capacity_pc > 0.0 {
+            flow_ramp_pc / ramp_capacity_pc
+        } else {
+            f64::INFINITY
+        };
+        // Chapter 14 checks the mainline against a GP lane's capacity
+        // (Exhibit 14-10); the ML checkpoint uses the ML capacity instead
+        // (Equation 12-14, from the Chapter 12 ML engine).
+        let f_hv = self.gp.f_hv();
+        let critical_pc = match junction.ramp_type {
+            RampType::OnRamp => ramp.get_flow_freeway() + flow_ramp_pc,
+            _ => ramp.get_flow_freeway(),
+        };
+        let ml_capacity_pc = self.ml_capacity[i][p] / f_hv;
+        let ml_dc = if ml_capacity_pc > 0.0 {
+            critical_pc / ml_capacity_pc
+        } else {
+            f64::INFINITY
+        };
+        let over = ml_dc > 1.0 || ramp_dc > 1.0;
+        let los = if over {
+            LevelOfService::F
+        } else {
+            ramp.los.unwrap_or(LevelOfService::F)
+        };
+        // VERIFY-HCM: capped by the ML's own speed at the same volume, the
+        // cap the core methodology puts on GP ramp segments.
+        let speed = ramp.get_speed_avg().min(self.ml_speed[i][p]);
+        if speed > 0.0 {
+            let lanes = f64::from(seg.lanes.max(1));
+            let density_veh = volume / (lanes * self.gp.phf * speed);
+            self.ml_speed[i][p] = speed;
+            self.ml_density_veh[i][p] = density_veh;
+            self.ml_density_pc[i][p] = density_veh / f_hv;
+        }
+        self.ml_los[i][p] = los;
+        self.ml_ramp[i][p] = Some(MlRampPerformance {
+            flow_ml_pc: ramp.get_flow_freeway(),
+            flow_ramp_pc,
+            influence_density_pc: ramp.get_density(),
+            speed,
+            ramp_capacity_pc,
+            ramp_dc,
+            ml_dc,
+            demand_exceeds_capacity: over,
+            los,
+        });
+    }
+
     /// Steps A-14/A-17: lane-group and combined facility aggregation.
     fn aggregate_performance(&mut self) {
         let n = self.num_segments();
@@ -473,7 +687,10 @@ impl ManagedLaneFacility {
                 ml_speeds.push(self.ml_speed[i][p]);
                 ml_dens.push(self.ml_density_veh[i][p]);
                 ml_lanes.push(f64::from(seg.lanes.max(1)));
-                if self.ml_dc_ratio[i][p] > 1.0 {
+                let ramp_over = self.ml_ramp[i][p]
+                    .as_ref()
+                    .is_some_and(|r| r.demand_exceeds_capacity);
+                if self.ml_dc_ratio[i][p] > 1.0 || ramp_over {
                     ml_over = true;
                 }
             }
@@ -7,7 +7,7 @@
 //! models, and facility-level LOS per Exhibit 10-6.
 //!
 //! This module also covers the managed-lane facility extension (Steps
-//! A-9/A-13/A-14/A-17; [`managed_lanes`]) and the Chapter 25 Section 6
+//! A-9/A-13/A-14/A-17 and direct-access ML ramps; [`managed_lanes`]) and the Chapter 25 Section 6
 //! planning-level method ([`planning`]).
 //!
 //! Out of scope in this pass (documented deferrals): the Chapter 25 Section 4
@@ -31,7 +31,7 @@ pub use freeway_facilities::{
 };
 pub use managed_lanes::{
     cross_weave_caf, cross_weave_crf, CrossWeave, LaneGroupPerformance, ManagedLaneFacility,
-    MlSegmentInput,
+    MlRampJunction, MlRampPerformance, MlSegmentInput,
 };
 pub use oversaturated::{OversatPeriodInput, OversatPeriodResult, OversaturatedEngine};
 pub use planning::{
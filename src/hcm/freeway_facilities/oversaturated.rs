@@ -154,7 +154,8 @@ pub struct OversaturatedEngine {
 impl OversaturatedEngine {
     /// Create an engine for `num_segments` segments.
     ///
-    /// * `length_mi`, `lanes` — static segment geometry
+    /// * `length_mi`, `lanes` — segment geometry (`lanes` may be replaced
+    ///   between periods to model lane closures)
     /// * `f_hv` — heavy-vehicle adjustment factor (converts pc to veh)
     /// * `jam_density_pc` — KJ, pc/mi/ln
     /// * `capacity_drop` — queue discharge drop alpha (Equation 25-29)
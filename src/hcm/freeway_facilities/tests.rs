@@ -6,9 +6,12 @@
 
 use crate::hcm::common::{CityType, LevelOfService};
 
+use crate::hcm::basicfreeways::composite_grade::{CompositeGrade, GradeSegment};
+use crate::hcm::basicfreeways::mixed_flow::MixedFlowSegment;
+
 use super::freeway_facilities::{
-    segment_ramp_section, FacilitySegment, FreewayFacility, SegmentType, Terrain,
-    WEAVE_INFLUENCE_EXTENSION_FT,
+    segment_ramp_section, FacilitySegment, FreewayFacility, SegmentType, SpecificGrade,
+    Terrain, WEAVE_INFLUENCE_EXTENSION_FT,
 };
 
 /// Build the Example Problem 1 facility (Exhibits 25-43/25-44) with the
@@ -119,7 +122,7 @@ fn test_demand_accumulation_matches_exhibit_25_48() {
 fn test_capacities_match_exhibit_25_46() {
     let mut fac = ep1_facility();
     fac.compute_demands();
-    fac.compute_capacities();
+    fac.compute_capacities().unwrap();
     // Basic/ramp segments: 2,300 pc/h/ln x 3 ln x 0.978 = 6,748 veh/h.
     for i in [0usize, 1, 2, 3, 4, 6, 7, 8, 9, 10] {
         approx(fac.capacity[i][0], 6748.0, 5.0, &format!("capacity seg {}", i + 1));
@@ -135,7 +138,7 @@ fn test_capacities_match_exhibit_25_46() {
 fn test_dc_ratios_match_exhibit_25_47() {
     let mut fac = ep1_facility();
     fac.compute_demands();
-    fac.compute_capacities();
+    fac.compute_capacities().unwrap();
     fac.compute_dc_ratios();
     assert!(!fac.oversaturated);
     // Peak-period (p3) ratios from Exhibit 25-47.
@@ -333,14 +336,38 @@ fn test_work_zone_reduces_capacity_and_speed() {
     assert!(fac.speed[4][0] < base.speed[4][0]);
 }
 
+#[test]
+fn test_lanes_schedule_closes_lanes_for_scheduled_periods() {
+    // Closing one of segment 5's lanes in period 2 only matches running the
+    // whole study period with the segment built at two lanes, in that
+    // period's capacity, and leaves period 1 untouched.
+    let mut base = ep1_facility();
+    base.run_analysis().unwrap();
+    let mut fac = ep1_facility();
+    let lanes = fac.segments[4].lanes;
+    let mut schedule = vec![lanes; fac.num_periods()];
+    schedule[1] = lanes - 1;
+    fac.segments[4].lanes_schedule = Some(schedule);
+    fac.run_analysis().unwrap();
+    let expected = base.capacity[4][1] * f64::from(lanes - 1) / f64::from(lanes);
+    assert!((fac.capacity[4][1] - expected).abs() < 1e-6);
+    assert!((fac.capacity[4][0] - base.capacity[4][0]).abs() < 1e-9);
+    assert!((fac.density_veh[4][0] - base.density_veh[4][0]).abs() < 1e-9);
+    assert!(fac.density_veh[4][1] > base.density_veh[4][1]);
+
+    fac.segments[4].lanes_schedule = Some(vec![0; fac.num_periods()]);
+    assert!(fac.validate().is_err());
+}
+
 // ═════════════════════════════════════════════════════════════════════════
 // Managed-lane facility (Steps A-9/A-13/A-14) and cross-weave CAF
 // ═════════════════════════════════════════════════════════════════════════
 
 use super::managed_lanes::{
-    cross_weave_caf, cross_weave_crf, ManagedLaneFacility, MlSegmentInput,
+    cross_weave_caf, cross_weave_crf, ManagedLaneFacility, MlRampJunction, MlSegmentInput,
 };
 use crate::hcm::basicfreeways::managed_lanes::ManagedLaneType;
+use crate::hcm::merge_diverge::merge_diverge::RampType;
 
 #[test]
 fn test_cross_weave_caf_equation_13_24() {
@@ -426,6 +453,39 @@ fn test_ml_adjacent_friction_activates_above_threshold() {
     assert!(any_friction, "the +11% facility should trigger ML friction");
 }
 
+#[test]
+fn test_ml_direct_access_ramp_over_capacity_is_los_f() {
+    // A 2,000 veh/h direct-access on-ramp into a single Continuous Access ML
+    // overloads the ML downstream of the merge: the junction, the ML segment
+    // and the ML lane group all report LOS F.
+    let gp = ep1_facility();
+    let n = gp.num_segments();
+    let mut ml: Vec<Option<MlSegmentInput>> = vec![Some(MlSegmentInput::default()); n];
+    ml[3] = Some(MlSegmentInput {
+        on_ramp_demand: vec![2000.0; 5],
+        ramp: Some(MlRampJunction::default()),
+        ..Default::default()
+    });
+    let mut fac = ManagedLaneFacility {
+        gp,
+        ml,
+        ml_entry_demand: vec![400.0; 5],
+        ..Default::default()
+    };
+    fac.run_analysis().unwrap();
+    let r = fac.ml_ramp[3][0].as_ref().unwrap();
+    assert!(r.ml_dc > 1.0 && r.demand_exceeds_capacity);
+    assert_eq!(r.los, LevelOfService::F);
+    assert_eq!(fac.ml_los[3][0], LevelOfService::F);
+    assert_eq!(fac.ml_group_performance[0].los, LevelOfService::F);
+    assert!(fac.ml_ramp[2][0].is_none());
+
+    // A major merge has no acceleration lane to analyze.
+    fac.ml[3].as_mut().unwrap().ramp =
+        Some(MlRampJunction { ramp_type: RampType::MajorMerge, ..Default::default() });
+    assert!(fac.run_analysis().is_err());
+}
+
 // ═════════════════════════════════════════════════════════════════════════
 // Planning-level method (Chapter 25 Section 6)
 // ═════════════════════════════════════════════════════════════════════════
@@ -688,3 +748,144 @@ fn test_a_weave_coded_gore_to_gore_does_not_reconstruct_example_problem_1() {
     approx(rebuilt[5].1, 1640.0, 0.001, "the weave without the extension");
     approx(rebuilt[6].1, 5780.0, 0.001, "segment 7 without the extension");
 }
+
+/// A three-lane, 65 mi/h mountain facility with 5% SUT and 10% TT (the
+/// Chapter 25 Example Problem 11 stream) over the given `(length_mi, grade)`
+/// segments, at 1,500 veh/h/ln.
+fn graded_facility(grades: &[(f64, Option<f64>)]) -> FreewayFacility {
+    FreewayFacility {
+        segments: grades
+            .iter()
+            .map(|&(length_mi, grade)| FacilitySegment {
+                length_ft: length_mi * 5280.0,
+                lanes: 3,
+                grade: grade.map(|grade| SpecificGrade { grade, sut_share: 1.0 / 3.0 }),
+                ..Default::default()
+            })
+            .collect(),
+        mainline_demand: vec![4500.0],
+        ffs: 65.0,
+        heavy_vehicle_pct: 0.15,
+        terrain: Terrain::Mountainous,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_single_specific_grade_uses_chapter_26_mixed_flow() {
+    let mut fac = graded_facility(&[(2.0, Some(5.0)), (1.0, Some(0.0))]);
+    fac.terrain = Terrain::Level;
+    fac.segments[1].grade = None;
+    fac.run_analysis().unwrap();
+
+    let r = MixedFlowSegment {
+        ffs: 65.0,
+        length: 2.0,
+        grade: 5.0,
+        v_mix: 1500.0,
+        p_sut: 0.05,
+        p_tt: 0.10,
+        caf_ao: 1.0,
+    }
+    .analyze()
+    .unwrap();
+    approx(fac.capacity[0][0], 3.0 * r.capacity_mix, 1e-9, "capacity");
+    approx(fac.get_speed(0, 0), r.s_mix.unwrap(), 1e-9, "speed");
+    approx(fac.density_veh[0][0], r.d_mix.unwrap(), 1e-9, "density");
+    // Mixed-flow densities are not PCE-converted; the level segment is.
+    approx(fac.density_pc[0][0], fac.density_veh[0][0], 1e-12, "graded pc density");
+    approx(fac.density_pc[1][0], fac.density_veh[1][0] / fac.f_hv(), 1e-12, "level pc density");
+}
+
+#[test]
+fn test_consecutive_grades_chain_as_a_composite_grade() {
+    let mut fac = graded_facility(&[(1.5, Some(3.0)), (2.0, Some(2.0)), (1.0, Some(5.0))]);
+    fac.run_analysis().unwrap();
+
+    let r = CompositeGrade {
+        ffs: 65.0,
+        v_mix: 1500.0,
+        p_sut: 0.05,
+        p_tt: 0.10,
+        segments: vec![
+            GradeSegment { length: 1.5, grade: 3.0 },
+            GradeSegment { length: 2.0, grade: 2.0 },
+            GradeSegment { length: 1.0, grade: 5.0 },
+        ],
+        caf_ao: 1.0,
+    }
+    .analyze()
+    .unwrap();
+    for (i, seg) in r.segments.iter().enumerate() {
+        approx(fac.capacity[i][0], 3.0 * seg.capacity_mix, 1e-9, "capacity");
+        approx(fac.get_speed(i, 0), seg.s_mix, 1e-9, "speed");
+    }
+    let perf = &fac.facility_performance[0];
+    approx(perf.avg_density_pc, perf.avg_density_veh, 1e-12, "facility pc density");
+}
+
+#[test]
+fn test_mountainous_terrain_requires_specific_grades() {
+    let fac = graded_facility(&[(1.0, Some(3.0)), (1.0, None)]);
+    let err = fac.validate().unwrap_err();
+    assert!(err.contains("segment 1 has no specific grade"), "{err}");
+
+    let mut fac = graded_facility(&[(1.0, Some(3.0)), (0.3, None), (1.0, Some(3.0))]);
+    fac.segments[1].seg_type = SegmentType::Merge;
+    fac.segments[1].on_ramp_demand = vec![300.0];
+    let err = fac.validate().unwrap_err();
+    assert!(err.contains("segment 1 has no specific grade"), "{err}");
+}
+
+#[test]
+fn test_compute_capacities_reports_a_bad_grade_without_validate() {
+    let mut fac = graded_facility(&[(1.0, Some(f64::NAN))]);
+    fac.compute_demands();
+    let err = fac.compute_capacities().unwrap_err();
+    assert!(err.contains("finite"), "{err}");
+}
+
+/// A 3% grade, an on-ramp on a 2% grade, then a 5% grade.
+fn interchange_on_grade() -> FreewayFacility {
+    let mut fac = graded_facility(&[(1.0, Some(3.0)), (1500.0 / 5280.0, Some(2.0)), (1.0, Some(5.0))]);
+    fac.mainline_demand = vec![4200.0];
+    fac.segments[1].seg_type = SegmentType::Merge;
+    fac.segments[1].on_ramp_demand = vec![300.0];
+    fac
+}
+
+#[test]
+fn test_trucks_carry_their_grade_speeds_through_a_ramp_segment() {
+    let mut fac = interchange_on_grade();
+    fac.run_analysis().unwrap();
+
+    let chain = |v_mix: f64| {
+        CompositeGrade {
+            ffs: 65.0,
+            v_mix,
+            p_sut: 0.05,
+            p_tt: 0.10,
+            segments: [(1.0, 3.0), (1500.0 / 5280.0, 2.0), (1.0, 5.0)]
+                .iter()
+                .map(|&(length, grade)| GradeSegment { length, grade })
+                .collect(),
+            caf_ao: 1.0,
+        }
+        .analyze()
+        .unwrap()
+    };
+    // Downstream of the merge the trucks are still on the composite grade
+    // that began upstream of it.
+    let r = chain(1500.0);
+    approx(fac.get_speed(2, 0), r.segments[2].s_mix, 1e-9, "downstream speed");
+    // The merge's Chapter 14 capacity carries the mixed-flow CAF in place
+    // of f_HV.
+    let cap = crate::hcm::merge_diverge::merge_diverge::get_freeway_capacity_per_lane(65.0);
+    approx(fac.capacity[1][0], 3.0 * cap * r.segments[1].caf_mix, 1e-9, "merge capacity");
+    approx(fac.density_pc[1][0], fac.density_veh[1][0], 1e-12, "merge pc density");
+
+    let mut no_trucks = interchange_on_grade();
+    no_trucks.heavy_vehicle_pct = 0.0;
+    no_trucks.run_analysis().unwrap();
+    assert!(fac.get_speed(1, 0) < no_trucks.get_speed(1, 0));
+}
@@ -0,0 +1,421 @@
+//! Saving, loading and exchanging freeway reliability scenario sets.
+//!
+//! Two formats:
+//!
+//! - [`ScenarioSetFile`], a versioned JSON document holding a complete
+//!   reliability reporting period: the generation configuration, every
+//!   generated scenario with its demand multiplier, weather and incident
+//!   assignments, and (when the set has been evaluated) the per-scenario
+//!   results and reliability measures. It round-trips losslessly,
+//!   including every `f64` bit for bit.
+//! - A FREEVAL-style scenario table ([`import_scenario_table`] and
+//!   [`export_scenario_table`]): one CSV row per scenario event, with the
+//!   1-based scenario, segment and analysis-period numbering FREEVAL's
+//!   scenario summaries use. Importing a table produced by another engine
+//!   and evaluating it with [`ReliabilityAnalysis::run_scenario_set`] runs
+//!   exactly the same scenarios through both, so their results can be
+//!   diffed scenario by scenario.
+//!
+//! Scenario table columns (case-insensitive, any order):
+//!
+//! | column | required | meaning |
+//! |---|---|---|
+//! | `scenario` | yes | 1-based scenario number; rows sharing a number describe one scenario |
+//! | `probability` | yes | scenario probability (all scenarios sum to 1) |
+//! | `daf` | yes | facility-wide demand adjustment factor DAF_s (Equation 25-72) |
+//! | `month` | no | 1–12 (default 1) |
+//! | `weekday` | no | `Monday` … `Sunday` (default Monday) |
+//! | `replication` | no | 0-based replication index (default 0) |
+//! | `demand_multiplier` | no | DM(s) (default: the DAF) |
+//! | `event` | no | `weather`, `incident` or empty for a scenario with no events |
+//! | `type` | for events | weather type or incident severity, by variant name (`HeavyRain`, `OneLane`) or Exhibit 11-20/11-22 label (`Heavy rain`, `1 lane closed`) |
+//! | `segment` | incidents | 1-based segment |
+//! | `start_period` | for events | 1-based first analysis period |
+//! | `duration_periods` | for events | whole analysis periods (>= 1) |
+//! | `caf`, `saf` | no | weather adjustment factors (default Exhibits 11-20/11-21 at the facility FFS) |
+//!
+//! Scenario-level columns are read from the first row of each scenario and
+//! must agree on every later row of the same scenario. Work zones and
+//! special events are not part of the table; they belong to the facility
+//! configuration the set is evaluated against.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hcm::common::reliability::ReliabilityMetrics;
+use crate::hcm::utils::csv::{join_line, CsvRow, CsvTable};
+
+use super::exhibits::{
+    weather_caf, weather_saf, IncidentSeverity, WeatherType, INCIDENT_SEVERITIES,
+    SEVERE_WEATHER_TYPES,
+};
+use super::reliability::{ReliabilityAnalysis, ScenarioResult};
+use super::scenario_generation::{
+    FreewayScenario, IncidentAssignment, ScenarioGenerationConfig, ScenarioSet,
+    WeatherEventAssignment, Weekday, WEEKDAYS,
+};
+
+/// `format` tag of a [`ScenarioSetFile`].
+pub const SCENARIO_SET_FORMAT: &str = "transportations_library/freeway-scenario-set";
+
+/// Current [`ScenarioSetFile`] format version. Bumped on any change a
+/// version-1 reader could not load; readers reject newer versions rather
+/// than guess at them.
+pub const SCENARIO_SET_FORMAT_VERSION: u32 = 1;
+
+/// Header of a scenario table, in the order [`export_scenario_table`]
+/// writes it.
+pub const SCENARIO_TABLE_COLUMNS: [&str; 14] = [
+    "scenario",
+    "month",
+    "weekday",
+    "replication",
+    "probability",
+    "demand_multiplier",
+    "daf",
+    "event",
+    "type",
+    "segment",
+    "start_period",
+    "duration_periods",
+    "caf",
+    "saf",
+];
+
+/// A complete reliability reporting period scenario set on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioSetFile {
+    /// Always [`SCENARIO_SET_FORMAT`].
+    pub format: String,
+    /// Format version ([`SCENARIO_SET_FORMAT_VERSION`] when written).
+    pub version: u32,
+    /// Version of this library that wrote the file (informational).
+    pub library_version: String,
+    /// Where the scenarios came from, e.g. `"generated"` or the name of
+    /// an imported table (informational).
+    pub source: String,
+    /// Configuration the scenarios were generated from, when known.
+    /// Imported tables carry none.
+    #[serde(default)]
+    pub generation: Option<ScenarioGenerationConfig>,
+    /// The scenarios and the generator's intermediate results.
+    pub scenario_set: ScenarioSet,
+    /// Per-scenario facility results, empty if the set was never
+    /// evaluated.
+    #[serde(default)]
+    pub results: Vec<ScenarioResult>,
+    /// Reliability performance measures, when the set was evaluated.
+    #[serde(default)]
+    pub metrics: Option<ReliabilityMetrics>,
+}
+
+impl ScenarioSetFile {
+    /// Wrap a scenario set that has not been evaluated.
+    pub fn new(scenario_set: ScenarioSet, source: &str) -> Self {
+        Self {
+            format: SCENARIO_SET_FORMAT.to_string(),
+            version: SCENARIO_SET_FORMAT_VERSION,
+            library_version: crate::VERSION.to_string(),
+            source: source.to_string(),
+            generation: None,
+            scenario_set,
+            results: Vec::new(),
+            metrics: None,
+        }
+    }
+
+    /// Capture a run analysis: its configuration, scenario set, results
+    /// and measures. Fails if [`ReliabilityAnalysis::run`] has not been
+    /// called.
+    pub fn from_analysis(analysis: &ReliabilityAnalysis) -> Result<Self, String> {
+        let set = analysis
+            .scenario_set
+            .clone()
+            .ok_or("reliability analysis has not been run; no scenario set to save")?;
+        Ok(Self {
+            generation: Some(analysis.scenario_generation.clone()),
+            results: analysis.scenario_results.clone(),
+            metrics: analysis.metrics.clone(),
+            ..Self::new(set, "generated")
+        })
+    }
+
+    /// Serialize to pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Parse from JSON, rejecting other formats and newer versions.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        match value.get("format").and_then(|f| f.as_str()) {
+            Some(SCENARIO_SET_FORMAT) => {}
+            Some(other) => return Err(format!("not a scenario set file: format {other:?}")),
+            None => return Err("not a scenario set file: no `format` field".into()),
+        }
+        let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+        if version == 0 || version > u64::from(SCENARIO_SET_FORMAT_VERSION) {
+            return Err(format!(
+                "unsupported scenario set format version {version}; this library reads up to \
+                 version {SCENARIO_SET_FORMAT_VERSION}"
+            ));
+        }
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    /// Write to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        fs::write(path, self.to_json().map_err(crate::TransportationError::InvalidInput)?)?;
+        Ok(())
+    }
+
+    /// Read from a JSON file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_json(&text).map_err(crate::TransportationError::InvalidInput)
+    }
+}
+
+// ═════════════════════════════════════════════════════════════════════════
+// FREEVAL-style scenario tables
+// ═════════════════════════════════════════════════════════════════════════
+
+/// Write a scenario set as a scenario table (see the module docs). A
+/// scenario with no weather or incident events gets one row with an empty
+/// `event`; otherwise one row per event. Work zones and special events are
+/// not written.
+pub fn export_scenario_table(set: &ScenarioSet) -> String {
+    let mut out = join_line(&SCENARIO_TABLE_COLUMNS);
+    out.push('\n');
+    for sc in &set.scenarios {
+        let scenario_fields = [
+            (sc.id + 1).to_string(),
+            sc.month.to_string(),
+            format!("{:?}", sc.weekday),
+            sc.replication.to_string(),
+            sc.probability.to_string(),
+            sc.demand_multiplier.to_string(),
+            sc.daf.to_string(),
+        ];
+        let mut events: Vec<[String; 7]> = Vec::new();
+        for w in &sc.weather_events {
+            events.push([
+                "weather".into(),
+                format!("{:?}", w.weather),
+                String::new(),
+                (w.start_period + 1).to_string(),
+                w.duration_periods.to_string(),
+                w.caf.to_string(),
+                w.saf.to_string(),
+            ]);
+        }
+        for i in &sc.incidents {
+            events.push([
+                "incident".into(),
+                format!("{:?}", i.severity),
+                (i.segment + 1).to_string(),
+                (i.start_period + 1).to_string(),
+                i.duration_periods.to_string(),
+                String::new(),
+                String::new(),
+            ]);
+        }
+        if events.is_empty() {
+            events.push(Default::default());
+        }
+        for ev in events {
+            let row: Vec<&str> = scenario_fields
+                .iter()
+                .chain(ev.iter())
+                .map(String::as_str)
+                .collect();
+            out.push_str(&join_line(&row));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Read a scenario table (see the module docs) into a scenario set for a
+/// facility with free-flow speed `facility_ffs` (mi/h, for default
+/// weather factors).
+///
+/// Scenario numbers must run 1..=N without gaps. The set's summary fields
+/// are recomputed from the table: event totals, and weather event counts
+/// by month and type in `expected_weather_events`. The expected monthly
+/// incident frequency is not recoverable from a table and is left at
+/// zero.
+pub fn import_scenario_table(csv: &str, facility_ffs: f64) -> Result<ScenarioSet, String> {
+    let table = CsvTable::parse(csv)?;
+    table.require_columns(&["scenario", "probability", "daf"])?;
+
+    let mut scenarios: Vec<FreewayScenario> = Vec::new();
+    for row in table.iter() {
+        let number = row.usize("scenario")?;
+        if number == 0 {
+            return Err(format!("line {}: scenario numbers start at 1", row.line));
+        }
+        let id = number - 1;
+        let header = scenario_header(&row, id)?;
+        match id.cmp(&scenarios.len()) {
+            std::cmp::Ordering::Less => {
+                if !same_scenario(&scenarios[id], &header) {
+                    return Err(format!(
+                        "line {}: scenario {number} fields disagree with its first row",
+                        row.line
+                    ));
+                }
+            }
+            std::cmp::Ordering::Equal => scenarios.push(header),
+            std::cmp::Ordering::Greater => {
+                return Err(format!(
+                    "line {}: scenario {number} follows scenario {}; scenarios must be \
+                     numbered 1..N in order",
+                    row.line,
+                    scenarios.len()
+                ))
+            }
+        }
+        let sc = &mut scenarios[id];
+        match row.get("event").map(str::to_ascii_lowercase).as_deref() {
+            None => {}
+            Some("weather") => {
+                let weather = parse_weather(row.str("type")?)
+                    .ok_or_else(|| format!("line {}: unknown weather type", row.line))?;
+                sc.weather_events.push(WeatherEventAssignment {
+                    weather,
+                    start_period: period_index(&row)?,
+                    duration_periods: duration(&row)?,
+                    caf: row.opt_f64("caf")?.unwrap_or_else(|| weather_caf(weather, facility_ffs)),
+                    saf: row.opt_f64("saf")?.unwrap_or_else(|| weather_saf(weather, facility_ffs)),
+                });
+            }
+            Some("incident") => {
+                let severity = parse_severity(row.str("type")?)
+                    .ok_or_else(|| format!("line {}: unknown incident severity", row.line))?;
+                let segment = row.usize("segment")?;
+                if segment == 0 {
+                    return Err(format!("line {}: segment numbers start at 1", row.line));
+                }
+                sc.incidents.push(IncidentAssignment {
+                    severity,
+                    segment: segment - 1,
+                    start_period: period_index(&row)?,
+                    duration_periods: duration(&row)?,
+                });
+            }
+            Some(other) => {
+                return Err(format!(
+                    "line {}: unknown event {other:?} (expected weather or incident)",
+                    row.line
+                ))
+            }
+        }
+    }
+    if scenarios.is_empty() {
+        return Err("scenario table has no rows".into());
+    }
+
+    let mut expected_weather_events = vec![vec![0u32; SEVERE_WEATHER_TYPES.len()]; 12];
+    for sc in &scenarios {
+        for w in &sc.weather_events {
+            if let Some(col) = SEVERE_WEATHER_TYPES.iter().position(|t| *t == w.weather) {
+                expected_weather_events[sc.month as usize - 1][col] += 1;
+            }
+        }
+    }
+    Ok(ScenarioSet {
+        total_incidents: scenarios.iter().map(|s| s.incidents.len()).sum(),
+        total_weather_events: scenarios.iter().map(|s| s.weather_events.len()).sum(),
+        scenarios,
+        expected_weather_events,
+        monthly_incident_frequency: vec![0.0; 12],
+        incident_rate_source: Default::default(),
+        crash_rate_per_100mvmt: None,
+        incident_rate_per_100mvmt: None,
+    })
+}
+
+/// The scenario-level fields of a table row, with no events.
+fn scenario_header(row: &CsvRow<'_>, id: usize) -> Result<FreewayScenario, String> {
+    let month = row.opt_usize("month")?.unwrap_or(1);
+    if !(1..=12).contains(&month) {
+        return Err(format!("line {}: month {month} outside 1-12", row.line));
+    }
+    let weekday = match row.get("weekday") {
+        None => Weekday::Monday,
+        Some(w) => parse_weekday(w)
+            .ok_or_else(|| format!("line {}: unknown weekday {w:?}", row.line))?,
+    };
+    let daf = row.f64("daf")?;
+    Ok(FreewayScenario {
+        id,
+        month: month as u32,
+        weekday,
+        replication: row.opt_usize("replication")?.unwrap_or(0) as u32,
+        probability: row.f64("probability")?,
+        demand_multiplier: row.opt_f64("demand_multiplier")?.unwrap_or(daf),
+        daf,
+        weather_events: Vec::new(),
+        incidents: Vec::new(),
+        work_zones: Vec::new(),
+        special_events: Vec::new(),
+    })
+}
+
+fn same_scenario(a: &FreewayScenario, b: &FreewayScenario) -> bool {
+    a.month == b.month
+        && a.weekday == b.weekday
+        && a.replication == b.replication
+        && a.probability == b.probability
+        && a.demand_multiplier == b.demand_multiplier
+        && a.daf == b.daf
+}
+
+fn period_index(row: &CsvRow<'_>) -> Result<usize, String> {
+    match row.usize("start_period")? {
+        0 => Err(format!("line {}: analysis periods start at 1", row.line)),
+        p => Ok(p - 1),
+    }
+}
+
+fn duration(row: &CsvRow<'_>) -> Result<usize, String> {
+    match row.usize("duration_periods")? {
+        0 => Err(format!("line {}: duration_periods must be at least 1", row.line)),
+        d => Ok(d),
+    }
+}
+
+/// Match a variant name (`HeavyRain`) or display label (`Heavy rain`),
+/// ignoring case.
+fn matches_label(text: &str, variant: &str, label: &str) -> bool {
+    text.eq_ignore_ascii_case(variant) || text.eq_ignore_ascii_case(label)
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    WEEKDAYS
+        .iter()
+        .chain([Weekday::Saturday, Weekday::Sunday].iter())
+        .copied()
+        .find(|d| {
+            let name = format!("{d:?}");
+            text.eq_ignore_ascii_case(&name) || text.eq_ignore_ascii_case(&name[..3])
+        })
+}
+
+fn parse_weather(text: &str) -> Option<WeatherType> {
+    SEVERE_WEATHER_TYPES
+        .iter()
+        .chain([WeatherType::NonSevere].iter())
+        .copied()
+        .find(|w| matches_label(text, &format!("{w:?}"), w.name()))
+}
+
+fn parse_severity(text: &str) -> Option<IncidentSeverity> {
+    INCIDENT_SEVERITIES
+        .iter()
+        .copied()
+        .find(|s| matches_label(text, &format!("{s:?}"), s.name()))
+}
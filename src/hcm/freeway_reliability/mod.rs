@@ -15,13 +15,17 @@
 //! the shared [`crate::hcm::common::reliability`] module.
 //!
 //! Also includes the Chapter 11 planning-level reliability method
-//! (Equations 11-1 through 11-5) in [`exhibits`].
+//! (Equations 11-1 through 11-5) in [`exhibits`], a CSV batch mode for it
+//! in [`planning_batch`], and a versioned file format plus FREEVAL-style
+//! scenario table import/export in [`interchange`].
 //!
 //! Out of scope in this pass (documented deferrals): managed lane
 //! reliability, the Section 4 ATDM strategy assessment (Steps C-1 through
 //! C-9), and the Chapter 25 reliability calibration methodology.
 
 pub mod exhibits;
+pub mod interchange;
+pub mod planning_batch;
 pub mod reliability;
 pub mod scenario_generation;
 
@@ -29,14 +33,26 @@ pub mod scenario_generation;
 mod tests;
 
 pub use exhibits::{
-    hers_crash_rate, incident_caf_per_open_lane, incident_caf_total, planning_pt45,
+    hers_crash_rate, incident_caf_per_open_lane, incident_caf_total,
+    incident_rate_from_crash_rate, planning_pt45,
     planning_tti_95, planning_tti_mean, weather_caf, weather_saf, IncidentDurationParams,
     IncidentSeverity, WeatherType, CHAPTER, DEFAULT_INCIDENT_SEVERITY_DISTRIBUTION,
     DEFAULT_INCIDENT_TO_CRASH_RATIO, RURAL_DEMAND_RATIOS, URBAN_DEMAND_RATIOS,
 };
-pub use reliability::{ReliabilityAnalysis, ScenarioResult};
+pub use interchange::{
+    export_scenario_table, import_scenario_table, ScenarioSetFile, SCENARIO_SET_FORMAT,
+    SCENARIO_SET_FORMAT_VERSION,
+};
+pub use planning_batch::{
+    evaluate_planning_record, run_planning_batch_csv, PlanningBatchConfig, PlanningBatchResults,
+    PlanningFacilityRecord, PlanningRecordResult, PlanningRowError,
+};
+pub use reliability::{
+    IncidentClosureComparison, IncidentClosureModel, ReliabilityAnalysis, ScenarioResult,
+};
 pub use scenario_generation::{
-    generate_scenarios, FreewayScenario, IncidentAssignment, IncidentInputs, Prng,
+    generate_scenarios, FreewayScenario, HersCrashInputs, IncidentAssignment, IncidentInputs,
+    IncidentRateSource, Prng,
     ScenarioGenerationConfig, ScenarioSet, SeedStatistics, SpecialEvent, WeatherEventAssignment,
     WeatherInputs, Weekday, WorkZoneEvent,
 };
@@ -0,0 +1,329 @@
+//! Batch screening with the Chapter 11 planning-level reliability method.
+//!
+//! Runs Equations 11-1 through 11-5 over many facility records at once, for
+//! statewide screening where the full scenario method is out of reach. Each
+//! record carries only planning data, so the peak-hour inputs the equations
+//! need are derived first:
+//!
+//! - DDHV = AADT × K × D (veh/h), converted to a flow rate in pc/h/ln with
+//!   the PHF and the Exhibit 12-25 level-terrain truck PCE of 2.0.
+//! - Capacity is Equation 12-6 at the record's FFS unless given.
+//! - Peak-hour speed is the Equation 12-1 speed-flow curve unless given. A
+//!   record whose demand exceeds capacity is held at the speed at capacity
+//!   and flagged `over_capacity`.
+//! - When a local crash rate is given, the Equation 11-3 incident delay rate
+//!   is scaled by its ratio to the HERS prediction (Equation 25-79) for the
+//!   same facility. VERIFY-HCM: Chapter 11 gives no crash-rate term for the
+//!   planning method; the HERS model stands in for the national average the
+//!   equation was fitted to.
+//!
+//! The equations are the verified ones in [`super::exhibits`] (Chapter 25
+//! Example Problem 10), not `common::adjustment_factors`'
+//! `planning_reliability_analysis`, whose Equation 11-3 and 11-4 forms are
+//! still provisional there.
+//!
+//! An invalid record, or a line that cannot be split into fields, becomes a
+//! [`PlanningRowError`] naming its CSV line and the batch continues; only a
+//! missing header or required column fails the batch.
+//!
+//! | Column | Required | Meaning |
+//! |--------|----------|---------|
+//! | `id` | yes | Record identifier |
+//! | `aadt` | yes | Annual average daily traffic, veh/day (two-way) |
+//! | `lanes` | yes | Directional lanes |
+//! | `ffs` | yes | Free-flow speed, mi/h |
+//! | `k_factor` | yes | Peak-hour proportion of AADT |
+//! | `d_factor` | yes | Peak-direction proportion |
+//! | `length_mi` | yes | Facility length, mi |
+//! | `crash_rate` | no | Crashes per 100 million VMT |
+//! | `phf` | no | Peak hour factor (default [`PlanningBatchConfig::default_phf`]) |
+//! | `heavy_vehicle_pct` | no | Heavy vehicles, % (default 0) |
+//! | `capacity` | no | Capacity, pc/h/ln |
+//! | `peak_speed` | no | Observed peak-hour speed, mi/h |
+//! | `lane_width_ft` | no | Lane width for the HERS model (default 12) |
+
+use serde::{Deserialize, Serialize};
+
+use super::exhibits::{
+    hers_crash_rate, planning_incident_delay_rate, planning_pt45, planning_recurring_delay_rate,
+    planning_tti_95, planning_tti_mean,
+};
+use crate::hcm::basicfreeways::basicfreeways::{
+    basic_segment_breakpoint, basic_segment_capacity, basic_segment_speed, DENSITY_AT_CAPACITY,
+    EXPONENT_BASIC_FREEWAY,
+};
+use crate::hcm::utils::csv::{join_line, CsvRow, CsvTable};
+
+/// Required input columns.
+pub const PLANNING_BATCH_COLUMNS: [&str; 7] =
+    ["id", "aadt", "lanes", "ffs", "k_factor", "d_factor", "length_mi"];
+
+/// Exhibit 12-25 level-terrain passenger car equivalent for trucks.
+const LEVEL_TERRAIN_TRUCK_PCE: f64 = 2.0;
+
+/// One facility record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanningFacilityRecord {
+    pub id: String,
+    pub aadt: f64,
+    pub lanes: u32,
+    pub ffs: f64,
+    pub k_factor: f64,
+    pub d_factor: f64,
+    pub length_mi: f64,
+    #[serde(default)]
+    pub crash_rate: Option<f64>,
+    #[serde(default)]
+    pub phf: Option<f64>,
+    #[serde(default)]
+    pub heavy_vehicle_pct: Option<f64>,
+    #[serde(default)]
+    pub capacity: Option<f64>,
+    #[serde(default)]
+    pub peak_speed: Option<f64>,
+    #[serde(default)]
+    pub lane_width_ft: Option<f64>,
+}
+
+/// Batch-wide settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanningBatchConfig {
+    /// A record is flagged unreliable when TTI_95 exceeds this.
+    pub unreliable_tti_95: f64,
+    /// Peak periods per year used to annualize delay.
+    pub annual_days: f64,
+    /// PHF for records that leave it blank.
+    pub default_phf: f64,
+}
+
+impl Default for PlanningBatchConfig {
+    fn default() -> Self {
+        Self {
+            unreliable_tti_95: 1.5,
+            annual_days: 250.0,
+            default_phf: 0.94,
+        }
+    }
+}
+
+/// Planning reliability measures for one record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanningRecordResult {
+    pub id: String,
+    /// Source CSV line (0 when the record did not come from CSV).
+    pub line: usize,
+    /// Demand flow rate, pc/h/ln.
+    pub flow_rate: f64,
+    /// Capacity, pc/h/ln.
+    pub capacity: f64,
+    pub vc_ratio: f64,
+    pub peak_speed: f64,
+    pub recurring_delay_rate: f64,
+    pub incident_delay_rate: f64,
+    pub tti_mean: f64,
+    pub tti_95: f64,
+    /// Share of trips below 45 mi/h (decimal).
+    pub pt45: f64,
+    /// Vehicle-hours of delay in the peak hour, peak direction.
+    pub peak_hour_vhd: f64,
+    /// `peak_hour_vhd` × [`PlanningBatchConfig::annual_days`].
+    pub annual_vhd: f64,
+    pub over_capacity: bool,
+    pub unreliable: bool,
+}
+
+/// A record the batch could not evaluate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanningRowError {
+    pub line: usize,
+    pub id: Option<String>,
+    pub message: String,
+}
+
+/// Results of a batch run, in input order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanningBatchResults {
+    pub results: Vec<PlanningRecordResult>,
+    pub errors: Vec<PlanningRowError>,
+}
+
+impl PlanningBatchResults {
+    /// Records flagged unreliable or over capacity.
+    pub fn flagged(&self) -> impl Iterator<Item = &PlanningRecordResult> {
+        self.results.iter().filter(|r| r.unreliable || r.over_capacity)
+    }
+
+    /// Results as CSV, one row per evaluated record.
+    pub fn to_csv(&self) -> String {
+        let mut out = join_line(&[
+            "id", "line", "flow_rate", "capacity", "vc_ratio", "peak_speed", "tti_mean",
+            "tti_95", "pt45", "peak_hour_vhd", "annual_vhd", "over_capacity", "unreliable",
+        ]);
+        out.push('\n');
+        for r in &self.results {
+            out.push_str(&join_line(&[
+                r.id.clone(),
+                r.line.to_string(),
+                r.flow_rate.to_string(),
+                r.capacity.to_string(),
+                r.vc_ratio.to_string(),
+                r.peak_speed.to_string(),
+                r.tti_mean.to_string(),
+                r.tti_95.to_string(),
+                r.pt45.to_string(),
+                r.peak_hour_vhd.to_string(),
+                r.annual_vhd.to_string(),
+                r.over_capacity.to_string(),
+                r.unreliable.to_string(),
+            ]));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn positive(name: &str, value: f64) -> Result<f64, String> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("`{name}` must be positive, got {value}"))
+    }
+}
+
+fn fraction(name: &str, value: f64) -> Result<f64, String> {
+    if value.is_finite() && value > 0.0 && value <= 1.0 {
+        Ok(value)
+    } else {
+        Err(format!("`{name}` must be in (0, 1], got {value}"))
+    }
+}
+
+/// Evaluate one record with Equations 11-1 through 11-5.
+pub fn evaluate_planning_record(
+    record: &PlanningFacilityRecord,
+    config: &PlanningBatchConfig,
+) -> Result<PlanningRecordResult, String> {
+    let aadt = positive("aadt", record.aadt)?;
+    let ffs = positive("ffs", record.ffs)?;
+    let k = fraction("k_factor", record.k_factor)?;
+    let d = fraction("d_factor", record.d_factor)?;
+    let length = positive("length_mi", record.length_mi)?;
+    if record.lanes == 0 {
+        return Err("`lanes` must be at least 1".into());
+    }
+    let phf = fraction("phf", record.phf.unwrap_or(config.default_phf))?;
+    let pt = record.heavy_vehicle_pct.unwrap_or(0.0);
+    if !(0.0..=100.0).contains(&pt) {
+        return Err(format!("`heavy_vehicle_pct` must be in [0, 100], got {pt}"));
+    }
+    let lanes = f64::from(record.lanes);
+
+    let ddhv = aadt * k * d;
+    let f_hv = 1.0 / (1.0 + pt / 100.0 * (LEVEL_TERRAIN_TRUCK_PCE - 1.0));
+    let flow_rate = ddhv / (phf * lanes * f_hv);
+    let capacity = match record.capacity {
+        Some(c) => positive("capacity", c)?,
+        None => basic_segment_capacity(ffs),
+    };
+    let vc_ratio = flow_rate / capacity;
+    let over_capacity = vc_ratio > 1.0;
+    let peak_speed = match record.peak_speed {
+        Some(s) => positive("peak_speed", s)?.min(ffs),
+        None if over_capacity => capacity / DENSITY_AT_CAPACITY,
+        None => basic_segment_speed(
+            flow_rate,
+            ffs,
+            capacity,
+            basic_segment_breakpoint(ffs, 1.0),
+            EXPONENT_BASIC_FREEWAY,
+        ),
+    };
+
+    let rdr = planning_recurring_delay_rate(peak_speed, ffs);
+    let base_idr = planning_incident_delay_rate(record.lanes, vc_ratio);
+    let mut idr = base_idr;
+    if let Some(cr) = record.crash_rate {
+        if !cr.is_finite() || cr < 0.0 {
+            return Err(format!("`crash_rate` must be non-negative, got {cr}"));
+        }
+        let lane_width = positive("lane_width_ft", record.lane_width_ft.unwrap_or(12.0))?;
+        let two_way_capacity = 2.0 * lanes * capacity * f_hv;
+        let hers = hers_crash_rate(aadt / two_way_capacity, lane_width);
+        if hers > 0.0 {
+            idr *= cr / hers;
+        }
+    }
+    // Equation 11-1, plus the crash-rate adjustment to IDR when there is one.
+    let tti_mean =
+        planning_tti_mean(ffs, peak_speed, record.lanes, vc_ratio) + ffs * (idr - base_idr);
+    let tti_95 = planning_tti_95(tti_mean);
+    let peak_hour_vhd = ddhv * length * (tti_mean - 1.0) / ffs;
+
+    Ok(PlanningRecordResult {
+        id: record.id.clone(),
+        line: 0,
+        flow_rate,
+        capacity,
+        vc_ratio,
+        peak_speed,
+        recurring_delay_rate: rdr,
+        incident_delay_rate: idr,
+        tti_mean,
+        tti_95,
+        pt45: planning_pt45(tti_mean),
+        peak_hour_vhd,
+        annual_vhd: peak_hour_vhd * config.annual_days,
+        over_capacity,
+        unreliable: tti_95 > config.unreliable_tti_95,
+    })
+}
+
+/// Read one CSV row into a record.
+fn record_from_row(row: &CsvRow) -> Result<PlanningFacilityRecord, String> {
+    let lanes = row.usize("lanes")?;
+    Ok(PlanningFacilityRecord {
+        id: row.str("id")?.to_string(),
+        aadt: row.f64("aadt")?,
+        lanes: u32::try_from(lanes).map_err(|_| format!("line {}: `lanes` is too large", row.line))?,
+        ffs: row.f64("ffs")?,
+        k_factor: row.f64("k_factor")?,
+        d_factor: row.f64("d_factor")?,
+        length_mi: row.f64("length_mi")?,
+        crash_rate: row.opt_f64("crash_rate")?,
+        phf: row.opt_f64("phf")?,
+        heavy_vehicle_pct: row.opt_f64("heavy_vehicle_pct")?,
+        capacity: row.opt_f64("capacity")?,
+        peak_speed: row.opt_f64("peak_speed")?,
+        lane_width_ft: row.opt_f64("lane_width_ft")?,
+    })
+}
+
+/// Evaluate every record of a CSV table. Fails only when there is no
+/// header or it lacks a required column; bad rows, including lines that
+/// cannot be split into fields, are returned as [`PlanningRowError`]s.
+pub fn run_planning_batch_csv(
+    csv: &str,
+    config: &PlanningBatchConfig,
+) -> Result<PlanningBatchResults, String> {
+    let table = CsvTable::parse(csv)?;
+    table.require_columns(&PLANNING_BATCH_COLUMNS)?;
+    let mut out = PlanningBatchResults::default();
+    for row in table.iter() {
+        let evaluated = record_from_row(&row).and_then(|rec| {
+            evaluate_planning_record(&rec, config).map_err(|e| format!("line {}: {e}", row.line))
+        });
+        match evaluated {
+            Ok(mut r) => {
+                r.line = row.line;
+                out.results.push(r);
+            }
+            Err(message) => out.errors.push(PlanningRowError {
+                line: row.line,
+                id: row.get("id").map(str::to_string),
+                message,
+            }),
+        }
+    }
+    Ok(out)
+}
@@ -22,12 +22,17 @@
 //!
 //! VERIFY-HCM: the FREEVAL computational engine additionally reduces the
 //! *number of lanes* (NLAF) on incident/work-zone segments, which alters
-//! per-lane density and speed on those segments; the Chapter 10 engine in
-//! this crate models the closure entirely through the total-capacity CAF
-//! (the segment keeps its lane count for density purposes). Facility
-//! travel times, the quantity feeding the reliability distribution, are
-//! driven by the capacity restriction (queueing) and are only marginally
-//! affected by this simplification.
+//! per-lane density and speed on those segments. By default this crate
+//! models the closure entirely through the total-capacity CAF (the segment
+//! keeps its lane count for density purposes); setting
+//! [`ReliabilityAnalysis::incident_closure`] to
+//! [`IncidentClosureModel::LaneClosure`] closes the blocked lanes for the
+//! incident's duration instead, and
+//! [`ReliabilityAnalysis::compare_incident_closure_models`] runs both on
+//! the same scenario set. Facility travel times, the quantity feeding the
+//! reliability distribution, are driven by the capacity restriction
+//! (queueing) and are only marginally affected by the choice; per-lane
+//! density and LOS on the incident segment are not.
 
 use serde::{Deserialize, Serialize};
 
@@ -36,7 +41,7 @@ use crate::hcm::common::reliability::{
     ReliabilityMetrics, Scenario as ScenarioSummary, TravelTimeDistribution,
 };
 
-use super::exhibits::incident_caf_total;
+use super::exhibits::{incident_caf_per_open_lane, incident_caf_total};
 use super::scenario_generation::{
     generate_scenarios, FreewayScenario, ScenarioGenerationConfig, ScenarioSet, SeedStatistics,
 };
@@ -66,6 +71,33 @@ pub struct ScenarioResult {
     pub oversaturated: bool,
 }
 
+/// How an incident's blocked lanes enter the scenario facility (Step B-9).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IncidentClosureModel {
+    /// Total-capacity CAF: the Exhibit 11-23 per-open-lane CAF times the
+    /// open-lane ratio, with the segment keeping its full lane count for
+    /// density and LOS.
+    #[default]
+    CapacityAdjustment,
+    /// The blocked lanes are closed on the incident segment for the
+    /// incident's duration and the Exhibit 11-23 per-open-lane CAF applies
+    /// to the lanes that remain, as FREEVAL does through its lane
+    /// adjustment. Total capacity is the same as under
+    /// `CapacityAdjustment`; per-lane density and LOS reflect the blocked
+    /// lanes.
+    LaneClosure,
+}
+
+/// The same reliability analysis run under both incident closure models
+/// (see [`ReliabilityAnalysis::compare_incident_closure_models`]).
+#[derive(Debug, Clone)]
+pub struct IncidentClosureComparison {
+    /// Run with [`IncidentClosureModel::CapacityAdjustment`].
+    pub capacity_adjustment: ReliabilityAnalysis,
+    /// Run with [`IncidentClosureModel::LaneClosure`].
+    pub lane_closure: ReliabilityAnalysis,
+}
+
 /// A freeway facility reliability analysis (Chapter 11, Part B).
 ///
 /// Deserializable from JSON with the same schema as the
@@ -86,6 +118,9 @@ pub struct ReliabilityAnalysis {
     /// 25-105). Set false for a purely time-based (analysis-period)
     /// distribution.
     pub vmt_weighted: bool,
+    /// How incident lane blockages are modeled (default: total-capacity
+    /// CAF).
+    pub incident_closure: IncidentClosureModel,
 
     // ── Computed ─────────────────────────────────────────────────────────
     /// Generated scenario set (populated by [`Self::run`]).
@@ -115,6 +150,7 @@ impl Default for ReliabilityAnalysis {
             facility: FreewayFacility::new(),
             scenario_generation: ScenarioGenerationConfig::default(),
             vmt_weighted: true,
+            incident_closure: IncidentClosureModel::default(),
             scenario_set: None,
             scenario_results: Vec::new(),
             distribution: TravelTimeDistribution::new(),
@@ -185,6 +221,9 @@ impl ReliabilityAnalysis {
         // Per-segment/per-period CAF/SAF event multipliers.
         let mut caf = vec![vec![1.0; p_count]; n];
         let mut saf = vec![vec![1.0; p_count]; n];
+        // Per-segment/per-period lanes closed by incidents (lane-closure
+        // model only).
+        let mut closed = vec![vec![0u32; p_count]; n];
 
         let weather_daf = self
             .scenario_generation
@@ -213,13 +252,25 @@ impl ReliabilityAnalysis {
             let end = (inc.start_period + inc.duration_periods).min(p_count);
             let seg = inc.segment.min(n - 1);
             let lanes = fac.segments[seg].lanes;
-            // Total-capacity multiplier (Exhibit 11-23 per-open-lane CAF ×
-            // open-lane ratio); severity was already made feasible during
-            // generation, so the lookup cannot fail for lanes >= 2.
-            let caf_inc = incident_caf_total(lanes, inc.severity).unwrap_or(1.0);
+            // Severity was already made feasible during generation, so the
+            // lookups cannot fail for lanes >= 2.
+            let caf_inc = match self.incident_closure {
+                // Total-capacity multiplier (Exhibit 11-23 per-open-lane
+                // CAF × open-lane ratio).
+                IncidentClosureModel::CapacityAdjustment => {
+                    incident_caf_total(lanes, inc.severity).unwrap_or(1.0)
+                }
+                // The open-lane ratio is carried by the lane count instead.
+                IncidentClosureModel::LaneClosure => {
+                    incident_caf_per_open_lane(lanes, inc.severity).unwrap_or(1.0)
+                }
+            };
             for p in inc.start_period..end {
                 caf[seg][p] *= caf_inc;
                 daf[p] *= incident_daf;
+                if self.incident_closure == IncidentClosureModel::LaneClosure {
+                    closed[seg][p] += inc.severity.lanes_closed();
+                }
             }
         }
 
@@ -295,6 +346,14 @@ impl ReliabilityAnalysis {
                 Some((0..p_count).map(|p| base_caf[p] * caf[i][p]).collect());
             seg.saf_schedule =
                 Some((0..p_count).map(|p| base_saf[p] * saf[i][p]).collect());
+            if closed[i].iter().any(|&c| c > 0) {
+                let base_lanes: Vec<u32> = (0..p_count).map(|p| seg.lanes_at(p)).collect();
+                seg.lanes_schedule = Some(
+                    (0..p_count)
+                        .map(|p| base_lanes[p].saturating_sub(closed[i][p]).max(1))
+                        .collect(),
+                );
+            }
         }
 
         fac
@@ -306,13 +365,30 @@ impl ReliabilityAnalysis {
     /// reliability performance measures.
     pub fn run(&mut self) -> Result<(), String> {
         self.facility.validate()?;
+        let seed_stats = self.seed_statistics();
+        let set = generate_scenarios(&self.scenario_generation, &seed_stats)?;
+        self.evaluate_scenario_set(set)
+    }
+
+    /// Evaluate a given scenario set instead of generating one (Steps
+    /// B-10 through B-13), e.g. a set loaded from a
+    /// [`super::interchange::ScenarioSetFile`] or imported from a
+    /// FREEVAL scenario table, so that two engines can be compared on
+    /// exactly the same scenarios. Work zone and special event indices
+    /// refer to this analysis's `scenario_generation` lists.
+    pub fn run_scenario_set(&mut self, set: ScenarioSet) -> Result<(), String> {
+        self.facility.validate()?;
+        self.evaluate_scenario_set(set)
+    }
+
+    /// Steps B-10 through B-13 on a facility that has already been
+    /// validated, shared by [`Self::run`] and [`Self::run_scenario_set`].
+    fn evaluate_scenario_set(&mut self, set: ScenarioSet) -> Result<(), String> {
         self.free_flow_travel_time_min = self.free_flow_travel_time();
         if self.free_flow_travel_time_min <= 0.0 {
             return Err("free-flow travel time must be positive".into());
         }
-
-        let seed_stats = self.seed_statistics();
-        let set = generate_scenarios(&self.scenario_generation, &seed_stats)?;
+        self.validate_scenario_set(&set)?;
 
         self.distribution = TravelTimeDistribution::new();
         self.scenario_results = Vec::with_capacity(set.scenarios.len());
@@ -359,6 +435,74 @@ impl ReliabilityAnalysis {
         Ok(())
     }
 
+    /// Run the analysis under both incident closure models on the same
+    /// scenario set (scenario generation depends only on the seed dataset
+    /// and configuration, so the two runs see identical incidents). The
+    /// receiver's own `incident_closure` setting is ignored.
+    pub fn compare_incident_closure_models(&self) -> Result<IncidentClosureComparison, String> {
+        let mut capacity_adjustment = Self {
+            incident_closure: IncidentClosureModel::CapacityAdjustment,
+            ..self.clone()
+        };
+        capacity_adjustment.run()?;
+        let mut lane_closure = Self {
+            incident_closure: IncidentClosureModel::LaneClosure,
+            ..self.clone()
+        };
+        lane_closure.run()?;
+        Ok(IncidentClosureComparison {
+            capacity_adjustment,
+            lane_closure,
+        })
+    }
+
+    /// Check that a scenario set fits this facility and configuration.
+    fn validate_scenario_set(&self, set: &ScenarioSet) -> Result<(), String> {
+        let n = self.facility.num_segments();
+        let p_count = self.facility.num_periods();
+        let total: f64 = set.scenarios.iter().map(|s| s.probability).sum();
+        if set.scenarios.is_empty() {
+            return Err("scenario set is empty".into());
+        }
+        if (total - 1.0).abs() > 1e-6 {
+            return Err(format!("scenario probabilities sum to {total}, not 1"));
+        }
+        for sc in &set.scenarios {
+            for inc in &sc.incidents {
+                if inc.segment >= n {
+                    return Err(format!(
+                        "scenario {}: incident segment {} outside the {n}-segment facility",
+                        sc.id, inc.segment
+                    ));
+                }
+            }
+            let starts = sc
+                .incidents
+                .iter()
+                .map(|i| i.start_period)
+                .chain(sc.weather_events.iter().map(|w| w.start_period));
+            if let Some(p) = starts.into_iter().find(|&p| p >= p_count) {
+                return Err(format!(
+                    "scenario {}: event start period {p} outside the {p_count}-period study period",
+                    sc.id
+                ));
+            }
+            if let Some(&wz) =
+                sc.work_zones.iter().find(|&&w| w >= self.scenario_generation.work_zones.len())
+            {
+                return Err(format!("scenario {}: unknown work zone index {wz}", sc.id));
+            }
+            if let Some(&se) = sc
+                .special_events
+                .iter()
+                .find(|&&e| e >= self.scenario_generation.special_events.len())
+            {
+                return Err(format!("scenario {}: unknown special event index {se}", sc.id));
+            }
+        }
+        Ok(())
+    }
+
     /// Failure measure (Step B-11): percentage of the weighted
     /// distribution with facility space mean speed below
     /// `target_speed_mi_h` (targets of 35/45/50 mi/h are typical).
@@ -33,7 +33,8 @@
 use serde::{Deserialize, Serialize};
 
 use super::exhibits::{
-    weather_caf, weather_saf, IncidentDurationParams, IncidentSeverity, WeatherType,
+    hers_crash_rate, incident_rate_from_crash_rate, weather_caf, weather_saf,
+    IncidentDurationParams, IncidentSeverity, WeatherType,
     DEFAULT_INCIDENT_DURATION_PARAMS, DEFAULT_INCIDENT_SEVERITY_DISTRIBUTION,
     DEFAULT_INCIDENT_TO_CRASH_RATIO, INCIDENT_SEVERITIES, SEVERE_WEATHER_TYPES,
     URBAN_DEMAND_RATIOS,
@@ -201,8 +202,12 @@ pub struct IncidentInputs {
     pub monthly_frequencies: Option<Vec<f64>>,
     /// Local facility-wide crash rate per 100 million VMT (CR_j of
     /// Equation 25-78, constant across months here; use
-    /// `monthly_frequencies` for month-varying local data).
+    /// `monthly_frequencies` for month-varying local data). A locally
+    /// calibrated rate takes precedence over `hers`.
     pub crash_rate_per_100mvmt: Option<f64>,
+    /// Facility characteristics for estimating the crash rate with the
+    /// HERS model (Equation 25-79) when no local crash rate is available.
+    pub hers: Option<HersCrashInputs>,
     /// Local incident-to-crash ratio ICR (Equation 25-78; national default
     /// 4.9).
     pub incident_to_crash_ratio: f64,
@@ -221,6 +226,7 @@ impl Default for IncidentInputs {
         Self {
             monthly_frequencies: None,
             crash_rate_per_100mvmt: None,
+            hers: None,
             incident_to_crash_ratio: DEFAULT_INCIDENT_TO_CRASH_RATIO,
             severity_distribution: DEFAULT_INCIDENT_SEVERITY_DISTRIBUTION.to_vec(),
             duration_params: DEFAULT_INCIDENT_DURATION_PARAMS.to_vec(),
@@ -229,6 +235,63 @@ impl Default for IncidentInputs {
     }
 }
 
+impl IncidentInputs {
+    /// Crash rate per 100 million VMT that Equation 25-78 will use, and
+    /// where it came from. Directly specified monthly frequencies bypass
+    /// the crash rate entirely, so they resolve to no rate.
+    pub fn resolved_crash_rate(&self) -> (IncidentRateSource, Option<f64>) {
+        if self.monthly_frequencies.is_some() {
+            (IncidentRateSource::MonthlyFrequencies, None)
+        } else if let Some(cr) = self.crash_rate_per_100mvmt {
+            (IncidentRateSource::LocalCrashRate, Some(cr))
+        } else if let Some(hers) = &self.hers {
+            (IncidentRateSource::HersModel, Some(hers.crash_rate()))
+        } else {
+            (IncidentRateSource::None, None)
+        }
+    }
+}
+
+/// Facility characteristics for the HERS crash-rate model (Equation
+/// 25-79), used when no locally calibrated crash rate is available.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HersCrashInputs {
+    /// Facility annual average daily traffic, veh/day (both directions).
+    pub aadt: f64,
+    /// Facility two-way hourly capacity, veh/h.
+    pub two_way_hourly_capacity: f64,
+    /// Average lane width, ft.
+    pub lane_width_ft: f64,
+}
+
+impl HersCrashInputs {
+    /// AADT-to-capacity ratio ACR of Equation 25-79.
+    pub fn acr(&self) -> f64 {
+        self.aadt / self.two_way_hourly_capacity
+    }
+
+    /// Equation 25-79 crash rate per 100 million VMT.
+    pub fn crash_rate(&self) -> f64 {
+        hers_crash_rate(self.acr(), self.lane_width_ft)
+    }
+}
+
+/// Where the expected incident frequency of a generated scenario set came
+/// from (Chapter 25 Step 20).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IncidentRateSource {
+    /// No incidents were modeled, or no frequency source was supplied.
+    #[default]
+    None,
+    /// `IncidentInputs::monthly_frequencies` supplied n_j directly.
+    MonthlyFrequencies,
+    /// A locally calibrated crash rate (`crash_rate_per_100mvmt`).
+    LocalCrashRate,
+    /// The HERS crash-rate model (Equation 25-79) from facility AADT,
+    /// capacity and lane width.
+    HersModel,
+}
+
 /// A scheduled short-term work zone (Chapter 11 Step B-8; Chapter 25
 /// Steps 6–9). Long-term work zones covering the whole RRP belong in the
 /// base scenario instead.
@@ -594,6 +657,17 @@ pub struct ScenarioSet {
     pub total_incidents: usize,
     /// Total number of generated weather events across all scenarios.
     pub total_weather_events: usize,
+    /// Source of the incident frequency (Step 20).
+    #[serde(default)]
+    pub incident_rate_source: IncidentRateSource,
+    /// Crash rate per 100 million VMT used by Equation 25-78, when the
+    /// frequency was derived from a crash rate.
+    #[serde(default)]
+    pub crash_rate_per_100mvmt: Option<f64>,
+    /// Incident rate IR per 100 million VMT (Equation 25-78), when the
+    /// frequency was derived from a crash rate.
+    #[serde(default)]
+    pub incident_rate_per_100mvmt: Option<f64>,
 }
 
 // ═════════════════════════════════════════════════════════════════════════
@@ -965,12 +1039,21 @@ pub fn generate_scenarios(
     // ── Steps 19–34: incidents ───────────────────────────────────────────
     let mut monthly_incident_frequency = vec![0.0; 12];
     let mut total_incidents = 0usize;
+    let mut incident_rate_source = IncidentRateSource::None;
+    let mut crash_rate_per_100mvmt = None;
+    let mut incident_rate_per_100mvmt = None;
 
     if let Some(incidents) = &config.incidents {
         let g = &incidents.severity_distribution;
         if (g.iter().sum::<f64>() - 1.0).abs() > 1e-6 {
             return Err("incident severity distribution must sum to 1.0".into());
         }
+        let (source, crash_rate) = incidents.resolved_crash_rate();
+        incident_rate_source = source;
+        crash_rate_per_100mvmt = crash_rate;
+        // Equation 25-78.
+        incident_rate_per_100mvmt = crash_rate
+            .map(|cr| incident_rate_from_crash_rate(cr, incidents.incident_to_crash_ratio));
 
         // Step 20: expected incident frequency by month (Equations
         // 25-77/25-78): n_j = IR_j × VMT_j, with VMT_j the average scenario
@@ -990,8 +1073,7 @@ pub fn generate_scenarios(
             }
             let n_j = if let Some(freqs) = &incidents.monthly_frequencies {
                 freqs.get(month as usize - 1).copied().unwrap_or(0.0)
-            } else if let Some(cr) = incidents.crash_rate_per_100mvmt {
-                let ir = cr * incidents.incident_to_crash_ratio; // Eq. 25-78
+            } else if let Some(ir) = incident_rate_per_100mvmt {
                 let mean_daf: f64 = month_ids
                     .iter()
                     .map(|&id| scenarios[id].daf)
@@ -1136,6 +1218,9 @@ pub fn generate_scenarios(
         monthly_incident_frequency,
         total_incidents,
         total_weather_events,
+        incident_rate_source,
+        crash_rate_per_100mvmt,
+        incident_rate_per_100mvmt,
     })
 }
 
@@ -1177,6 +1262,17 @@ fn validate_config(
     if config.demand_multipliers.len() != 12 {
         return Err("demand_multipliers must have 12 rows (Jan-Dec)".into());
     }
+    if let Some(hers) = config.incidents.as_ref().and_then(|i| i.hers.as_ref()) {
+        for (name, value) in [
+            ("aadt", hers.aadt),
+            ("two_way_hourly_capacity", hers.two_way_hourly_capacity),
+            ("lane_width_ft", hers.lane_width_ft),
+        ] {
+            if value.is_nan() || value <= 0.0 {
+                return Err(format!("HERS crash model: {name} must be positive"));
+            }
+        }
+    }
     if let Some(w) = &config.weather {
         if w.probabilities_by_month.len() != 12 {
             return Err("weather probabilities_by_month must have 12 rows".into());
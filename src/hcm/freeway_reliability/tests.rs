@@ -1,9 +1,12 @@
-//! Unit tests for HCM Chapter 11 scenario generation and the reliability
-//! wrapper (exhibit lookups are tested in `exhibits.rs`; distribution
-//! metrics in `common::reliability`).
+//! Unit tests for HCM Chapter 11 scenario generation, the reliability
+//! wrapper, scenario set interchange and the planning batch mode (exhibit
+//! lookups are tested in
+//! `exhibits.rs`; distribution metrics in `common::reliability`).
 
 use super::exhibits::*;
-use super::reliability::ReliabilityAnalysis;
+use super::interchange::*;
+use super::planning_batch::*;
+use super::reliability::{IncidentClosureModel, ReliabilityAnalysis};
 use super::scenario_generation::*;
 
 use crate::hcm::freeway_facilities::freeway_facilities::{FacilitySegment, FreewayFacility, SegmentType};
@@ -328,6 +331,100 @@ fn test_incident_frequency_from_crash_rate() {
     );
 }
 
+#[test]
+fn test_incident_frequency_from_hers_model() {
+    // Equation 25-79 feeds Equation 25-78 when no local crash rate is
+    // given: 60,000 veh/day over a 6,600 veh/h two-way capacity, 11-ft
+    // lanes.
+    let fac = small_facility();
+    let stats = seed_stats(&fac);
+    let hers = HersCrashInputs {
+        aadt: 60_000.0,
+        two_way_hourly_capacity: 6_600.0,
+        lane_width_ft: 11.0,
+    };
+    let cfg = ScenarioGenerationConfig {
+        months: vec![1],
+        incidents: Some(IncidentInputs {
+            hers: Some(hers),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let set = generate_scenarios(&cfg, &stats).unwrap();
+    let cr = hers_crash_rate(60_000.0 / 6_600.0, 11.0);
+    assert_eq!(set.incident_rate_source, IncidentRateSource::HersModel);
+    assert_eq!(set.crash_rate_per_100mvmt, Some(cr));
+    let ir = cr * DEFAULT_INCIDENT_TO_CRASH_RATIO;
+    assert_eq!(set.incident_rate_per_100mvmt, Some(ir));
+    let mean_daf = (1.00 + 1.00 + 1.02 + 1.05 + 1.17) / 5.0;
+    let expected = ir * stats.total_vmt() * mean_daf / 1e8;
+    assert!((set.monthly_incident_frequency[0] - expected).abs() < 1e-9);
+}
+
+#[test]
+fn test_local_crash_rate_overrides_hers_model() {
+    let fac = small_facility();
+    let stats = seed_stats(&fac);
+    let hers = HersCrashInputs {
+        aadt: 60_000.0,
+        two_way_hourly_capacity: 6_600.0,
+        lane_width_ft: 12.0,
+    };
+    let local = ScenarioGenerationConfig {
+        months: vec![1],
+        incidents: Some(IncidentInputs {
+            crash_rate_per_100mvmt: Some(150.0),
+            hers: Some(hers),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let set = generate_scenarios(&local, &stats).unwrap();
+    assert_eq!(set.incident_rate_source, IncidentRateSource::LocalCrashRate);
+    assert_eq!(set.crash_rate_per_100mvmt, Some(150.0));
+
+    // Directly specified frequencies take precedence over both and carry
+    // no crash rate.
+    let direct = ScenarioGenerationConfig {
+        months: vec![1],
+        incidents: Some(IncidentInputs {
+            monthly_frequencies: Some(vec![0.5; 12]),
+            crash_rate_per_100mvmt: Some(150.0),
+            hers: Some(hers),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let set = generate_scenarios(&direct, &stats).unwrap();
+    assert_eq!(set.incident_rate_source, IncidentRateSource::MonthlyFrequencies);
+    assert_eq!(set.crash_rate_per_100mvmt, None);
+    assert_eq!(set.monthly_incident_frequency[0], 0.5);
+
+    // No incidents at all.
+    let none = ScenarioGenerationConfig { months: vec![1], ..Default::default() };
+    let set = generate_scenarios(&none, &stats).unwrap();
+    assert_eq!(set.incident_rate_source, IncidentRateSource::None);
+}
+
+#[test]
+fn test_hers_inputs_rejected_when_not_positive() {
+    let fac = small_facility();
+    let cfg = ScenarioGenerationConfig {
+        incidents: Some(IncidentInputs {
+            hers: Some(HersCrashInputs {
+                aadt: 60_000.0,
+                two_way_hourly_capacity: 0.0,
+                lane_width_ft: 12.0,
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let err = generate_scenarios(&cfg, &seed_stats(&fac)).unwrap_err();
+    assert!(err.contains("two_way_hourly_capacity"), "{err}");
+}
+
 #[test]
 fn test_work_zone_and_special_event_assignment() {
     let fac = small_facility();
@@ -513,6 +610,90 @@ fn test_scenario_facility_daf_and_caf_folding() {
     assert!((caf2[1] - 0.88).abs() < 1e-12);
 }
 
+fn one_lane_incident_scenario() -> FreewayScenario {
+    FreewayScenario {
+        id: 0,
+        month: 1,
+        weekday: Weekday::Monday,
+        replication: 0,
+        probability: 1.0,
+        demand_multiplier: 1.0,
+        daf: 1.0,
+        weather_events: vec![],
+        incidents: vec![IncidentAssignment {
+            severity: IncidentSeverity::OneLane,
+            segment: 2,
+            start_period: 1,
+            duration_periods: 2,
+        }],
+        work_zones: vec![],
+        special_events: vec![],
+    }
+}
+
+#[test]
+fn test_scenario_facility_lane_closure_model() {
+    // The lane-closure model drops one of three lanes on segment 3 for
+    // periods 2-3 and applies the Exhibit 11-23 per-open-lane CAF alone,
+    // so total capacity matches the CAF-only model while per-lane density
+    // rises on the blocked segment.
+    let sc = one_lane_incident_scenario();
+    let caf_only = ReliabilityAnalysis::new(small_facility(), ScenarioGenerationConfig::default());
+    let closure = ReliabilityAnalysis {
+        incident_closure: IncidentClosureModel::LaneClosure,
+        ..caf_only.clone()
+    };
+
+    let mut a = caf_only.build_scenario_facility(&sc);
+    let mut b = closure.build_scenario_facility(&sc);
+    assert!(a.segments[2].lanes_schedule.is_none());
+    assert_eq!(b.segments[2].lanes_schedule, Some(vec![3, 2, 2, 3]));
+    assert!(b.segments[0].lanes_schedule.is_none());
+    let caf_b = b.segments[2].caf_schedule.as_ref().unwrap();
+    assert!((caf_b[1] - 0.74).abs() < 1e-12);
+    assert!((caf_b[0] - 1.0).abs() < 1e-12);
+
+    a.run_analysis().unwrap();
+    b.run_analysis().unwrap();
+    for p in 0..4 {
+        assert!((a.capacity[2][p] - b.capacity[2][p]).abs() < 1e-6, "period {p}");
+    }
+    assert!(b.density_veh[2][1] > a.density_veh[2][1]);
+    assert!((b.density_veh[2][0] - a.density_veh[2][0]).abs() < 1e-9);
+    // Unaffected upstream segment is identical in period 1.
+    assert!((a.speed[0][0] - b.speed[0][0]).abs() < 1e-9);
+}
+
+#[test]
+fn test_compare_incident_closure_models() {
+    let cfg = ScenarioGenerationConfig {
+        months: vec![1],
+        incidents: Some(IncidentInputs {
+            monthly_frequencies: Some(vec![1.0; 12]),
+            ..Default::default()
+        }),
+        rng_seed: 3,
+        ..Default::default()
+    };
+    let rel = ReliabilityAnalysis::new(small_facility(), cfg);
+    let cmp = rel.compare_incident_closure_models().unwrap();
+    let a = &cmp.capacity_adjustment;
+    let b = &cmp.lane_closure;
+    assert_eq!(a.incident_closure, IncidentClosureModel::CapacityAdjustment);
+    assert_eq!(b.incident_closure, IncidentClosureModel::LaneClosure);
+    // Same scenarios under both models.
+    assert_eq!(
+        serde_json::to_string(a.scenario_set.as_ref().unwrap()).unwrap(),
+        serde_json::to_string(b.scenario_set.as_ref().unwrap()).unwrap()
+    );
+    assert!(a.scenario_set.as_ref().unwrap().total_incidents > 0);
+    assert_eq!(a.scenario_results.len(), b.scenario_results.len());
+    // Capacity is the same, so travel times stay close.
+    let tti_a = a.metrics.as_ref().unwrap().tti_mean;
+    let tti_b = b.metrics.as_ref().unwrap().tti_mean;
+    assert!((tti_a - tti_b).abs() / tti_a < 0.05, "{tti_a} vs {tti_b}");
+}
+
 #[test]
 fn test_reliability_run_small_rrp() {
     // Two months, weather + incidents: distribution is populated, weights
@@ -584,3 +765,235 @@ fn test_fixture_json_roundtrip() {
     rel.run().unwrap();
     assert_eq!(rel.scenario_results.len(), 2 * 5 * 2);
 }
+
+// ═════════════════════════════════════════════════════════════════════════
+// Scenario set interchange
+// ═════════════════════════════════════════════════════════════════════════
+
+/// A run analysis with weather and incidents in two months.
+fn run_small_rrp() -> ReliabilityAnalysis {
+    let mut weather = WeatherInputs::default();
+    for m in 0..12 {
+        weather.probabilities_by_month[m][1] = 0.10;
+    }
+    weather.durations_min[1] = 45.0;
+    let cfg = ScenarioGenerationConfig {
+        months: vec![1, 7],
+        replications: 2,
+        weather: Some(weather),
+        incidents: Some(IncidentInputs {
+            monthly_frequencies: Some(vec![0.8; 12]),
+            ..Default::default()
+        }),
+        rng_seed: 2,
+        ..Default::default()
+    };
+    let mut rel = ReliabilityAnalysis::new(small_facility(), cfg);
+    rel.run().unwrap();
+    rel
+}
+
+#[test]
+fn test_scenario_set_file_round_trips_losslessly() {
+    let rel = run_small_rrp();
+    let file = ScenarioSetFile::from_analysis(&rel).unwrap();
+    assert_eq!(file.format, SCENARIO_SET_FORMAT);
+    assert_eq!(file.version, SCENARIO_SET_FORMAT_VERSION);
+    assert_eq!(file.results.len(), 20);
+
+    let json = file.to_json().unwrap();
+    let back = ScenarioSetFile::from_json(&json).unwrap();
+    // Re-serializing gives identical text, and every f64 survives bit for
+    // bit.
+    assert_eq!(back.to_json().unwrap(), json);
+    for (a, b) in file.results.iter().zip(&back.results) {
+        for (x, y) in a.tti.iter().zip(&b.tti) {
+            assert_eq!(x.to_bits(), y.to_bits());
+        }
+        assert_eq!(a.vhd.to_bits(), b.vhd.to_bits());
+    }
+    let set = rel.scenario_set.as_ref().unwrap();
+    for (a, b) in set.scenarios.iter().zip(&back.scenario_set.scenarios) {
+        assert_eq!(a.probability.to_bits(), b.probability.to_bits());
+        assert_eq!(a.daf.to_bits(), b.daf.to_bits());
+    }
+    assert_eq!(
+        back.metrics.as_ref().unwrap().tti_95.to_bits(),
+        rel.metrics.as_ref().unwrap().tti_95.to_bits()
+    );
+}
+
+#[test]
+fn test_scenario_set_file_rejects_foreign_and_newer_files() {
+    let rel = run_small_rrp();
+    let file = ScenarioSetFile::from_analysis(&rel).unwrap();
+    let mut value: serde_json::Value = serde_json::to_value(&file).unwrap();
+    value["version"] = serde_json::json!(SCENARIO_SET_FORMAT_VERSION + 1);
+    let err = ScenarioSetFile::from_json(&value.to_string()).unwrap_err();
+    assert!(err.contains("unsupported"), "{err}");
+    value["format"] = serde_json::json!("something-else");
+    assert!(ScenarioSetFile::from_json(&value.to_string()).is_err());
+    assert!(ScenarioSetFile::from_json("{}").is_err());
+    // An analysis that has not been run has nothing to save.
+    let fresh = ReliabilityAnalysis::new(small_facility(), ScenarioGenerationConfig::default());
+    assert!(ScenarioSetFile::from_analysis(&fresh).is_err());
+}
+
+#[test]
+fn test_saved_scenario_set_reevaluates_to_identical_results() {
+    let rel = run_small_rrp();
+    let json = ScenarioSetFile::from_analysis(&rel).unwrap().to_json().unwrap();
+    let loaded = ScenarioSetFile::from_json(&json).unwrap();
+    let mut again = ReliabilityAnalysis::new(small_facility(), loaded.generation.unwrap());
+    again.run_scenario_set(loaded.scenario_set).unwrap();
+    assert_eq!(
+        serde_json::to_string(&again.scenario_results).unwrap(),
+        serde_json::to_string(&rel.scenario_results).unwrap()
+    );
+}
+
+#[test]
+fn test_scenario_table_round_trip() {
+    let rel = run_small_rrp();
+    let set = rel.scenario_set.as_ref().unwrap();
+    let table = export_scenario_table(set);
+    assert!(table.starts_with("scenario,month,weekday,"));
+    let imported = import_scenario_table(&table, 60.0).unwrap();
+    assert_eq!(imported.scenarios.len(), set.scenarios.len());
+    assert_eq!(imported.total_incidents, set.total_incidents);
+    assert_eq!(imported.total_weather_events, set.total_weather_events);
+    assert_eq!(
+        serde_json::to_string(&imported.scenarios).unwrap(),
+        serde_json::to_string(&set.scenarios).unwrap()
+    );
+
+    // The imported set evaluates to the same results as the generated one.
+    let mut other = ReliabilityAnalysis::new(small_facility(), rel.scenario_generation.clone());
+    other.run_scenario_set(imported).unwrap();
+    assert_eq!(
+        serde_json::to_string(&other.scenario_results).unwrap(),
+        serde_json::to_string(&rel.scenario_results).unwrap()
+    );
+}
+
+#[test]
+fn test_import_freeval_style_table_with_labels_and_defaults() {
+    // Two scenarios: one quiet, one with heavy rain and a one-lane incident
+    // written with the Exhibit 11-20/11-22 labels and 1-based numbering.
+    let csv = "\
+Scenario,Probability,DAF,Month,Weekday,Event,Type,Segment,Start_Period,Duration_Periods
+1,0.5,1.0,3,Tue,,,,,
+2,0.5,1.1,3,Tue,weather,Heavy rain,,2,2
+2,0.5,1.1,3,Tue,incident,1 lane closed,3,1,1
+";
+    let set = import_scenario_table(csv, 60.0).unwrap();
+    assert_eq!(set.scenarios.len(), 2);
+    let sc = &set.scenarios[1];
+    assert_eq!(sc.weekday, Weekday::Tuesday);
+    assert_eq!(sc.demand_multiplier, 1.1);
+    assert_eq!(sc.weather_events[0].weather, WeatherType::HeavyRain);
+    assert_eq!(sc.weather_events[0].start_period, 1);
+    assert_eq!(sc.weather_events[0].caf, weather_caf(WeatherType::HeavyRain, 60.0));
+    assert_eq!(sc.incidents[0].severity, IncidentSeverity::OneLane);
+    assert_eq!(sc.incidents[0].segment, 2);
+    assert_eq!(set.expected_weather_events[2][1], 1);
+
+    let mut rel = ReliabilityAnalysis::new(small_facility(), ScenarioGenerationConfig::default());
+    rel.run_scenario_set(set).unwrap();
+    assert_eq!(rel.scenario_results.len(), 2);
+}
+
+#[test]
+fn test_import_scenario_table_errors_name_the_line() {
+    let missing = "scenario,probability\n1,1.0\n";
+    assert!(import_scenario_table(missing, 60.0).unwrap_err().contains("daf"));
+    let gap = "scenario,probability,daf\n1,0.5,1\n3,0.5,1\n";
+    assert!(import_scenario_table(gap, 60.0).unwrap_err().contains("line 3"));
+    let bad = "scenario,probability,daf,event,type,start_period,duration_periods\n\
+               1,1,1,weather,Drizzle,1,1\n";
+    assert!(import_scenario_table(bad, 60.0).unwrap_err().contains("line 2"));
+    let clash = "scenario,probability,daf\n1,0.5,1\n1,0.6,1\n";
+    assert!(import_scenario_table(clash, 60.0).unwrap_err().contains("disagree"));
+
+    // A set that does not fit the facility is refused at evaluation.
+    let far = "scenario,probability,daf,event,type,segment,start_period,duration_periods\n\
+               1,1,1,incident,OneLane,9,1,1\n";
+    let set = import_scenario_table(far, 60.0).unwrap();
+    let mut rel = ReliabilityAnalysis::new(small_facility(), ScenarioGenerationConfig::default());
+    assert!(rel.run_scenario_set(set).unwrap_err().contains("segment"));
+}
+
+// ═════════════════════════════════════════════════════════════════════════
+// Planning-level batch mode
+// ═════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_planning_batch_reproduces_example_problem_10() {
+    // EP10: FFS 75, peak speed 62, 3 lanes, X = 0.95. Capacity caps at
+    // 2,400 pc/h/ln, so X = 0.95 needs 2,280 pc/h/ln = 6,840 veh/h at PHF 1.
+    let csv = "id,aadt,lanes,ffs,k_factor,d_factor,length_mi,phf,peak_speed\n\
+               ep10,76000,3,75,0.1,0.9,4.0,1.0,62\n";
+    let out = run_planning_batch_csv(csv, &PlanningBatchConfig::default()).unwrap();
+    assert!(out.errors.is_empty());
+    let r = &out.results[0];
+    assert_eq!(r.line, 2);
+    assert!((r.vc_ratio - 0.95).abs() < 1e-12);
+    assert!((r.tti_mean - 1.899).abs() < 0.001);
+    assert!((r.tti_95 - 3.353).abs() < 0.005);
+    assert!((r.pt45 - 0.743).abs() < 0.001);
+    assert!(r.unreliable && !r.over_capacity);
+    // Delay: 6,840 veh × 4 mi × (TTI_mean − 1) / 75 mi/h.
+    assert!((r.peak_hour_vhd - 6840.0 * 4.0 * (r.tti_mean - 1.0) / 75.0).abs() < 1e-9);
+    assert!((r.annual_vhd - 250.0 * r.peak_hour_vhd).abs() < 1e-9);
+}
+
+#[test]
+fn test_planning_batch_derives_speed_and_flags_records() {
+    let csv = "id,aadt,lanes,ffs,k_factor,d_factor,length_mi,heavy_vehicle_pct,crash_rate\n\
+               light,30000,3,70,0.09,0.55,2.0,5,\n\
+               heavy,150000,2,65,0.1,0.6,3.0,10,\n\
+               crashy,30000,3,70,0.09,0.55,2.0,5,400\n";
+    let out = run_planning_batch_csv(csv, &PlanningBatchConfig::default()).unwrap();
+    assert_eq!(out.results.len(), 3);
+    let light = &out.results[0];
+    assert_eq!(light.peak_speed, 70.0); // below the Exhibit 12-6 breakpoint
+    assert!(!light.unreliable && !light.over_capacity);
+    let heavy = &out.results[1];
+    assert!(heavy.over_capacity && heavy.vc_ratio > 1.0);
+    assert!((heavy.peak_speed - heavy.capacity / 45.0).abs() < 1e-9);
+    assert!(heavy.unreliable);
+    // A crash rate above the HERS prediction raises incident delay only.
+    let crashy = &out.results[2];
+    assert!(crashy.incident_delay_rate > light.incident_delay_rate);
+    assert_eq!(crashy.recurring_delay_rate, light.recurring_delay_rate);
+    let flagged: Vec<&str> = out.flagged().map(|r| r.id.as_str()).collect();
+    assert!(flagged.contains(&"heavy"));
+    assert!(!flagged.contains(&"light"));
+    assert_eq!(out.to_csv().lines().count(), 4);
+}
+
+#[test]
+fn test_planning_batch_reports_bad_rows_without_aborting() {
+    let csv = "id,aadt,lanes,ffs,k_factor,d_factor,length_mi\n\
+               ok,40000,2,65,0.1,0.55,1.5\n\
+               neg,-5,2,65,0.1,0.55,1.5\n\
+               text,40000,two,65,0.1,0.55,1.5\n\
+               k,40000,2,65,1.4,0.55,1.5\n\
+               \"quote,40000,2,65,0.1,0.55,1.5\n\
+               ok2,40000,3,65,0.1,0.55,1.5\n";
+    let out = run_planning_batch_csv(csv, &PlanningBatchConfig::default()).unwrap();
+    let ids: Vec<&str> = out.results.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["ok", "ok2"]);
+    assert_eq!(out.errors.len(), 4);
+    assert_eq!(out.errors[0].line, 3);
+    assert!(out.errors[0].message.contains("line 3") && out.errors[0].message.contains("aadt"));
+    assert_eq!(out.errors[1].id.as_deref(), Some("text"));
+    assert!(out.errors[1].message.contains("lanes"));
+    assert!(out.errors[2].message.contains("k_factor"));
+    assert_eq!(out.errors[3].line, 6);
+    assert_eq!(out.errors[3].id, None);
+    assert_eq!(out.errors[3].message, "line 6: unterminated quoted field");
+
+    let err = run_planning_batch_csv("id,aadt\nx,1\n", &PlanningBatchConfig::default());
+    assert!(err.unwrap_err().contains("lanes"));
+}
@@ -0,0 +1,326 @@
+//! Design analysis for ramp junctions: the shortest acceleration or deceleration lane that
+//! delivers a target LOS, and optionally a target v/c, for given demands.
+//!
+//! Chapter 14 takes the lane length as an input and reports density. This module inverts it. For
+//! each HCM edition and ramp lane count asked for, it scans the lane length from `min_length` to
+//! `max_length` in steps of `length_step`, keeps every point as a sensitivity curve, and reports
+//! the shortest length that meets the target.
+//!
+//! The lane length enters the two editions differently. The 7th Edition reads it in Equation 14-3
+//! (P_FM), Equations 14-22/14-23 (density) and Exhibit 14-13 (merge speed), through the Equation
+//! 14-25/14-26 effective length on a two-lane ramp. Edition 7.1 reads it only in the speed
+//! impedance, Equation 14-4 for a merge ([`merge_speed_impedance`](super::v7_1::merge_speed_impedance))
+//! and 14-5 for a diverge, and so also in the Equation 14-8 capacity. A 7th Edition v/c compares
+//! demand with the downstream freeway and ramp roadway capacities, which do not depend on the
+//! length, so a v/c target the 7th Edition misses is missed at every length. Under Edition 7.1 the
+//! impedance falls toward zero as the lane lengthens, so density falls only toward that of the
+//! equivalent basic segment, and no lane length buys a LOS better than the basic segment's.
+//!
+//! On a two-lane ramp the first lane (L_A1 or L_D1) is scanned and the second is held at the
+//! template's value. The Edition 7.1 path reads the first lane only.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hcm::common::length_search::{
+    self, both_editions, default_length_step, default_target, length_grid, validate_search,
+};
+use crate::hcm::common::{HcmVersion, LevelOfService};
+
+use super::merge_diverge::{RampLanes, RampSegment, RampType, RAMP_INFLUENCE_AREA_LENGTH};
+
+fn default_min_length() -> f64 {
+    100.0
+}
+
+fn default_max_length() -> f64 {
+    RAMP_INFLUENCE_AREA_LENGTH
+}
+
+/// What to search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RampDesignSpec {
+    /// An on-ramp or off-ramp with its demands, speeds, heavy vehicles, terrain and adjacent
+    /// ramps. Its edition, ramp lane count and first lane length are replaced by the search; a
+    /// two-lane candidate takes its second lane length from here.
+    pub template: RampSegment,
+    /// Worst acceptable LOS, A to E.
+    #[serde(default = "default_target")]
+    pub target_los: LevelOfService,
+    /// Highest acceptable v/c (7th Edition) or d/c (Edition 7.1). None for no v/c target.
+    #[serde(default)]
+    pub target_vc: Option<f64>,
+    /// Editions to design under.
+    #[serde(default = "both_editions")]
+    pub versions: Vec<HcmVersion>,
+    /// Candidate ramp lane counts. Empty means the template's.
+    #[serde(default)]
+    pub ramp_lanes: Vec<RampLanes>,
+    /// Shortest lane length to try (ft).
+    #[serde(default = "default_min_length")]
+    pub min_length: f64,
+    /// Longest lane length to try (ft).
+    #[serde(default = "default_max_length")]
+    pub max_length: f64,
+    /// Step between the lengths tried (ft).
+    #[serde(default = "default_length_step")]
+    pub length_step: f64,
+}
+
+/// Operational results at one lane length.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RampDesignPoint {
+    /// Length of the first acceleration or deceleration lane (ft).
+    pub length: f64,
+    /// Length the edition's equations read (ft): Equation 14-25/14-26 on a 7th Edition two-lane
+    /// ramp, otherwise `length`.
+    pub effective_length: f64,
+    /// Ramp influence area density (pc/mi/ln). Infinite where Edition 7.1 has no speed.
+    pub density: f64,
+    /// Ramp influence area speed S_R (7th Edition) or S_M/S_D (Edition 7.1) (mi/h).
+    pub speed: Option<f64>,
+    /// v/c (7th Edition) or d/c (Edition 7.1, Equation 14-8).
+    pub vc_ratio: Option<f64>,
+    pub los: LevelOfService,
+}
+
+/// One candidate: an edition and a ramp lane count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RampDesignCandidate {
+    pub version: HcmVersion,
+    pub ramp_lanes: RampLanes,
+    /// Length of the second lane, held fixed (ft). Two-lane ramps only.
+    pub second_lane_length: Option<f64>,
+    /// Shortest length meeting the target. None when no length up to `max_length` does.
+    pub design: Option<RampDesignPoint>,
+    /// Every length tried, shortest first.
+    pub curve: Vec<RampDesignPoint>,
+}
+
+/// Every candidate, in the order editions, ramp lane counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RampDesignResult {
+    pub target_los: LevelOfService,
+    pub target_vc: Option<f64>,
+    pub candidates: Vec<RampDesignCandidate>,
+}
+
+impl RampDesignResult {
+    /// The candidates that meet the target within `max_length`.
+    pub fn feasible(&self) -> impl Iterator<Item = &RampDesignCandidate> {
+        self.candidates.iter().filter(|c| c.design.is_some())
+    }
+}
+
+impl RampDesignSpec {
+    /// Run the search.
+    pub fn search(&self) -> Result<RampDesignResult, String> {
+        self.validate()?;
+        let mut candidates = Vec::new();
+        for &version in &self.versions {
+            for &lanes in &self.candidate_ramp_lanes() {
+                candidates.push(self.candidate(version, lanes)?);
+            }
+        }
+        Ok(RampDesignResult { target_los: self.target_los, target_vc: self.target_vc, candidates })
+    }
+
+    fn candidate_ramp_lanes(&self) -> Vec<RampLanes> {
+        if self.ramp_lanes.is_empty() {
+            vec![self.template.ramp_lanes]
+        } else {
+            self.ramp_lanes.clone()
+        }
+    }
+
+    /// The template's second lane length for an on-ramp or off-ramp.
+    fn second_lane_length(&self) -> Option<f64> {
+        match self.template.ramp_type {
+            RampType::OnRamp => self.template.accel_lane_length2,
+            _ => self.template.decel_lane_length2,
+        }
+    }
+
+    fn meets(&self, point: &RampDesignPoint) -> bool {
+        point.los <= self.target_los
+            && match self.target_vc {
+                Some(target) => point.vc_ratio.is_some_and(|vc| vc <= target),
+                None => true,
+            }
+    }
+
+    fn candidate(
+        &self,
+        version: HcmVersion,
+        lanes: RampLanes,
+    ) -> Result<RampDesignCandidate, String> {
+        let mut segment = self.template.clone();
+        segment.version = version;
+        segment.ramp_lanes = lanes;
+        let second_lane_length = match lanes {
+            RampLanes::OneLane => None,
+            RampLanes::TwoLane => self.second_lane_length(),
+        };
+
+        let mut curve = Vec::new();
+        let mut design = None;
+        // The tolerance keeps a `max_length` on the grid from being lost to rounding.
+        let longest = self.max_length + 1e-9 * self.length_step;
+        for length in length_grid(self.min_length, self.length_step).take_while(|&l| l <= longest) {
+            let point = evaluate(&segment, length)?;
+            if design.is_none() && self.meets(&point) {
+                design = Some(point);
+            }
+            curve.push(point);
+        }
+
+        Ok(RampDesignCandidate { version, ramp_lanes: lanes, second_lane_length, design, curve })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !matches!(self.template.ramp_type, RampType::OnRamp | RampType::OffRamp) {
+            return Err(format!(
+                "only an on-ramp or off-ramp has an acceleration or deceleration lane to design, \
+                 got {:?}",
+                self.template.ramp_type
+            ));
+        }
+        validate_search(
+            self.target_los,
+            &self.versions,
+            self.min_length,
+            self.length_step,
+            self.template.phf,
+        )?;
+        if let Some(vc) = self.target_vc {
+            if vc.is_nan() || vc <= 0.0 {
+                return Err(format!("target_vc must be positive, got {vc}"));
+            }
+        }
+        if self.candidate_ramp_lanes().contains(&RampLanes::TwoLane)
+            && self.second_lane_length().is_none_or(|l| l.is_nan() || l <= 0.0)
+        {
+            return Err("a two-lane ramp candidate needs the template's second lane length".to_string());
+        }
+        if self.max_length.is_nan() || self.max_length < self.min_length {
+            return Err(format!(
+                "max_length must be at least min_length ({} ft), got {} ft",
+                self.min_length, self.max_length
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        length_search::from_json(s)
+    }
+}
+
+/// Run `segment`'s edition with a first lane of `length`.
+fn evaluate(segment: &RampSegment, length: f64) -> Result<RampDesignPoint, String> {
+    let mut seg = segment.clone();
+    let on_ramp = seg.ramp_type == RampType::OnRamp;
+    if on_ramp {
+        seg.accel_lane_length = Some(length);
+    } else {
+        seg.decel_lane_length = Some(length);
+    }
+    let los = seg.run_analysis().ok_or_else(|| {
+        format!("the {:?} junction has no LOS with a {length} ft lane", seg.ramp_type)
+    })?;
+    let effective_length = match seg.version {
+        HcmVersion::V7 if on_ramp => seg.effective_la(),
+        HcmVersion::V7 => seg.effective_ld(),
+        HcmVersion::V7_1 => length,
+    };
+    Ok(RampDesignPoint {
+        length,
+        effective_length,
+        density: seg.density.unwrap_or(f64::INFINITY),
+        speed: seg.speed_ramp,
+        vc_ratio: seg.vc_ratio,
+        los,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> RampDesignSpec {
+        RampDesignSpec {
+            template: RampSegment::default(),
+            target_los: LevelOfService::C,
+            target_vc: None,
+            versions: vec![HcmVersion::V7, HcmVersion::V7_1],
+            ramp_lanes: Vec::new(),
+            min_length: 100.0,
+            max_length: 1500.0,
+            length_step: 50.0,
+        }
+    }
+
+    #[test]
+    fn the_lane_design_is_the_first_point_of_the_curve_meeting_the_target() {
+        let r = spec().search().unwrap();
+        assert_eq!(r.candidates.len(), 2);
+        for c in &r.candidates {
+            assert_eq!(c.curve.len(), 29);
+            assert_eq!(c.curve.last().unwrap().length, 1500.0);
+            // A longer lane never makes the junction worse.
+            for w in c.curve.windows(2) {
+                assert!(w[1].density <= w[0].density + 1e-9);
+            }
+        }
+
+        let v7 = &r.candidates[0];
+        let d = v7.design.expect("the 7th Edition reaches LOS C within 1,500 ft");
+        assert_eq!(d.los, LevelOfService::C);
+        for p in v7.curve.iter().take_while(|p| p.length < d.length) {
+            assert!(p.los > LevelOfService::C);
+        }
+
+        // Edition 7.1's impedance flattens out with length, leaving the same junction at LOS D.
+        let v7_1 = &r.candidates[1];
+        assert!(v7_1.design.is_none());
+        assert_eq!(v7_1.curve.last().unwrap().los, LevelOfService::D);
+        assert_eq!(r.feasible().count(), 1);
+    }
+
+    #[test]
+    fn a_second_lane_shortens_the_seventh_edition_design() {
+        let template = RampSegment { accel_lane_length2: Some(400.0), ..RampSegment::default() };
+        let r = RampDesignSpec {
+            template,
+            versions: vec![HcmVersion::V7],
+            ramp_lanes: vec![RampLanes::OneLane, RampLanes::TwoLane],
+            ..spec()
+        }
+        .search()
+        .unwrap();
+        let (one, two) = (&r.candidates[0], &r.candidates[1]);
+        assert_eq!(one.second_lane_length, None);
+        assert_eq!(two.second_lane_length, Some(400.0));
+        let p = two.curve[0];
+        assert_eq!(p.effective_length, 2.0 * p.length + 400.0);
+        assert!(two.design.unwrap().length < one.design.unwrap().length);
+    }
+
+    #[test]
+    fn an_unreachable_vc_target_and_bad_ramp_inputs() {
+        let r = RampDesignSpec { target_vc: Some(0.1), ..spec() }.search().unwrap();
+        assert!(r.feasible().next().is_none());
+        assert!(r.candidates.iter().all(|c| !c.curve.is_empty()));
+
+        assert!(RampDesignSpec { target_los: LevelOfService::F, ..spec() }.search().is_err());
+        assert!(RampDesignSpec { max_length: 50.0, ..spec() }.search().is_err());
+        assert!(RampDesignSpec { ramp_lanes: vec![RampLanes::TwoLane], ..spec() }
+            .search()
+            .is_err());
+        let major = RampSegment { ramp_type: RampType::MajorMerge, ..RampSegment::default() };
+        assert!(RampDesignSpec { template: major.clone(), ..spec() }.search().is_err());
+        // A junction with no LOS is an error, not a LOS F point on the curve.
+        assert!(evaluate(&major, 500.0).unwrap_err().contains("no LOS"));
+    }
+}
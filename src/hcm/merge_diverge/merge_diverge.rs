@@ -147,20 +147,21 @@ pub fn get_ramp_capacity(ramp_ffs: f64, two_lane: bool) -> f64 {
 // =============================================================================
 
 /// P_FM for two-lane on-ramps (Chapter 14, Special Cases):
-/// 4-lane: 1.000; 6-lane: 0.555; 8-lane: 0.209.
+/// 4-lane: 1.000; 6-lane: 0.555; 8-lane: 0.209. A one-lane mainline (a
+/// managed lane) carries all of its flow in Lanes 1 and 2, as a 4-lane one does.
 pub fn pfm_two_lane_onramp(freeway_lanes: u32) -> f64 {
     match freeway_lanes {
-        2 => 1.000,
+        1 | 2 => 1.000,
         3 => 0.555,
         _ => 0.209,
     }
 }
 
 /// P_FD for two-lane off-ramps (Chapter 14, Special Cases):
-/// 4-lane: 1.000; 6-lane: 0.450; 8-lane: 0.260.
+/// 4-lane: 1.000; 6-lane: 0.450; 8-lane: 0.260. One lane as for 4-lane.
 pub fn pfd_two_lane_offramp(freeway_lanes: u32) -> f64 {
     match freeway_lanes {
-        2 => 1.000,
+        1 | 2 => 1.000,
         3 => 0.450,
         _ => 0.260,
     }
@@ -481,7 +482,7 @@ impl RampSegment {
 
     /// Acceleration lane length used in the equations (ft): the effective
     /// two-lane value (Eq. 14-25) when a second acceleration lane is given.
-    fn effective_la(&self) -> f64 {
+    pub(crate) fn effective_la(&self) -> f64 {
         let l_a1 = self.accel_lane_length.unwrap_or(800.0);
         match (self.ramp_lanes, self.accel_lane_length2) {
             (RampLanes::TwoLane, Some(l_a2)) => effective_accel_length(l_a1, l_a2),
@@ -491,7 +492,7 @@ impl RampSegment {
 
     /// Deceleration lane length used in the equations (ft): the effective
     /// two-lane value (Eq. 14-26) when a second deceleration lane is given.
-    fn effective_ld(&self) -> f64 {
+    pub(crate) fn effective_ld(&self) -> f64 {
         let l_d1 = self.decel_lane_length.unwrap_or(400.0);
         match (self.ramp_lanes, self.decel_lane_length2) {
             (RampLanes::TwoLane, Some(l_d2)) => effective_decel_length(l_d1, l_d2),
@@ -545,7 +546,9 @@ impl RampSegment {
         let lanes = self.freeway_lanes.min(4);
 
         match lanes {
-            2 => 1.0, // 4-lane freeway: all vehicles in Lanes 1 and 2
+            // 4-lane freeway: all vehicles in Lanes 1 and 2. A one-lane managed
+            // lane has nowhere else to carry them either.
+            1 | 2 => 1.0,
             3 => {
                 // Equation 14-3 (base case): P_FM = 0.5775 + 0.000028 L_A
                 let pfm_base = 0.5775 + 0.000028 * l_a;
@@ -626,7 +629,8 @@ impl RampSegment {
         let lanes = self.freeway_lanes.min(4);
 
         match lanes {
-            2 => 1.0,
+            // As for P_FM: a one-lane managed lane is handled as a 4-lane freeway.
+            1 | 2 => 1.0,
             3 => {
                 // Equation 14-9 (base case):
                 // P_FD = 0.760 - 0.000025 v_F - 0.000046 v_R
@@ -1150,6 +1154,25 @@ mod tests {
         assert_eq!(pfd_two_lane_offramp(2), 1.000);
         assert_eq!(pfd_two_lane_offramp(3), 0.450);
         assert_eq!(pfd_two_lane_offramp(4), 0.260);
+        assert_eq!(pfm_two_lane_onramp(1), 1.000);
+        assert_eq!(pfd_two_lane_offramp(1), 1.000);
+    }
+
+    #[test]
+    fn test_one_lane_mainline_keeps_all_flow_in_lanes_1_and_2() {
+        // A one-lane managed lane previously fell through to the 8-lane P_FM/P_FD forms.
+        for ramp_type in [RampType::OnRamp, RampType::OffRamp] {
+            let mut seg = RampSegment {
+                ramp_type,
+                freeway_lanes: 1,
+                freeway_demand: 1200.0,
+                ramp_demand: 300.0,
+                ..Default::default()
+            };
+            seg.run_analysis();
+            assert_eq!(seg.p_f, Some(1.0));
+            assert_eq!(seg.get_capacity_freeway(), 2400.0);
+        }
     }
 
     #[test]
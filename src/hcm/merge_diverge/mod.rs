@@ -3,8 +3,10 @@
 //! Two editions of this chapter are implemented. [`merge_diverge`] holds the 7th Edition
 //! methodology and [`v7_1`] the Edition 7.1 replacement chapter (November 2025, NCHRP Research
 //! Report 1038). A [`RampSegment`](merge_diverge::RampSegment) carries the edition it should be
-//! analyzed under; see [`crate::hcm::common::HcmVersion`].
+//! analyzed under; see [`crate::hcm::common::HcmVersion`]. [`design`] searches acceleration and
+//! deceleration lane lengths for a target LOS under either edition.
 
+pub mod design;
 pub mod merge_diverge;
 pub mod v7_1;
 
@@ -0,0 +1,415 @@
+//! Design analysis for roundabout entries: the lane configuration of each entry that minimizes
+//! approach delay or balances lane v/c, from the lane counts, lane assignments and bypass lane
+//! types allowed.
+//!
+//! Step 4 takes the lane assignment as an input. This module tries every allowed configuration
+//! on every entry, runs the full Chapter 22 procedure for each (Exhibits 22-14/22-15 lane flows,
+//! Equations 22-1 through 22-7 capacities, pedestrian impedance, Equation 22-17 delay), and keeps
+//! the results of every candidate.
+//!
+//! Circulating and exiting flows (Equations 22-11 and 22-12) depend on the demand only, not on
+//! how an entry's lanes are assigned, so each entry is designed on its own. Approach flows are
+//! fixed by demand as well, so the entries that each minimize their approach delay together
+//! minimize the Equation 22-19 intersection delay. The circulating and exiting lane counts are
+//! geometry and are held at the template's.
+//!
+//! A candidate is feasible when no entry or yielding bypass lane has v/c above `max_vc`. A
+//! nonyielding bypass carries no delay and no v/c (the Chapter 33 Example Problem 1 treatment),
+//! so where it is allowed it is always the lowest-delay home for right turns. Candidates are
+//! listed with fewer lanes first and in the order of the allowed lists, and a tie keeps the
+//! earlier candidate.
+
+use serde::{Deserialize, Serialize};
+
+use super::multileg::MultiLegRoundabout;
+use super::roundabouts::{
+    de_facto_lane_assignment, BypassType, LaneAssignment, RoundaboutLaneResult,
+};
+
+fn all_lane_assignments() -> Vec<LaneAssignment> {
+    vec![
+        LaneAssignment::LeftAndThroughRight,
+        LaneAssignment::LeftThroughAndRight,
+        LaneAssignment::LeftThroughAndThroughRight,
+        LaneAssignment::LeftAndAllMovements,
+        LaneAssignment::AllMovementsAndRight,
+    ]
+}
+
+fn all_bypass_types() -> Vec<BypassType> {
+    vec![BypassType::None, BypassType::Yielding, BypassType::NonYielding]
+}
+
+fn default_max_vc() -> f64 {
+    1.0
+}
+
+/// How to rank the feasible configurations of an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LaneUseObjective {
+    /// Lowest approach control delay (Equation 22-18), then lowest highest lane v/c.
+    #[default]
+    MinDelay,
+    /// Lowest highest lane v/c, then lowest approach control delay.
+    BalanceVc,
+}
+
+/// What to search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LaneUseDesignSpec {
+    /// The roundabout with its demand, circulating and exiting lanes, heavy vehicles and
+    /// pedestrians. Each entry's lane count, lane assignment and bypass type are replaced by the
+    /// search. A four-leg `Roundabouts` comes in through `Roundabouts::to_multileg`.
+    pub template: MultiLegRoundabout,
+    #[serde(default)]
+    pub objective: LaneUseObjective,
+    /// Entry lane counts to try (1 and/or 2). Empty keeps each entry's lane count from the
+    /// template, so only the lane assignment and bypass lane are designed.
+    #[serde(default)]
+    pub entry_lanes: Vec<u32>,
+    /// Lane assignments to try on a two-lane entry (Exhibit 22-14).
+    #[serde(default = "all_lane_assignments")]
+    pub lane_assignments: Vec<LaneAssignment>,
+    /// Right-turn bypass lane types to try (Exhibit 22-7).
+    #[serde(default = "all_bypass_types")]
+    pub bypass_types: Vec<BypassType>,
+    /// Highest acceptable lane v/c.
+    #[serde(default = "default_max_vc")]
+    pub max_vc: f64,
+}
+
+/// One configuration of one entry and its Chapter 22 results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaneUseCandidate {
+    pub entry_lanes: u32,
+    /// Designated assignment. None on a one-lane entry.
+    pub lane_assignment: Option<LaneAssignment>,
+    /// Assignment the entry operates under after the Exhibit 22-14 de facto lane checks.
+    pub operating_assignment: Option<LaneAssignment>,
+    pub bypass: BypassType,
+    /// Entry lanes, left to right.
+    pub lanes: Vec<RoundaboutLaneResult>,
+    pub bypass_lane: Option<RoundaboutLaneResult>,
+    /// Approach control delay, s/veh (Equation 22-18). 0 without demand.
+    pub approach_delay: f64,
+    /// Approach LOS (Exhibit 22-8). None without demand.
+    pub los: Option<char>,
+    /// Highest v/c of the entry lanes and bypass lane.
+    pub max_vc: f64,
+    /// Whether `max_vc` is within the spec's `max_vc`.
+    pub feasible: bool,
+}
+
+/// Every candidate of one entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryLaneUseDesign {
+    /// Leg index in circulating order.
+    pub leg: usize,
+    pub name: String,
+    /// Conflicting circulating flow, pc/h (Equation 22-11).
+    pub circulating_flow_pce: f64,
+    pub candidates: Vec<LaneUseCandidate>,
+    /// Index into `candidates` of the best feasible configuration. None when none is feasible.
+    pub best: Option<usize>,
+}
+
+impl EntryLaneUseDesign {
+    /// The best feasible configuration.
+    pub fn best_candidate(&self) -> Option<&LaneUseCandidate> {
+        self.best.map(|i| &self.candidates[i])
+    }
+}
+
+/// Every entry's candidates, and the roundabout built from the best of each.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaneUseDesignResult {
+    pub objective: LaneUseObjective,
+    pub max_vc: f64,
+    pub entries: Vec<EntryLaneUseDesign>,
+    /// The template with every entry in its best configuration, analyzed. None when an entry
+    /// has no feasible configuration.
+    pub design: Option<MultiLegRoundabout>,
+}
+
+impl LaneUseDesignSpec {
+    /// Run the search.
+    pub fn search(&self) -> Result<LaneUseDesignResult, String> {
+        self.validate()?;
+        let entries: Vec<EntryLaneUseDesign> =
+            (0..self.template.n_legs()).map(|i| self.entry(i)).collect();
+
+        let design = if entries.iter().all(|e| e.best.is_some()) {
+            let mut m = self.template.clone();
+            for e in &entries {
+                let c = e.best_candidate().expect("every entry has a best candidate");
+                configure(&mut m, e.leg, c.entry_lanes, c.lane_assignment, c.bypass);
+            }
+            m.analyze()?;
+            Some(m)
+        } else {
+            None
+        };
+
+        Ok(LaneUseDesignResult { objective: self.objective, max_vc: self.max_vc, entries, design })
+    }
+
+    /// Entry lane counts to try on leg `i`, fewest first.
+    fn lane_counts(&self, i: usize) -> Vec<u32> {
+        if self.entry_lanes.is_empty() {
+            return vec![self.template.legs[i].approach.entry_lanes];
+        }
+        let mut lane_counts = self.entry_lanes.clone();
+        lane_counts.sort_unstable();
+        lane_counts.dedup();
+        lane_counts
+    }
+
+    /// The configurations to try on leg `i`, fewer lanes first.
+    fn configurations(&self, i: usize) -> Vec<(u32, Option<LaneAssignment>, BypassType)> {
+        let lane_counts = self.lane_counts(i);
+        let mut configs = Vec::new();
+        for lanes in lane_counts {
+            for &bypass in &self.bypass_types {
+                if lanes == 1 {
+                    configs.push((1, None, bypass));
+                } else {
+                    for &assignment in &self.lane_assignments {
+                        configs.push((2, Some(assignment), bypass));
+                    }
+                }
+            }
+        }
+        configs
+    }
+
+    fn entry(&self, i: usize) -> EntryLaneUseDesign {
+        let mut candidates = Vec::new();
+        let mut circulating_flow_pce = 0.0;
+        for (lanes, assignment, bypass) in self.configurations(i) {
+            let mut m = self.template.clone();
+            configure(&mut m, i, lanes, assignment, bypass);
+            // The template is validated, and a configuration keeps it valid.
+            m.analyze().expect("validated template");
+            let a = &m.legs[i].approach;
+            circulating_flow_pce = a.circulating_flow_pce.unwrap_or(0.0);
+            let max_vc = a
+                .lanes
+                .iter()
+                .chain(&a.bypass_lane)
+                .map(|l| l.v_c_ratio)
+                .fold(0.0, f64::max);
+            candidates.push(LaneUseCandidate {
+                entry_lanes: lanes,
+                lane_assignment: assignment,
+                operating_assignment: de_facto_lane_assignment(a),
+                bypass,
+                lanes: a.lanes.clone(),
+                bypass_lane: a.bypass_lane.clone(),
+                approach_delay: a.control_delay.unwrap_or(0.0),
+                los: a.los,
+                max_vc,
+                feasible: max_vc <= self.max_vc,
+            });
+        }
+
+        let key = |c: &LaneUseCandidate| match self.objective {
+            LaneUseObjective::MinDelay => (c.approach_delay, c.max_vc),
+            LaneUseObjective::BalanceVc => (c.max_vc, c.approach_delay),
+        };
+        let mut best: Option<usize> = None;
+        for (k, c) in candidates.iter().enumerate() {
+            if !c.feasible {
+                continue;
+            }
+            // Strictly better by more than rounding, so a tie keeps the simpler candidate.
+            let better = best.is_none_or(|b| {
+                let (p0, p1) = key(c);
+                let (q0, q1) = key(&candidates[b]);
+                p0 < q0 - 1e-9 || (p0 <= q0 + 1e-9 && p1 < q1 - 1e-9)
+            });
+            if better {
+                best = Some(k);
+            }
+        }
+
+        EntryLaneUseDesign {
+            leg: i,
+            name: self.template.legs[i].name.clone(),
+            circulating_flow_pce,
+            candidates,
+            best,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.template.validate()?;
+        if let Some(n) = self.entry_lanes.iter().find(|n| !(1..=2).contains(*n)) {
+            return Err(format!("entry lane counts must be 1 or 2, got {n}"));
+        }
+        let two_lane = (0..self.template.n_legs()).any(|i| self.lane_counts(i).contains(&2));
+        if two_lane && self.lane_assignments.is_empty() {
+            return Err("a two-lane entry needs at least one lane assignment".to_string());
+        }
+        if self.bypass_types.is_empty() {
+            return Err("at least one bypass type is needed (\"None\" for no bypass)".to_string());
+        }
+        if self.max_vc.is_nan() || self.max_vc <= 0.0 {
+            return Err(format!("max_vc must be positive, got {}", self.max_vc));
+        }
+        Ok(())
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
+/// Set one entry's lane count, assignment and bypass type.
+fn configure(
+    m: &mut MultiLegRoundabout,
+    leg: usize,
+    lanes: u32,
+    assignment: Option<LaneAssignment>,
+    bypass: BypassType,
+) {
+    let a = &mut m.legs[leg].approach;
+    a.entry_lanes = lanes;
+    if let Some(assignment) = assignment {
+        a.lane_assignment = assignment;
+    }
+    a.bypass = bypass;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hcm::roundabouts::multileg::RoundaboutLeg;
+    use crate::hcm::roundabouts::roundabouts::RoundaboutApproach;
+
+    /// Three single-lane legs with a heavy right turn from leg 0 and a heavy left turn from
+    /// leg 1.
+    fn template() -> MultiLegRoundabout {
+        let legs = (0..3)
+            .map(|i| RoundaboutLeg::new(&format!("leg{i}"), RoundaboutApproach::default()))
+            .collect();
+        let demand = vec![
+            vec![0.0, 700.0, 150.0],
+            vec![100.0, 0.0, 500.0],
+            vec![200.0, 250.0, 0.0],
+        ];
+        MultiLegRoundabout::new(legs, demand)
+    }
+
+    fn spec() -> LaneUseDesignSpec {
+        LaneUseDesignSpec {
+            template: template(),
+            objective: LaneUseObjective::MinDelay,
+            entry_lanes: vec![1, 2],
+            lane_assignments: all_lane_assignments(),
+            bypass_types: all_bypass_types(),
+            max_vc: 1.0,
+        }
+    }
+
+    #[test]
+    fn every_configuration_is_tried_and_the_best_has_the_least_delay() {
+        let r = spec().search().unwrap();
+        assert_eq!(r.entries.len(), 3);
+        for e in &r.entries {
+            // 3 one-lane and 15 two-lane configurations
+            assert_eq!(e.candidates.len(), 18);
+            let best = e.best_candidate().unwrap();
+            for c in e.candidates.iter().filter(|c| c.feasible) {
+                assert!(best.approach_delay <= c.approach_delay + 1e-9);
+            }
+        }
+        // With a nonyielding bypass allowed, leg 0's right turns take it.
+        assert_eq!(r.entries[0].best_candidate().unwrap().bypass, BypassType::NonYielding);
+
+        // The designed roundabout carries each entry's best configuration.
+        let design = r.design.as_ref().unwrap();
+        for e in &r.entries {
+            let best = e.best_candidate().unwrap();
+            let a = &design.legs[e.leg].approach;
+            assert_eq!(a.entry_lanes, best.entry_lanes);
+            assert_eq!(a.bypass, best.bypass);
+            assert!((a.control_delay.unwrap_or(0.0) - best.approach_delay).abs() < 1e-9);
+        }
+        // No single-lane, no-bypass template does better.
+        let mut single = template();
+        single.analyze().unwrap();
+        assert!(design.intersection_delay.unwrap() <= single.intersection_delay.unwrap());
+    }
+
+    #[test]
+    fn balancing_lane_vc_minimizes_the_highest_lane() {
+        let r = LaneUseDesignSpec {
+            objective: LaneUseObjective::BalanceVc,
+            entry_lanes: vec![2],
+            bypass_types: vec![BypassType::None],
+            ..spec()
+        }
+        .search()
+        .unwrap();
+        let e = &r.entries[1];
+        let best = e.best_candidate().unwrap();
+        for c in &e.candidates {
+            assert!(best.max_vc <= c.max_vc + 1e-9);
+        }
+        // Leg 1 is mostly left turns (500 of 600), so a shared left lane beats a left-only one.
+        assert_ne!(best.operating_assignment, Some(LaneAssignment::LeftAndThroughRight));
+    }
+
+    #[test]
+    fn ties_keep_the_simpler_configuration() {
+        // Leg 2 has no right turns into a bypass worth having: every bypass type ties with none,
+        // and the first listed (no bypass) is kept.
+        let mut t = template();
+        t.demand[2] = vec![0.0, 200.0, 0.0];
+        let r = LaneUseDesignSpec { template: t, entry_lanes: vec![1], ..spec() }.search().unwrap();
+        let best = r.entries[2].best_candidate().unwrap();
+        assert_eq!(best.bypass, BypassType::None);
+        assert_eq!(best.lane_assignment, None);
+    }
+
+    #[test]
+    fn no_entry_lane_counts_keeps_the_template_lanes() {
+        let mut t = template();
+        t.legs[1].approach.entry_lanes = 2;
+        let r = LaneUseDesignSpec { template: t, entry_lanes: Vec::new(), ..spec() }
+            .search()
+            .unwrap();
+        assert_eq!(r.entries[0].candidates.len(), 3);
+        assert_eq!(r.entries[1].candidates.len(), 15);
+        assert!(r.entries[1].candidates.iter().all(|c| c.entry_lanes == 2));
+        // One-lane entries need no lane assignment to try.
+        let r = LaneUseDesignSpec {
+            entry_lanes: Vec::new(),
+            lane_assignments: Vec::new(),
+            ..spec()
+        };
+        assert!(r.search().is_ok());
+    }
+
+    #[test]
+    fn infeasible_entries_and_bad_inputs() {
+        let mut t = template();
+        t.demand[0][1] = 3000.0;
+        let r = LaneUseDesignSpec { template: t, bypass_types: vec![BypassType::None], ..spec() }
+            .search()
+            .unwrap();
+        assert!(r.entries[0].best.is_none());
+        assert!(r.entries[0].candidates.iter().all(|c| !c.feasible));
+        assert!(r.design.is_none());
+
+        assert!(LaneUseDesignSpec { entry_lanes: vec![3], ..spec() }.search().is_err());
+        assert!(LaneUseDesignSpec { lane_assignments: vec![], ..spec() }.search().is_err());
+        assert!(LaneUseDesignSpec { bypass_types: vec![], ..spec() }.search().is_err());
+        assert!(LaneUseDesignSpec { max_vc: 0.0, ..spec() }.search().is_err());
+        let mut t = template();
+        t.demand.pop();
+        assert!(LaneUseDesignSpec { template: t, ..spec() }.search().is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! HCM Chapter 22: Roundabouts.
 
+pub mod design;
+pub mod multileg;
 pub mod roundabouts;
 
+pub use multileg::*;
 pub use roundabouts::*;
 
 pub const CHAPTER: u8 = 22;
@@ -0,0 +1,413 @@
+//! # N-leg roundabouts (HCM Chapter 22)
+//!
+//! Generalizes the four-leg [`Roundabouts`] model to any number of legs
+//! (three or more). Legs are listed in the order circulating traffic passes
+//! them (counterclockwise for right-hand traffic), and demand is an
+//! origin-destination matrix rather than per-entry U/L/T/R volumes.
+//!
+//! ## Conflicting flows
+//!
+//! A vehicle from entry `o` to exit `d` travels `k_d = (d - o) mod N` legs
+//! around the circle (`k_d = N` for a U-turn). It passes in front of the
+//! entry `k_i = (i - o) mod N` legs downstream when `1 <= k_i < k_d`, so
+//!
+//! ```text
+//! v_c,i,pce  = Sum over (o, d) with 1 <= k_i < k_d of v_od,pce        (generalized Equation 22-11)
+//! v_ex,j,pce = Sum over o of v_oj,pce                                 (generalized Equation 22-12)
+//! ```
+//!
+//! With four legs these reduce term by term to Equations 22-11 and 22-12.
+//! [`Roundabouts::to_multileg`] builds the equivalent N-leg model from a
+//! four-leg one, and both give identical results.
+//!
+//! ## Movement classes
+//!
+//! Entry lane assignment (Exhibits 22-14 and 22-15) needs U/L/T/R flows.
+//! The first exit after the entry is the right turn, the last exit before
+//! the entry's own leg is the left turn, the entry's own leg is the U-turn,
+//! and every exit in between is through traffic. A three-leg roundabout has
+//! no through movement; a five- or six-leg roundabout has two or three
+//! through exits.
+//!
+//! VERIFY-HCM: no Chapter 33 example problem has other than four legs, so
+//! the generalized flows and the unchanged capacity models are checked
+//! against the four-leg examples only.
+
+use serde::{Deserialize, Serialize};
+
+use super::roundabouts::{
+    aggregate_approach_delay, evaluate_entry, BypassType, Leg, RoundaboutApproach, Roundabouts,
+};
+use crate::hcm::common::delay::aggregate_control_delay;
+use crate::hcm::common::los_tables::los_unsignalized;
+
+/// Turning movement class of an origin-destination pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Movement {
+    U,
+    L,
+    T,
+    R,
+}
+
+impl Movement {
+    /// Movement class of a trip from leg `origin` to leg `destination` on a
+    /// roundabout with `n_legs` legs (see the module docs).
+    pub fn classify(n_legs: usize, origin: usize, destination: usize) -> Movement {
+        match (destination + n_legs - origin) % n_legs {
+            0 => Movement::U,
+            1 => Movement::R,
+            k if k == n_legs - 1 => Movement::L,
+            _ => Movement::T,
+        }
+    }
+
+    /// Position in the `[U, L, T, R]` order of
+    /// [`RoundaboutApproach::flows_pce`].
+    pub fn index(self) -> usize {
+        match self {
+            Movement::U => 0,
+            Movement::L => 1,
+            Movement::T => 2,
+            Movement::R => 3,
+        }
+    }
+}
+
+/// One leg of an N-leg roundabout: its entry geometry, heavy vehicles and
+/// pedestrians, plus the computed entry results. The movement volumes
+/// `v_u`/`v_l`/`v_t`/`v_r` are not used; demand comes from
+/// [`MultiLegRoundabout::demand`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoundaboutLeg {
+    /// Leg label (e.g., "North" or "Main St").
+    #[serde(default)]
+    pub name: String,
+    /// Entry inputs and results. `exiting_lanes` is the number of exit
+    /// lanes on this leg (Exhibit 22-17, for the upstream leg's bypass).
+    #[serde(flatten)]
+    pub approach: RoundaboutApproach,
+}
+
+impl RoundaboutLeg {
+    /// Create a named leg from its entry inputs.
+    pub fn new(name: &str, approach: RoundaboutApproach) -> Self {
+        RoundaboutLeg {
+            name: name.to_string(),
+            approach,
+        }
+    }
+}
+
+/// HCM Chapter 22 analysis of a roundabout with any number of legs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiLegRoundabout {
+    /// Legs in the order circulating traffic passes them.
+    pub legs: Vec<RoundaboutLeg>,
+    /// Demand volumes, veh/h: `demand[o][d]` from the entry on leg `o` to
+    /// the exit on leg `d` (the diagonal holds U-turns).
+    pub demand: Vec<Vec<f64>>,
+    /// Peak hour factor (Equation 22-8). `None` if volumes are flow rates.
+    #[serde(default)]
+    pub phf: Option<f64>,
+    /// Analysis period T, h (0.25 for 15 min).
+    #[serde(default = "default_t")]
+    pub analysis_period_h: f64,
+    /// Optional calibrated (A, B) parameters replacing every entry-lane
+    /// capacity equation (Section 4, Equations 22-21 through 22-23).
+    #[serde(default)]
+    pub calibration: Option<(f64, f64)>,
+
+    // ── Computed ────────────────────────────────────────────────────────────
+    /// O-D flow rates in pc/h (Steps 1–2), same layout as `demand`.
+    #[serde(default)]
+    pub demand_pce: Vec<Vec<f64>>,
+    /// Total exiting flow per leg, pc/h (generalized Equation 22-12,
+    /// bypass traffic included).
+    #[serde(default)]
+    pub exiting_flow_pce: Vec<f64>,
+    /// Intersection control delay, s/veh (Equation 22-19).
+    #[serde(default)]
+    pub intersection_delay: Option<f64>,
+    /// Intersection LOS (Exhibit 22-8).
+    #[serde(default)]
+    pub intersection_los: Option<char>,
+}
+
+fn default_t() -> f64 {
+    0.25
+}
+
+impl MultiLegRoundabout {
+    /// Create an analysis from legs in circulating order and their O-D
+    /// demand matrix (veh/h).
+    pub fn new(legs: Vec<RoundaboutLeg>, demand: Vec<Vec<f64>>) -> Self {
+        MultiLegRoundabout {
+            legs,
+            demand,
+            phf: None,
+            analysis_period_h: 0.25,
+            calibration: None,
+            demand_pce: Vec::new(),
+            exiting_flow_pce: Vec::new(),
+            intersection_delay: None,
+            intersection_los: None,
+        }
+    }
+
+    /// Deserialize from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Serialize inputs + results to JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Number of legs.
+    pub fn n_legs(&self) -> usize {
+        self.legs.len()
+    }
+
+    /// Index of the leg with the given name.
+    pub fn leg_index(&self, name: &str) -> Option<usize> {
+        self.legs.iter().position(|l| l.name == name)
+    }
+
+    /// Check the leg count, demand matrix and entry geometry.
+    pub fn validate(&self) -> Result<(), String> {
+        let n = self.n_legs();
+        if n < 3 {
+            return Err(format!("a roundabout needs at least 3 legs, got {n}"));
+        }
+        if self.demand.len() != n || self.demand.iter().any(|row| row.len() != n) {
+            return Err(format!("demand must be a {n} x {n} origin-destination matrix"));
+        }
+        if self
+            .demand
+            .iter()
+            .flatten()
+            .any(|v| !v.is_finite() || *v < 0.0)
+        {
+            return Err("demand volumes must be finite and non-negative".to_string());
+        }
+        if let Some(phf) = self.phf {
+            if !(phf > 0.0 && phf <= 1.0) {
+                return Err(format!("phf must be in (0, 1], got {phf}"));
+            }
+        }
+        if self.analysis_period_h.is_nan() || self.analysis_period_h <= 0.0 {
+            return Err("analysis_period_h must be positive".to_string());
+        }
+        for (i, leg) in self.legs.iter().enumerate() {
+            let a = &leg.approach;
+            let label = if leg.name.is_empty() {
+                format!("leg {i}")
+            } else {
+                format!("leg {i} ({})", leg.name)
+            };
+            if a.v_u != 0.0 || a.v_l != 0.0 || a.v_t != 0.0 || a.v_r != 0.0 {
+                return Err(format!(
+                    "{label}: movement volumes come from the demand matrix, not v_u/v_l/v_t/v_r"
+                ));
+            }
+            for (field, lanes) in [
+                ("entry_lanes", a.entry_lanes),
+                ("circulating_lanes", a.circulating_lanes),
+                ("exiting_lanes", a.exiting_lanes),
+            ] {
+                if !(1..=2).contains(&lanes) {
+                    return Err(format!("{label}: {field} must be 1 or 2, got {lanes}"));
+                }
+            }
+            if !(0.0..=100.0).contains(&a.heavy_vehicle_pct) {
+                return Err(format!("{label}: heavy_vehicle_pct must be in [0, 100]"));
+            }
+            if a.n_ped.is_nan() || a.n_ped < 0.0 {
+                return Err(format!("{label}: n_ped must be non-negative"));
+            }
+        }
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Steps 1–2: flow rates in pc/h
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// HCM Equations 22-8 through 22-10 applied to every O-D cell, using
+    /// the origin leg's heavy-vehicle percentage. Each entry's `flows_pce`
+    /// receives its O-D flows summed by movement class.
+    pub fn step1_2_flow_rates_pce(&mut self) {
+        let phf = match self.phf {
+            Some(p) if p > 0.0 => p,
+            _ => 1.0,
+        };
+        let n = self.n_legs();
+        self.demand_pce = self
+            .demand
+            .iter()
+            .zip(&self.legs)
+            .map(|(row, leg)| {
+                let f_hv = Roundabouts::heavy_vehicle_factor(leg.approach.heavy_vehicle_pct);
+                row.iter().map(|v| v / phf / f_hv).collect()
+            })
+            .collect();
+        for o in 0..n {
+            let mut flows = [0.0; 4];
+            for d in 0..n {
+                flows[Movement::classify(n, o, d).index()] += self.demand_pce[o][d];
+            }
+            self.legs[o].approach.flows_pce = Some(flows);
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Step 3: circulating and exiting flows
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Generalized HCM Equation 22-11: conflicting circulating flow in
+    /// front of the entry on leg `i`, pc/h.
+    pub fn circulating_flow_pce(&self, i: usize) -> f64 {
+        let n = self.n_legs();
+        let mut v_c = 0.0;
+        for (o, row) in self.demand_pce.iter().enumerate() {
+            let k_i = (i + n - o) % n;
+            if k_i == 0 {
+                continue;
+            }
+            for (d, v) in row.iter().enumerate() {
+                let k_d = match (d + n - o) % n {
+                    0 => n,
+                    k => k,
+                };
+                if k_i < k_d {
+                    v_c += v;
+                }
+            }
+        }
+        v_c
+    }
+
+    /// Generalized HCM Equation 22-12: total flow leaving the roundabout on
+    /// leg `j`, pc/h, including right turns that use a bypass lane.
+    pub fn exiting_flow_on_leg_pce(&self, j: usize) -> f64 {
+        self.demand_pce.iter().map(|row| row[j]).sum()
+    }
+
+    /// HCM Equation 22-12: conflicting exiting flow for the bypass lane of
+    /// the entry on leg `i`, which merges into the next leg downstream.
+    /// Right turns using that bypass are excluded.
+    pub fn bypass_conflicting_exit_flow_pce(&self, i: usize) -> f64 {
+        let j = (i + 1) % self.n_legs();
+        let r_bypass = if self.legs[i].approach.bypass != BypassType::None {
+            self.demand_pce[i][j]
+        } else {
+            0.0
+        };
+        self.exiting_flow_on_leg_pce(j) - r_bypass
+    }
+
+    /// HCM Chapter 22, Step 3: populate circulating (and bypass exiting)
+    /// flows for every entry and the exiting flow of every leg.
+    pub fn step3_conflicting_flows(&mut self) {
+        let n = self.n_legs();
+        self.exiting_flow_pce = (0..n).map(|j| self.exiting_flow_on_leg_pce(j)).collect();
+        for i in 0..n {
+            let vc = self.circulating_flow_pce(i);
+            let vex = if self.legs[i].approach.bypass != BypassType::None {
+                Some(self.bypass_conflicting_exit_flow_pce(i))
+            } else {
+                None
+            };
+            let a = &mut self.legs[i].approach;
+            a.circulating_flow_pce = Some(vc);
+            a.bypass_conflicting_flow_pce = vex;
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Steps 4–12
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// HCM Chapter 22, Steps 4 through 10 and 12 for every entry.
+    pub fn step4_12_lane_performance(&mut self) {
+        let n = self.n_legs();
+        let t = self.analysis_period_h;
+        let calibration = self.calibration;
+        for i in 0..n {
+            // Exhibit 22-17: exit lanes of the leg the bypass merges into
+            let exit_lanes = self.legs[(i + 1) % n].approach.exiting_lanes;
+            let a = &mut self.legs[i].approach;
+            let (lanes, bypass_lane) = evaluate_entry(a, calibration, exit_lanes, t);
+            a.lanes = lanes;
+            a.bypass_lane = bypass_lane;
+        }
+    }
+
+    /// HCM Chapter 22, Step 11 (Equations 22-18 and 22-19): approach and
+    /// intersection control delay and LOS.
+    pub fn step11_aggregate_delay(&mut self) -> f64 {
+        let pairs: Vec<(f64, f64)> = self
+            .legs
+            .iter_mut()
+            .filter_map(|leg| aggregate_approach_delay(&mut leg.approach))
+            .collect();
+        let d_i = aggregate_control_delay(&pairs);
+        self.intersection_delay = Some(d_i);
+        self.intersection_los = Some(los_unsignalized(d_i, false).into());
+        d_i
+    }
+
+    /// Validate the inputs and run the complete Chapter 22 procedure
+    /// (Steps 1–12).
+    pub fn analyze(&mut self) -> Result<(), String> {
+        self.validate()?;
+        self.step1_2_flow_rates_pce();
+        self.step3_conflicting_flows();
+        self.step4_12_lane_performance();
+        self.step11_aggregate_delay();
+        Ok(())
+    }
+}
+
+/// Four-leg entries in circulating order: the south leg (NB entry), then
+/// east (WB), north (SB) and west (EB).
+const CIRCULATING_ORDER: [Leg; 4] = [Leg::NB, Leg::WB, Leg::SB, Leg::EB];
+
+impl Roundabouts {
+    /// The equivalent N-leg model (legs named "NB", "WB", "SB", "EB" in
+    /// circulating order), with each entry's U/L/T/R volumes moved into the
+    /// O-D matrix. Computed results are not carried over.
+    pub fn to_multileg(&self) -> MultiLegRoundabout {
+        let mut legs = Vec::with_capacity(4);
+        let mut demand = vec![vec![0.0; 4]; 4];
+        for (o, &entry) in CIRCULATING_ORDER.iter().enumerate() {
+            let src = self.approach(entry);
+            for (k, v) in [(0, src.v_u), (1, src.v_r), (2, src.v_t), (3, src.v_l)] {
+                demand[o][(o + k) % 4] = v;
+            }
+            // The four-leg model reads `exiting_lanes` by direction of
+            // exiting travel, so the exit lanes on this entry's leg belong
+            // to the opposite entry.
+            let exiting_lanes = self.approach(entry.opposite()).exiting_lanes;
+            let approach = RoundaboutApproach {
+                heavy_vehicle_pct: src.heavy_vehicle_pct,
+                entry_lanes: src.entry_lanes,
+                circulating_lanes: src.circulating_lanes,
+                exiting_lanes,
+                bypass: src.bypass,
+                lane_assignment: src.lane_assignment,
+                pct_left_lane: src.pct_left_lane,
+                n_ped: src.n_ped,
+                ..Default::default()
+            };
+            legs.push(RoundaboutLeg::new(&format!("{entry:?}"), approach));
+        }
+        let mut m = MultiLegRoundabout::new(legs, demand);
+        m.phf = self.phf;
+        m.analysis_period_h = self.analysis_period_h;
+        m.calibration = self.calibration;
+        m
+    }
+}
@@ -516,85 +516,13 @@ impl Roundabouts {
     ///
     /// Right-turn flow is excluded when a bypass lane is present.
     pub fn entry_lane_flows_pce(&self, leg: Leg) -> (f64, f64) {
-        let a = self.approach(leg);
-        let [u, l, t, r_all] = self.pce(leg);
-        let r_e = if a.bypass != BypassType::None { 0.0 } else { r_all };
-        let ve = u + l + t + r_e;
-        if a.entry_lanes <= 1 {
-            return (0.0, ve);
-        }
-        use LaneAssignment as La;
-        // Exhibit 22-14 de facto lane checks
-        let assignment = match a.lane_assignment {
-            La::LeftThroughAndThroughRight => {
-                if u + l > t + r_e {
-                    La::LeftAndThroughRight // de facto left-turn lane
-                } else if r_e > u + l + t {
-                    La::LeftThroughAndRight // de facto right-turn lane
-                } else {
-                    La::LeftThroughAndThroughRight
-                }
-            }
-            La::LeftAndAllMovements => {
-                if t + r_e > u + l {
-                    La::LeftAndThroughRight // de facto through–right lane
-                } else {
-                    La::LeftAndAllMovements
-                }
-            }
-            La::AllMovementsAndRight => {
-                if u + l + t > r_e {
-                    La::LeftThroughAndRight // de facto left–through lane
-                } else {
-                    La::AllMovementsAndRight
-                }
-            }
-            other => other,
-        };
-        // Exhibit 22-15 volume assignments
-        match assignment {
-            La::LeftAndThroughRight => (u + l, t + r_e),
-            La::LeftThroughAndRight => (u + l + t, r_e),
-            La::LeftThroughAndThroughRight
-            | La::LeftAndAllMovements
-            | La::AllMovementsAndRight => {
-                // Exhibit 22-9 lane-utilization defaults
-                let pct_ll = a.pct_left_lane.unwrap_or(match assignment {
-                    La::LeftAndAllMovements => 0.53,
-                    _ => 0.47,
-                });
-                (pct_ll * ve, (1.0 - pct_ll) * ve)
-            }
-        }
+        entry_lane_flows(self.approach(leg))
     }
 
     // ═══════════════════════════════════════════════════════════════════════
     // Steps 5–12 per approach
     // ═══════════════════════════════════════════════════════════════════════
 
-    /// Entry-lane capacity in pc/h per HCM Exhibit 22-16 (Equations 22-1
-    /// through 22-5), `lane_is_left` selecting Equation 22-5 for the left
-    /// lane of a 2×2 configuration. A calibration `(A, B)` overrides the
-    /// national models (Equation 22-21).
-    fn entry_capacity_pce(&self, leg: Leg, lane_is_left: bool, v_c: f64) -> f64 {
-        if let Some((a, b)) = self.calibration {
-            return capacity_exponential(a, b, v_c);
-        }
-        let ap = self.approach(leg);
-        match (ap.entry_lanes, ap.circulating_lanes) {
-            (1, 1) => capacity_single_lane(v_c),
-            (2, 1) => capacity_two_lane_entry_one_circ(v_c),
-            (1, _) => capacity_one_lane_entry_two_circ(v_c),
-            (_, _) => {
-                if lane_is_left {
-                    capacity_two_lane_entry_two_circ_left(v_c)
-                } else {
-                    capacity_two_lane_entry_two_circ_right(v_c)
-                }
-            }
-        }
-    }
-
     /// HCM Equation 22-20: 95th percentile queue, veh.
     pub fn queue_95(x: f64, c_veh: f64, t_h: f64) -> f64 {
         if c_veh <= 0.0 {
@@ -610,99 +538,12 @@ impl Roundabouts {
     /// control delay, LOS, and queues.
     pub fn step4_12_lane_performance(&mut self) {
         let t = self.analysis_period_h;
+        let calibration = self.calibration;
         for leg in LEGS {
-            let (left_pce, right_pce) = self.entry_lane_flows_pce(leg);
-            let v_c = self.approach(leg).circulating_flow_pce.unwrap_or(0.0);
-            let n_ped = self.approach(leg).n_ped;
-            let entry_lanes = self.approach(leg).entry_lanes;
-            // Step 6: pedestrian impedance (Exhibits 22-18 and 22-20)
-            let f_ped = if entry_lanes >= 2 {
-                ped_factor_two_lane(n_ped, v_c)
-            } else {
-                ped_factor_one_lane(n_ped, v_c)
-            };
-            let f_hv = Self::heavy_vehicle_factor(self.approach(leg).heavy_vehicle_pct);
-
-            let mut lanes = Vec::new();
-            let lane_specs: Vec<(&str, f64, bool)> = if entry_lanes >= 2 {
-                vec![("left", left_pce, true), ("right", right_pce, false)]
-            } else {
-                vec![("entry", right_pce, false)]
-            };
-            for (label, v_pce, is_left) in lane_specs {
-                // Step 5: capacity in pc/h
-                let c_pce = self.entry_capacity_pce(leg, is_left, v_c);
-                // Step 7 (Equations 22-13 and 22-14)
-                let v_veh = v_pce * f_hv;
-                let c_veh = c_pce * f_hv * f_ped;
-                // Step 8 (Equation 22-16)
-                let x = if c_veh > 0.0 { v_veh / c_veh } else { 0.0 };
-                // Step 9 (Equation 22-17)
-                let d = control_delay_roundabout(v_veh, c_veh, t);
-                lanes.push(RoundaboutLaneResult {
-                    label: label.to_string(),
-                    flow_pce: v_pce,
-                    flow_veh: v_veh,
-                    capacity_pce: c_pce,
-                    capacity_veh: c_veh,
-                    v_c_ratio: x,
-                    control_delay: d,
-                    los: los_char(los_unsignalized(d, x > 1.0)),
-                    queue_95: Self::queue_95(x, c_veh, t),
-                });
-            }
-
-            // Bypass lane (Steps 5–12)
-            let bypass_lane = match self.approach(leg).bypass {
-                BypassType::None => None,
-                bypass => {
-                    let [_, _, _, r_pce] = self.pce(leg);
-                    let v_veh = r_pce * f_hv;
-                    match bypass {
-                        BypassType::Yielding => {
-                            let v_ex =
-                                self.approach(leg).bypass_conflicting_flow_pce.unwrap_or(0.0);
-                            // Exhibit 22-17: exit lanes of the leg the
-                            // bypass merges into
-                            let exit_lanes =
-                                self.approach(leg.left_of()).exiting_lanes.max(1);
-                            let c_pce = if exit_lanes >= 2 {
-                                capacity_bypass_two_exit_lanes(v_ex)
-                            } else {
-                                capacity_bypass_one_exit_lane(v_ex)
-                            };
-                            let c_veh = c_pce * f_hv; // pedestrians not modeled
-                            let x = if c_veh > 0.0 { v_veh / c_veh } else { 0.0 };
-                            let d = control_delay_roundabout(v_veh, c_veh, t);
-                            Some(RoundaboutLaneResult {
-                                label: "bypass".to_string(),
-                                flow_pce: r_pce,
-                                flow_veh: v_veh,
-                                capacity_pce: c_pce,
-                                capacity_veh: c_veh,
-                                v_c_ratio: x,
-                                control_delay: d,
-                                los: los_char(los_unsignalized(d, x > 1.0)),
-                                queue_95: Self::queue_95(x, c_veh, t),
-                            })
-                        }
-                        _ => Some(RoundaboutLaneResult {
-                            // Type 2 nonyielding bypass: delay assumed 0
-                            // (HCM Chapter 33 Example Problem 1 treatment)
-                            label: "bypass".to_string(),
-                            flow_pce: r_pce,
-                            flow_veh: v_veh,
-                            capacity_pce: 0.0,
-                            capacity_veh: 0.0,
-                            v_c_ratio: 0.0,
-                            control_delay: 0.0,
-                            los: 'A',
-                            queue_95: 0.0,
-                        }),
-                    }
-                }
-            };
+            // Exhibit 22-17: exit lanes of the leg the bypass merges into
+            let exit_lanes = self.approach(leg.left_of()).exiting_lanes;
             let a = self.approach_mut(leg);
+            let (lanes, bypass_lane) = evaluate_entry(a, calibration, exit_lanes, t);
             a.lanes = lanes;
             a.bypass_lane = bypass_lane;
         }
@@ -711,28 +552,11 @@ impl Roundabouts {
     /// HCM Chapter 22, Step 11 (Equations 22-18 and 22-19): approach and
     /// intersection control delay and LOS.
     pub fn step11_aggregate_delay(&mut self) -> f64 {
-        let mut intersection_pairs = Vec::new();
-        for leg in LEGS {
-            let a = self.approach(leg);
-            let mut pairs: Vec<(f64, f64)> = a
-                .lanes
-                .iter()
-                .map(|l| (l.control_delay, l.flow_veh))
-                .collect();
-            if let Some(b) = &a.bypass_lane {
-                pairs.push((b.control_delay, b.flow_veh));
-            }
-            if pairs.iter().all(|(_, v)| *v <= 0.0) {
-                continue;
-            }
-            let d_a = aggregate_control_delay(&pairs);
-            let v_a: f64 = pairs.iter().map(|(_, v)| v).sum();
-            let a = self.approach_mut(leg);
-            a.control_delay = Some(d_a);
-            a.los = Some(los_char(los_unsignalized(d_a, false)));
-            intersection_pairs.push((d_a, v_a));
-        }
-        let d_i = aggregate_control_delay(&intersection_pairs);
+        let pairs: Vec<(f64, f64)> = LEGS
+            .iter()
+            .filter_map(|&leg| aggregate_approach_delay(self.approach_mut(leg)))
+            .collect();
+        let d_i = aggregate_control_delay(&pairs);
         self.intersection_delay = Some(d_i);
         self.intersection_los = Some(los_char(los_unsignalized(d_i, false)));
         d_i
@@ -747,6 +571,225 @@ impl Roundabouts {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Per-entry evaluation shared by the four-leg and N-leg models
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Entry flows `(U, L, T, R_e)` in pc/h, with the right turns that use a
+/// bypass lane removed.
+fn entry_movement_flows(a: &RoundaboutApproach) -> [f64; 4] {
+    let [u, l, t, r_all] = a.flows_pce.unwrap_or([0.0; 4]);
+    let r_e = if a.bypass != BypassType::None { 0.0 } else { r_all };
+    [u, l, t, r_e]
+}
+
+/// HCM Exhibit 22-14: the lane assignment a two-lane entry operates under
+/// after the de facto lane checks on its `flows_pce`. `None` for a
+/// one-lane entry.
+pub(crate) fn de_facto_lane_assignment(a: &RoundaboutApproach) -> Option<LaneAssignment> {
+    if a.entry_lanes <= 1 {
+        return None;
+    }
+    let [u, l, t, r_e] = entry_movement_flows(a);
+    use LaneAssignment as La;
+    Some(match a.lane_assignment {
+        La::LeftThroughAndThroughRight => {
+            if u + l > t + r_e {
+                La::LeftAndThroughRight // de facto left-turn lane
+            } else if r_e > u + l + t {
+                La::LeftThroughAndRight // de facto right-turn lane
+            } else {
+                La::LeftThroughAndThroughRight
+            }
+        }
+        La::LeftAndAllMovements => {
+            if t + r_e > u + l {
+                La::LeftAndThroughRight // de facto through–right lane
+            } else {
+                La::LeftAndAllMovements
+            }
+        }
+        La::AllMovementsAndRight => {
+            if u + l + t > r_e {
+                La::LeftThroughAndRight // de facto left–through lane
+            } else {
+                La::AllMovementsAndRight
+            }
+        }
+        other => other,
+    })
+}
+
+/// HCM Chapter 22, Step 4 (Exhibits 22-14 and 22-15) on one approach's
+/// `flows_pce`. Returns `(left_lane_pce, right_lane_pce)`; a one-lane
+/// entry places everything in the right lane slot.
+pub(crate) fn entry_lane_flows(a: &RoundaboutApproach) -> (f64, f64) {
+    let [u, l, t, r_e] = entry_movement_flows(a);
+    let ve = u + l + t + r_e;
+    let Some(assignment) = de_facto_lane_assignment(a) else {
+        return (0.0, ve);
+    };
+    use LaneAssignment as La;
+    // Exhibit 22-15 volume assignments
+    match assignment {
+        La::LeftAndThroughRight => (u + l, t + r_e),
+        La::LeftThroughAndRight => (u + l + t, r_e),
+        La::LeftThroughAndThroughRight
+        | La::LeftAndAllMovements
+        | La::AllMovementsAndRight => {
+            // Exhibit 22-9 lane-utilization defaults
+            let pct_ll = a.pct_left_lane.unwrap_or(match assignment {
+                La::LeftAndAllMovements => 0.53,
+                _ => 0.47,
+            });
+            (pct_ll * ve, (1.0 - pct_ll) * ve)
+        }
+    }
+}
+
+/// Entry-lane capacity in pc/h per HCM Exhibit 22-16 (Equations 22-1
+/// through 22-5), `lane_is_left` selecting Equation 22-5 for the left
+/// lane of a 2×2 configuration. A calibration `(A, B)` overrides the
+/// national models (Equation 22-21).
+fn entry_capacity_pce(
+    a: &RoundaboutApproach,
+    calibration: Option<(f64, f64)>,
+    lane_is_left: bool,
+    v_c: f64,
+) -> f64 {
+    if let Some((ca, cb)) = calibration {
+        return capacity_exponential(ca, cb, v_c);
+    }
+    match (a.entry_lanes, a.circulating_lanes) {
+        (1, 1) => capacity_single_lane(v_c),
+        (2, 1) => capacity_two_lane_entry_one_circ(v_c),
+        (1, _) => capacity_one_lane_entry_two_circ(v_c),
+        (_, _) => {
+            if lane_is_left {
+                capacity_two_lane_entry_two_circ_left(v_c)
+            } else {
+                capacity_two_lane_entry_two_circ_right(v_c)
+            }
+        }
+    }
+}
+
+/// HCM Chapter 22, Steps 4 through 10 and 12 for one entry whose
+/// `flows_pce`, `circulating_flow_pce` and `bypass_conflicting_flow_pce`
+/// are populated. `bypass_exit_lanes` is the exiting lane count of the leg
+/// a bypass lane merges into (Exhibit 22-17). Returns the entry-lane
+/// results and the bypass-lane result.
+pub(crate) fn evaluate_entry(
+    a: &RoundaboutApproach,
+    calibration: Option<(f64, f64)>,
+    bypass_exit_lanes: u32,
+    t: f64,
+) -> (Vec<RoundaboutLaneResult>, Option<RoundaboutLaneResult>) {
+    let (left_pce, right_pce) = entry_lane_flows(a);
+    let v_c = a.circulating_flow_pce.unwrap_or(0.0);
+    // Step 6: pedestrian impedance (Exhibits 22-18 and 22-20)
+    let f_ped = if a.entry_lanes >= 2 {
+        ped_factor_two_lane(a.n_ped, v_c)
+    } else {
+        ped_factor_one_lane(a.n_ped, v_c)
+    };
+    let f_hv = Roundabouts::heavy_vehicle_factor(a.heavy_vehicle_pct);
+
+    let mut lanes = Vec::new();
+    let lane_specs: Vec<(&str, f64, bool)> = if a.entry_lanes >= 2 {
+        vec![("left", left_pce, true), ("right", right_pce, false)]
+    } else {
+        vec![("entry", right_pce, false)]
+    };
+    for (label, v_pce, is_left) in lane_specs {
+        // Step 5: capacity in pc/h
+        let c_pce = entry_capacity_pce(a, calibration, is_left, v_c);
+        // Step 7 (Equations 22-13 and 22-14)
+        let v_veh = v_pce * f_hv;
+        let c_veh = c_pce * f_hv * f_ped;
+        // Step 8 (Equation 22-16)
+        let x = if c_veh > 0.0 { v_veh / c_veh } else { 0.0 };
+        // Step 9 (Equation 22-17)
+        let d = control_delay_roundabout(v_veh, c_veh, t);
+        lanes.push(RoundaboutLaneResult {
+            label: label.to_string(),
+            flow_pce: v_pce,
+            flow_veh: v_veh,
+            capacity_pce: c_pce,
+            capacity_veh: c_veh,
+            v_c_ratio: x,
+            control_delay: d,
+            los: los_char(los_unsignalized(d, x > 1.0)),
+            queue_95: Roundabouts::queue_95(x, c_veh, t),
+        });
+    }
+
+    // Bypass lane (Steps 5–12)
+    let [_, _, _, r_pce] = a.flows_pce.unwrap_or([0.0; 4]);
+    let v_veh = r_pce * f_hv;
+    let bypass_lane = match a.bypass {
+        BypassType::None => None,
+        BypassType::Yielding => {
+            let v_ex = a.bypass_conflicting_flow_pce.unwrap_or(0.0);
+            let c_pce = if bypass_exit_lanes >= 2 {
+                capacity_bypass_two_exit_lanes(v_ex)
+            } else {
+                capacity_bypass_one_exit_lane(v_ex)
+            };
+            let c_veh = c_pce * f_hv; // pedestrians not modeled
+            let x = if c_veh > 0.0 { v_veh / c_veh } else { 0.0 };
+            let d = control_delay_roundabout(v_veh, c_veh, t);
+            Some(RoundaboutLaneResult {
+                label: "bypass".to_string(),
+                flow_pce: r_pce,
+                flow_veh: v_veh,
+                capacity_pce: c_pce,
+                capacity_veh: c_veh,
+                v_c_ratio: x,
+                control_delay: d,
+                los: los_char(los_unsignalized(d, x > 1.0)),
+                queue_95: Roundabouts::queue_95(x, c_veh, t),
+            })
+        }
+        BypassType::NonYielding => Some(RoundaboutLaneResult {
+            // Type 2 nonyielding bypass: delay assumed 0
+            // (HCM Chapter 33 Example Problem 1 treatment)
+            label: "bypass".to_string(),
+            flow_pce: r_pce,
+            flow_veh: v_veh,
+            capacity_pce: 0.0,
+            capacity_veh: 0.0,
+            v_c_ratio: 0.0,
+            control_delay: 0.0,
+            los: 'A',
+            queue_95: 0.0,
+        }),
+    };
+    (lanes, bypass_lane)
+}
+
+/// HCM Equation 22-18: set an approach's control delay and LOS from its
+/// lane (and bypass-lane) results. Returns the `(delay, flow)` pair that
+/// enters Equation 22-19, or `None` for an approach without demand.
+pub(crate) fn aggregate_approach_delay(a: &mut RoundaboutApproach) -> Option<(f64, f64)> {
+    let mut pairs: Vec<(f64, f64)> = a
+        .lanes
+        .iter()
+        .map(|l| (l.control_delay, l.flow_veh))
+        .collect();
+    if let Some(b) = &a.bypass_lane {
+        pairs.push((b.control_delay, b.flow_veh));
+    }
+    if pairs.iter().all(|(_, v)| *v <= 0.0) {
+        return None;
+    }
+    let d_a = aggregate_control_delay(&pairs);
+    let v_a: f64 = pairs.iter().map(|(_, v)| v).sum();
+    a.control_delay = Some(d_a);
+    a.los = Some(los_char(los_unsignalized(d_a, false)));
+    Some((d_a, v_a))
+}
+
 /// Convert a [`crate::hcm::common::LevelOfService`] to its letter.
 fn los_char(los: crate::hcm::common::LevelOfService) -> char {
     los.into()
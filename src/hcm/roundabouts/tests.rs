@@ -2,6 +2,7 @@
 //! the intermediate values published in HCM Chapter 33, Example Problems 1
 //! and 2.
 
+use super::multileg::*;
 use super::roundabouts::*;
 
 fn approx(a: f64, b: f64, tol: f64) -> bool {
@@ -378,3 +379,188 @@ fn test_serde_roundtrip() {
         1e-9
     ));
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// N-leg roundabouts
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn single_lane_legs(n: usize) -> Vec<RoundaboutLeg> {
+    (0..n)
+        .map(|i| RoundaboutLeg::new(&format!("leg{i}"), RoundaboutApproach::default()))
+        .collect()
+}
+
+/// Movement classes by leg count: three legs have no through movement,
+/// five and six legs have two and three through exits.
+#[test]
+fn test_movement_classes() {
+    use Movement::*;
+    let classes = |n: usize| -> Vec<Movement> {
+        (0..n).map(|d| Movement::classify(n, 0, d)).collect()
+    };
+    assert_eq!(classes(3), vec![U, R, L]);
+    assert_eq!(classes(4), vec![U, R, T, L]);
+    assert_eq!(classes(5), vec![U, R, T, T, L]);
+    assert_eq!(classes(6), vec![U, R, T, T, T, L]);
+    // Relative to the origin, not the leg numbering
+    assert_eq!(Movement::classify(5, 3, 4), R);
+    assert_eq!(Movement::classify(5, 3, 2), L);
+    assert_eq!(Movement::classify(5, 3, 3), U);
+}
+
+/// Both Example Problems run through the N-leg model match the four-leg
+/// model exactly, bypass lanes included.
+#[test]
+fn test_four_leg_model_is_the_n_leg_special_case() {
+    for mut r in [example_problem_1(), example_problem_2()] {
+        let mut m = r.to_multileg();
+        r.analyze();
+        m.analyze().unwrap();
+        assert_eq!(m.n_legs(), 4);
+        for (i, leg) in [Leg::NB, Leg::WB, Leg::SB, Leg::EB].into_iter().enumerate() {
+            let a = r.approach(leg);
+            let b = &m.legs[i].approach;
+            assert_eq!(m.legs[i].name, format!("{leg:?}"));
+            assert_eq!(a.flows_pce, b.flows_pce);
+            assert!(approx(
+                a.circulating_flow_pce.unwrap(),
+                b.circulating_flow_pce.unwrap(),
+                1e-9
+            ));
+            assert_eq!(
+                a.bypass_conflicting_flow_pce.is_some(),
+                b.bypass_conflicting_flow_pce.is_some()
+            );
+            if let (Some(x), Some(y)) =
+                (a.bypass_conflicting_flow_pce, b.bypass_conflicting_flow_pce)
+            {
+                assert!(approx(x, y, 1e-9));
+            }
+            assert_eq!(a.lanes.len(), b.lanes.len());
+            for (la, lb) in a.lanes.iter().zip(&b.lanes) {
+                assert!(approx(la.capacity_veh, lb.capacity_veh, 1e-9));
+                assert!(approx(la.control_delay, lb.control_delay, 1e-9));
+                assert_eq!(la.los, lb.los);
+            }
+            assert_eq!(a.control_delay, b.control_delay);
+            assert_eq!(a.los, b.los);
+        }
+        assert!(approx(
+            r.intersection_delay.unwrap(),
+            m.intersection_delay.unwrap(),
+            1e-9
+        ));
+        assert_eq!(r.intersection_los, m.intersection_los);
+    }
+}
+
+/// Three-leg roundabout, hand-computed conflicting flows. Legs 0, 1, 2 in
+/// circulating order; flow rates in pc/h (no PHF or heavy vehicles).
+#[test]
+fn test_three_leg_conflicting_flows() {
+    let demand = vec![
+        vec![5.0, 200.0, 100.0],
+        vec![10.0, 0.0, 300.0],
+        vec![150.0, 250.0, 20.0],
+    ];
+    let mut m = MultiLegRoundabout::new(single_lane_legs(3), demand);
+    m.analyze().unwrap();
+    // Entry 0 is passed by leg 1's U-turns and by leg 2's trips to leg 1
+    // (its left turn) and U-turns.
+    assert!(approx(m.legs[0].approach.circulating_flow_pce.unwrap(), 0.0 + 250.0 + 20.0, 1e-9));
+    // Entry 1: leg 2's U-turns, leg 0's left turns (to leg 2) and U-turns.
+    assert!(approx(m.legs[1].approach.circulating_flow_pce.unwrap(), 20.0 + 100.0 + 5.0, 1e-9));
+    // Entry 2: leg 0's U-turns, leg 1's left turns (to leg 0) and U-turns.
+    assert!(approx(m.legs[2].approach.circulating_flow_pce.unwrap(), 5.0 + 10.0 + 0.0, 1e-9));
+    // Movement classes on leg 0: U = 5, L = 100, T = 0, R = 200
+    assert_eq!(m.legs[0].approach.flows_pce, Some([5.0, 100.0, 0.0, 200.0]));
+    // Exiting flows are the column sums, and all entering flow exits.
+    assert_eq!(m.exiting_flow_pce, vec![165.0, 450.0, 420.0]);
+    let entering: f64 = m.demand_pce.iter().flatten().sum();
+    assert!(approx(m.exiting_flow_pce.iter().sum::<f64>(), entering, 1e-9));
+}
+
+/// With a uniform q between every pair of distinct legs, each entry is
+/// conflicted by (N - 1)(N - 2)/2 O-D pairs and each leg carries
+/// (N - 1) q exiting: 6q and 4q for five legs, 10q and 5q for six.
+#[test]
+fn test_five_and_six_leg_uniform_demand() {
+    let q = 50.0;
+    for (n, conflicting_pairs) in [(5, 6.0), (6, 10.0)] {
+        let demand = (0..n)
+            .map(|o| (0..n).map(|d| if o == d { 0.0 } else { q }).collect())
+            .collect();
+        let mut m = MultiLegRoundabout::new(single_lane_legs(n), demand);
+        m.analyze().unwrap();
+        for leg in &m.legs {
+            let a = &leg.approach;
+            assert!(approx(a.circulating_flow_pce.unwrap(), conflicting_pairs * q, 1e-9));
+            // R and L are single exits; the rest are through.
+            assert_eq!(a.flows_pce, Some([0.0, q, (n as f64 - 3.0) * q, q]));
+            let c_expected = capacity_single_lane(conflicting_pairs * q);
+            assert!(approx(a.lanes[0].capacity_pce, c_expected, 1e-9));
+        }
+        for v in &m.exiting_flow_pce {
+            assert!(approx(*v, (n as f64 - 1.0) * q, 1e-9));
+        }
+        assert!(m.intersection_los.is_some());
+    }
+}
+
+/// A yielding bypass on a five-leg roundabout merges into the next leg and
+/// is conflicted by that leg's exiting flow minus its own right turns.
+#[test]
+fn test_five_leg_bypass_conflicting_flow() {
+    let mut legs = single_lane_legs(5);
+    legs[1].approach.bypass = BypassType::Yielding;
+    let mut demand = vec![vec![0.0; 5]; 5];
+    demand[0][2] = 100.0; // leg 0 through, exits on leg 2
+    demand[1][2] = 300.0; // leg 1 right turn, uses the bypass
+    demand[4][2] = 80.0; // leg 4 through
+    let mut m = MultiLegRoundabout::new(legs, demand);
+    m.analyze().unwrap();
+    assert!(approx(m.exiting_flow_pce[2], 480.0, 1e-9));
+    let a = &m.legs[1].approach;
+    assert!(approx(a.bypass_conflicting_flow_pce.unwrap(), 180.0, 1e-9));
+    let bypass = a.bypass_lane.as_ref().unwrap();
+    assert!(approx(bypass.capacity_pce, capacity_bypass_one_exit_lane(180.0), 1e-9));
+    // Bypass traffic does not use the entry lane
+    assert!(approx(a.lanes[0].flow_pce, 0.0, 1e-9));
+}
+
+/// Input checks: too few legs, a non-square demand matrix, and movement
+/// volumes on a leg.
+#[test]
+fn test_multileg_validation() {
+    let m = MultiLegRoundabout::new(single_lane_legs(2), vec![vec![0.0; 2]; 2]);
+    assert!(m.validate().unwrap_err().contains("at least 3 legs"));
+    let m = MultiLegRoundabout::new(single_lane_legs(3), vec![vec![0.0; 3]; 2]);
+    assert!(m.validate().unwrap_err().contains("3 x 3"));
+    let mut legs = single_lane_legs(3);
+    legs[0].approach.v_t = 100.0;
+    let mut m = MultiLegRoundabout::new(legs, vec![vec![0.0; 3]; 3]);
+    assert!(m.analyze().unwrap_err().contains("demand matrix"));
+    let mut m = MultiLegRoundabout::new(single_lane_legs(3), vec![vec![-1.0; 3]; 3]);
+    assert!(m.analyze().is_err());
+}
+
+/// Legs deserialize with the approach fields flattened beside the name.
+#[test]
+fn test_multileg_json() {
+    let json = r#"{
+        "legs": [
+            {"name": "North", "heavy_vehicle_pct": 2.0},
+            {"name": "East", "bypass": "Yielding"},
+            {"name": "South", "entry_lanes": 2, "lane_assignment": "LeftThroughAndRight"}
+        ],
+        "demand": [[0, 100, 200], [50, 0, 150], [120, 80, 0]],
+        "phf": 0.9
+    }"#;
+    let mut m = MultiLegRoundabout::from_json(json).unwrap();
+    assert_eq!(m.leg_index("South"), Some(2));
+    assert_eq!(m.legs[1].approach.bypass, BypassType::Yielding);
+    m.analyze().unwrap();
+    let back = MultiLegRoundabout::from_json(&m.to_json().unwrap()).unwrap();
+    assert_eq!(back.intersection_los, m.intersection_los);
+    assert_eq!(back.legs[2].approach.lanes.len(), 2);
+}
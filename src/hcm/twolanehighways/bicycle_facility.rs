@@ -0,0 +1,172 @@
+//! # Bicycle LOS Over a Facility
+//!
+//! Section 4 of Chapter 15 scores one segment. [`BicycleFacility`] scores a
+//! sequence of them, built from a [`TwoLaneHighways`] facility or from
+//! Chapter 12 multilane highway segments ([`BasicFreeways`] with
+//! `highway_type` `"multilane"`), so the bicycle inputs come from the
+//! motorized ones already entered:
+//!
+//! | Bicycle input | Two-lane segment | Multilane segment |
+//! |---|---|---|
+//! | lane width | `lane_width` (12 ft) | `lw` (12 ft) |
+//! | shoulder width | `shoulder_width` (6 ft) | `lc_r` |
+//! | posted speed | `spl` | `speed_limit` |
+//! | directional lanes | 1, or 2 on a passing lane | `lane_count` |
+//! | hourly volume, PHF | `volume`, `phf` | `demand_flow_i`, `phf` |
+//! | heavy vehicles (decimal) | `phv` / 100 | `p_t` |
+//!
+//! Pavement condition and on-highway parking have no motorized counterpart
+//! and are supplied as [`BicycleConditions`], one for the whole facility or
+//! one per segment.
+//!
+//! The facility BLOS score is the length-weighted mean of the segment
+//! scores, graded on Exhibit 15-7. Chapter 15 defines no facility bicycle
+//! LOS; the length weighting follows the Chapter 16 urban street bicycle
+//! facility score. [`BicycleLOS::analyze`] checks every segment with
+//! [`BicycleLOS::validate`], so a posted speed at or below 20 mi/h is an
+//! error, not a score.
+
+use serde::{Deserialize, Serialize};
+
+use super::twolanehighways::{bicycle_los_from_score, BicycleLOS, BicycleLOSResult, TwoLaneHighways};
+use crate::hcm::basicfreeways::BasicFreeways;
+
+/// Bicycle inputs with no motorized counterpart.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BicycleConditions {
+    /// FHWA 5-point pavement rating.
+    pub pavement_condition: f64,
+    /// Share of the segment with occupied on-highway parking, decimal.
+    #[serde(default)]
+    pub pct_on_highway_parking: f64,
+}
+
+/// One segment of a bicycle facility.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BicycleFacilitySegment {
+    /// Segment length, mi.
+    pub length: f64,
+    pub inputs: BicycleLOS,
+}
+
+/// A sequence of segments scored for bicycles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BicycleFacility {
+    pub segments: Vec<BicycleFacilitySegment>,
+}
+
+/// One segment's Section 4 result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BicycleSegmentResult {
+    pub index: usize,
+    pub length: f64,
+    pub result: BicycleLOSResult,
+}
+
+/// Output of [`BicycleFacility::analyze`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BicycleFacilityResult {
+    pub total_length: f64,
+    /// Length-weighted BLOS score.
+    pub blos_score: f64,
+    /// Exhibit 15-7 LOS of the facility score.
+    pub los: char,
+    pub segments: Vec<BicycleSegmentResult>,
+}
+
+/// The conditions for segment `i` of `n`.
+fn conditions_for(conditions: &[BicycleConditions], i: usize, n: usize) -> Result<BicycleConditions, String> {
+    match conditions.len() {
+        1 => Ok(conditions[0]),
+        len if len == n => Ok(conditions[i]),
+        len => Err(format!(
+            "bicycle facility: {len} sets of conditions for {n} segments; give one, or one per segment"
+        )),
+    }
+}
+
+impl BicycleFacility {
+    /// Bicycle segments from a two-lane highway facility.
+    pub fn from_two_lane(highway: &TwoLaneHighways, conditions: &[BicycleConditions]) -> Result<Self, String> {
+        let n = highway.segments.len();
+        let segments = highway
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| {
+                let c = conditions_for(conditions, i, n)?;
+                Ok(BicycleFacilitySegment {
+                    length: seg.get_length(),
+                    inputs: BicycleLOS::new(
+                        highway.lane_width.unwrap_or(12.0),
+                        highway.shoulder_width.unwrap_or(6.0),
+                        seg.get_spl(),
+                        if seg.get_passing_type() == 2 { 2 } else { 1 },
+                        c.pavement_condition,
+                        seg.get_volume(),
+                        seg.get_phf(),
+                        seg.get_phv() / 100.0,
+                        c.pct_on_highway_parking,
+                    ),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { segments })
+    }
+
+    /// Bicycle segments from Chapter 12 multilane highway segments.
+    pub fn from_multilane(segments: &[BasicFreeways], conditions: &[BicycleConditions]) -> Result<Self, String> {
+        let n = segments.len();
+        let segments = segments
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| {
+                if seg.highway_type != "multilane" {
+                    return Err(format!(
+                        "bicycle facility: segment {i} is a {:?} segment, not a multilane highway",
+                        seg.highway_type
+                    ));
+                }
+                let c = conditions_for(conditions, i, n)?;
+                Ok(BicycleFacilitySegment {
+                    length: seg.length,
+                    inputs: BicycleLOS::new(
+                        seg.lw.unwrap_or(12.0),
+                        seg.lc_r,
+                        f64::from(seg.speed_limit),
+                        seg.lane_count as i32,
+                        c.pavement_condition,
+                        seg.demand_flow_i,
+                        seg.phf,
+                        seg.p_t.unwrap_or(0.0),
+                        c.pct_on_highway_parking,
+                    ),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { segments })
+    }
+
+    /// Score every segment and the facility.
+    pub fn analyze(&self) -> Result<BicycleFacilityResult, String> {
+        if self.segments.is_empty() {
+            return Err("bicycle facility: no segments".into());
+        }
+        let mut segments = Vec::with_capacity(self.segments.len());
+        for (index, seg) in self.segments.iter().enumerate() {
+            if seg.length.is_nan() || seg.length <= 0.0 {
+                return Err(format!("bicycle facility: segment {index} length must be positive"));
+            }
+            let result = seg.inputs.analyze().map_err(|e| format!("{e} (segment {index})"))?;
+            segments.push(BicycleSegmentResult { index, length: seg.length, result });
+        }
+        let total_length: f64 = segments.iter().map(|s| s.length).sum();
+        let blos_score = segments.iter().map(|s| s.result.blos_score * s.length).sum::<f64>() / total_length;
+        Ok(BicycleFacilityResult {
+            total_length,
+            blos_score,
+            los: bicycle_los_from_score(blos_score),
+            segments,
+        })
+    }
+}
@@ -0,0 +1,343 @@
+//! # Two-Lane Highway Analysis in Both Directions
+//!
+//! Chapter 15 analyzes one direction at a time, with the other direction
+//! present only as the opposing demand on Passing Zone segments. A study of
+//! a two-lane road needs both directions, and they are not independent:
+//! each direction's demand is the other's opposing flow, and a passing lane
+//! in one direction takes the opposing lane the other direction would pass
+//! in.
+//!
+//! [`BidirectionalTwoLane`] holds one shared alignment and each direction's
+//! striping and demand, all on one stationing: stations in feet increasing
+//! in the [`TravelDirection::Increasing`] direction, grades as seen by that
+//! direction. Each direction is mirrored into its own direction of travel,
+//! cut by [`TwoLaneAlignment::build`] and run through
+//! [`TwoLaneHighways::analyze`]. The coupling between the directions:
+//!
+//! - **Opposing flow.** A segment's `volume_op` is the other direction's
+//!   demand, length-weighted over the segment's extent, so the opposing
+//!   demand does not add segment boundaries of its own.
+//! - **Passing lanes.** Where the other direction has a passing lane, a
+//!   passing zone is analyzed as Passing Constrained (a three-lane section
+//!   gives the single-lane direction no passing). A passing lane in both
+//!   directions at one station is a four-lane section and is rejected.
+//!
+//! [`TwoLaneAlignment::build`]: super::segmentation::TwoLaneAlignment::build
+//! [`TwoLaneHighways::analyze`]: super::twolanehighways::TwoLaneHighways::analyze
+
+use serde::{Deserialize, Serialize};
+
+use super::segmentation::{
+    DemandStretch, GradeTangent, PassingStretch, SegmentationDecision, SegmentationRule,
+    StationedCurve, TwoLaneAlignment, TwoLaneSegmentation,
+};
+use super::twolanehighways::TwoLaneFacilityResult;
+
+/// Stations closer than this are the same station, ft.
+const STATION_TOL_FT: f64 = 1e-6;
+
+/// Direction of travel relative to the shared stationing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TravelDirection {
+    Increasing,
+    Decreasing,
+}
+
+/// One direction's striping and demand, on the shared stationing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectionInputs {
+    /// Passing zones and passing lanes for this direction.
+    pub passing: Vec<PassingStretch>,
+    /// This direction's demand. An entry applies from `start_ft` up to the
+    /// next entry's `start_ft` on the shared stationing, whichever way the
+    /// direction travels. `opposing_volume` must be left at zero; it is
+    /// derived from the other direction.
+    pub demand: Vec<DemandStretch>,
+    /// Posted speed limit, mi/h, if it differs from the facility's.
+    pub spl: Option<f64>,
+}
+
+/// A two-lane highway analyzed in both directions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidirectionalTwoLane {
+    /// Facility length, ft.
+    pub length_ft: f64,
+    /// Grade tangents for the increasing direction.
+    #[serde(default)]
+    pub grades: Vec<GradeTangent>,
+    #[serde(default)]
+    pub curves: Vec<StationedCurve>,
+    pub increasing: DirectionInputs,
+    pub decreasing: DirectionInputs,
+    /// Posted speed limit, mi/h.
+    pub spl: f64,
+    /// Peak hour factor (Exhibit 15-8 default 0.94).
+    #[serde(default = "default_phf")]
+    pub phf: f64,
+    /// Heavy vehicles, % (Exhibit 15-8 default 6).
+    #[serde(default = "default_phv")]
+    pub phv: f64,
+    pub lane_width: Option<f64>,
+    pub shoulder_width: Option<f64>,
+    pub apd: Option<f64>,
+    pub pmhvfl: Option<f64>,
+}
+
+fn default_phf() -> f64 {
+    0.94
+}
+
+fn default_phv() -> f64 {
+    6.0
+}
+
+/// One direction's segmentation and Chapter 15 result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectionResult {
+    pub direction: TravelDirection,
+    /// Segments in this direction's order of travel, stations measured from
+    /// where this direction enters the facility.
+    pub segmentation: TwoLaneSegmentation,
+    pub result: TwoLaneFacilityResult,
+}
+
+/// Output of [`BidirectionalTwoLane::run`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidirectionalResults {
+    pub increasing: DirectionResult,
+    pub decreasing: DirectionResult,
+}
+
+impl BidirectionalResults {
+    /// The direction with the worse LOS, or the higher follower density on
+    /// a tie.
+    pub fn critical(&self) -> &DirectionResult {
+        let (a, b) = (&self.increasing.result, &self.decreasing.result);
+        if (b.los, b.follower_density) > (a.los, a.follower_density) {
+            &self.decreasing
+        } else {
+            &self.increasing
+        }
+    }
+}
+
+/// Clip `[start, end]` to the parts not covered by `cover`.
+fn subtract(start: f64, end: f64, cover: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut parts = vec![(start, end)];
+    for &(cs, ce) in cover {
+        parts = parts
+            .into_iter()
+            .flat_map(|(s, e)| {
+                let mut kept = Vec::new();
+                if cs > s + STATION_TOL_FT {
+                    kept.push((s, cs.min(e)));
+                }
+                if ce < e - STATION_TOL_FT {
+                    kept.push((ce.max(s), e));
+                }
+                kept.into_iter().filter(|(a, b)| b - a > STATION_TOL_FT)
+            })
+            .collect();
+    }
+    parts
+}
+
+/// Length-weighted volume of `demand` over `[start, end]`, stations on the
+/// shared stationing.
+fn average_volume(demand: &[DemandStretch], length: f64, start: f64, end: f64) -> f64 {
+    let total: f64 = demand
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            let next = demand.get(i + 1).map_or(length, |n| n.start_ft);
+            let overlap = next.min(end) - d.start_ft.max(start);
+            d.volume * overlap.max(0.0)
+        })
+        .sum();
+    total / (end - start)
+}
+
+impl BidirectionalTwoLane {
+    fn inputs(&self, direction: TravelDirection) -> &DirectionInputs {
+        match direction {
+            TravelDirection::Increasing => &self.increasing,
+            TravelDirection::Decreasing => &self.decreasing,
+        }
+    }
+
+    /// Check the shared alignment and the direction inputs.
+    pub fn validate(&self) -> Result<(), String> {
+        for direction in [TravelDirection::Increasing, TravelDirection::Decreasing] {
+            let inputs = self.inputs(direction);
+            if inputs.demand.iter().any(|d| d.opposing_volume != 0.0) {
+                return Err(format!(
+                    "two-lane bidirectional: {direction:?} opposing_volume is derived from the other direction and must be 0"
+                ));
+            }
+            self.shared_alignment(inputs)
+                .validate()
+                .map_err(|e| format!("{e} ({direction:?})"))?;
+        }
+        let lanes = |inputs: &DirectionInputs| -> Vec<(f64, f64)> {
+            inputs
+                .passing
+                .iter()
+                .filter(|p| p.passing_type == 2)
+                .map(|p| (p.start_ft, p.end_ft))
+                .collect()
+        };
+        for (s, e) in lanes(&self.increasing) {
+            if let Some((os, oe)) = lanes(&self.decreasing)
+                .into_iter()
+                .find(|&(os, oe)| os < e - STATION_TOL_FT && oe > s + STATION_TOL_FT)
+            {
+                return Err(format!(
+                    "two-lane bidirectional: passing lanes in both directions overlap from station {} to {}; analyze as a multilane highway",
+                    s.max(os),
+                    e.min(oe)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Segment and analyze both directions.
+    pub fn run(&self) -> Result<BidirectionalResults, String> {
+        self.validate()?;
+        Ok(BidirectionalResults {
+            increasing: self.direction(TravelDirection::Increasing)?,
+            decreasing: self.direction(TravelDirection::Decreasing)?,
+        })
+    }
+
+    /// One direction's inputs on the shared stationing, without coupling.
+    fn shared_alignment(&self, inputs: &DirectionInputs) -> TwoLaneAlignment {
+        TwoLaneAlignment {
+            length_ft: self.length_ft,
+            grades: self.grades.clone(),
+            curves: self.curves.clone(),
+            passing: inputs.passing.clone(),
+            demand: inputs.demand.clone(),
+            spl: inputs.spl.unwrap_or(self.spl),
+            phf: self.phf,
+            phv: self.phv,
+            lane_width: self.lane_width,
+            shoulder_width: self.shoulder_width,
+            apd: self.apd,
+            pmhvfl: self.pmhvfl,
+        }
+    }
+
+    fn direction(&self, direction: TravelDirection) -> Result<DirectionResult, String> {
+        let (own, other) = match direction {
+            TravelDirection::Increasing => (&self.increasing, &self.decreasing),
+            TravelDirection::Decreasing => (&self.decreasing, &self.increasing),
+        };
+
+        // Passing zones lose the stretches where the other direction has a
+        // passing lane.
+        let opposing_lanes: Vec<(f64, f64)> = other
+            .passing
+            .iter()
+            .filter(|p| p.passing_type == 2)
+            .map(|p| (p.start_ft, p.end_ft))
+            .collect();
+        let mut constrained = Vec::new();
+        let mut passing = Vec::new();
+        for p in &own.passing {
+            if p.passing_type != 1 {
+                passing.push(*p);
+                continue;
+            }
+            let kept = subtract(p.start_ft, p.end_ft, &opposing_lanes);
+            let mut station = p.start_ft;
+            for &(s, e) in &kept {
+                if s > station + STATION_TOL_FT {
+                    constrained.push((station, s));
+                }
+                passing.push(PassingStretch { start_ft: s, end_ft: e, ..*p });
+                station = e;
+            }
+            if p.end_ft > station + STATION_TOL_FT {
+                constrained.push((station, p.end_ft));
+            }
+        }
+
+        let mut shared = self.shared_alignment(own);
+        shared.passing = passing;
+        let alignment = match direction {
+            TravelDirection::Increasing => shared,
+            TravelDirection::Decreasing => self.mirror(shared),
+        };
+        let mut segmentation = alignment.build()?;
+
+        for (s, e) in constrained {
+            let (s, e) = self.to_direction(direction, s, e);
+            segmentation.decisions.push(SegmentationDecision {
+                station_ft: s,
+                rule: SegmentationRule::OpposingPassingLane,
+                detail: format!(
+                    "passing zone to station {e} faces an opposing passing lane; analyzed as Passing Constrained"
+                ),
+            });
+        }
+        segmentation.decisions.sort_by(|a, b| a.station_ft.total_cmp(&b.station_ft));
+
+        for (seg, extent) in segmentation.highway.segments.iter_mut().zip(&segmentation.segments) {
+            let (s, e) = self.to_direction(direction, extent.start_ft, extent.end_ft);
+            seg.volume_op = Some(average_volume(&other.demand, self.length_ft, s, e));
+        }
+
+        let result = segmentation.highway.clone().analyze();
+        Ok(DirectionResult { direction, segmentation, result })
+    }
+
+    /// Map `[start, end]` between the shared stationing and a direction's
+    /// own; the mapping is its own inverse.
+    fn to_direction(&self, direction: TravelDirection, start: f64, end: f64) -> (f64, f64) {
+        match direction {
+            TravelDirection::Increasing => (start, end),
+            TravelDirection::Decreasing => (self.length_ft - end, self.length_ft - start),
+        }
+    }
+
+    /// The decreasing direction's alignment in its own direction of travel.
+    fn mirror(&self, shared: TwoLaneAlignment) -> TwoLaneAlignment {
+        let len = self.length_ft;
+        let ends = |starts: Vec<f64>| -> Vec<f64> {
+            starts.iter().skip(1).copied().chain(std::iter::once(len)).collect()
+        };
+
+        let grade_ends = ends(shared.grades.iter().map(|g| g.start_ft).collect());
+        let grades = shared
+            .grades
+            .iter()
+            .zip(grade_ends)
+            .rev()
+            .map(|(g, end)| GradeTangent { start_ft: len - end, grade: -g.grade })
+            .collect();
+
+        let demand_ends = ends(shared.demand.iter().map(|d| d.start_ft).collect());
+        let demand = shared
+            .demand
+            .iter()
+            .zip(demand_ends)
+            .rev()
+            .map(|(d, end)| DemandStretch { start_ft: len - end, ..*d })
+            .collect();
+
+        let curves = shared
+            .curves
+            .iter()
+            .map(|c| StationedCurve { start_ft: len - c.end_ft, end_ft: len - c.start_ft, ..*c })
+            .collect();
+        let passing = shared
+            .passing
+            .iter()
+            .map(|p| PassingStretch { start_ft: len - p.end_ft, end_ft: len - p.start_ft, ..*p })
+            .collect();
+
+        TwoLaneAlignment { grades, curves, passing, demand, ..shared }
+    }
+}
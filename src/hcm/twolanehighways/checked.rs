@@ -0,0 +1,216 @@
+//! # Checked Two-Lane Highway Analysis
+//!
+//! [`TwoLaneHighways::analyze`] takes its inputs as given. A missing
+//! optional input falls back to a hard-coded value, such as 5 access points
+//! per mile for `apd`. An out-of-domain input goes straight into the
+//! equations, such as a passing type of 3 or a PHF of zero.
+//! [`TwoLaneHighways::try_analyze`] checks every input against the domain
+//! of the HCM exhibit or equation that consumes it first. It returns
+//! [`TransportationError::InvalidInput`] naming the field, the segment and
+//! the exhibit. On success the result carries every default the engine
+//! applied, as [`AppliedDefault`]s.
+//!
+//! | Field | Domain | Reference |
+//! |---|---|---|
+//! | `passing_type` | 0 (PC), 1 (PZ), 2 (PL) | Exhibit 15-10 |
+//! | `length` | > 0 mi | Exhibit 15-10 |
+//! | `spl` | > 0 mi/h | Exhibit 15-6 |
+//! | `volume` | ≥ 0 veh/h | Equation 15-1 |
+//! | `volume_op` (PZ) | 0 to 1,700 veh/h as a flow rate | Exhibit 15-5 |
+//! | `phf` | > 0 to 1 | Equation 15-1 |
+//! | `phv` | 0 to 100 % | Exhibit 15-5 |
+//! | `vertical_class` | 1 to 5 | Exhibit 15-11 |
+//! | `hor_class` | 0 to 5 | Exhibit 15-22 |
+//! | `lane_width` | 9 to 12 ft | Exhibit 15-8 |
+//! | `shoulder_width` | 0 to 8 ft | Exhibit 15-8 |
+//! | `apd` | ≥ 0 per mi | Equation 15-6 |
+//! | `pmhvfl` | 0 to 1 | Equation 15-28 |
+//! | subsegment `length`, `design_rad`, `sup_ele` | ≥ 0 | Exhibit 15-22 |
+//!
+//! A segment with `is_hc` set must have subsegments, or Step 5 averages its
+//! speed over none and returns zero.
+//!
+//! Defaults are recorded only where the engine reads them: `volume_op` on PZ
+//! segments (PC assumes 1,500 veh/h and PL none), `pmhvfl` when a PL
+//! segment is present, `is_hc` when subsegments are present, and
+//! `sup_ele` on subsegments with a curve.
+
+use serde::{Deserialize, Serialize};
+
+use super::twolanehighways::{TwoLaneFacilityResult, TwoLaneHighways};
+use crate::TransportationError;
+
+/// An input the engine assumed because it was not given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedDefault {
+    /// Segment index, or `None` for a facility input.
+    pub segment: Option<usize>,
+    /// Subsegment index within the segment.
+    #[serde(default)]
+    pub subsegment: Option<usize>,
+    pub field: String,
+    pub value: f64,
+}
+
+/// Output of [`TwoLaneHighways::try_analyze`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneCheckedResult {
+    pub result: TwoLaneFacilityResult,
+    /// Every default applied, facility inputs first, then by segment.
+    pub defaults: Vec<AppliedDefault>,
+}
+
+/// Where an input sits in the facility.
+#[derive(Clone, Copy)]
+struct Site {
+    segment: Option<usize>,
+    subsegment: Option<usize>,
+}
+
+impl Site {
+    const FACILITY: Site = Site { segment: None, subsegment: None };
+
+    fn segment(i: usize) -> Self {
+        Site { segment: Some(i), subsegment: None }
+    }
+
+    fn describe(&self, field: &str) -> String {
+        match (self.segment, self.subsegment) {
+            (Some(i), Some(j)) => format!("segment {i} subsegment {j} {field}"),
+            (Some(i), None) => format!("segment {i} {field}"),
+            _ => field.to_string(),
+        }
+    }
+}
+
+/// Collects defaults and checks domains.
+struct Checker {
+    defaults: Vec<AppliedDefault>,
+}
+
+impl Checker {
+    /// `value`, or `default` recorded as applied.
+    fn take<T: Copy + Into<f64>>(&mut self, site: Site, field: &str, value: Option<T>, default: T) -> T {
+        value.unwrap_or_else(|| {
+            self.defaults.push(AppliedDefault {
+                segment: site.segment,
+                subsegment: site.subsegment,
+                field: field.to_string(),
+                value: default.into(),
+            });
+            default
+        })
+    }
+
+    /// Fail unless `ok`.
+    fn require(site: Site, field: &str, value: f64, ok: bool, domain: &str, reference: &str) -> crate::Result<()> {
+        if ok {
+            Ok(())
+        } else {
+            Err(TransportationError::InvalidInput(format!(
+                "two-lane input: {} = {value} is outside {domain} (HCM {reference})",
+                site.describe(field)
+            )))
+        }
+    }
+}
+
+impl TwoLaneHighways {
+    /// Check every input against its Chapter 15 domain and list the
+    /// defaults [`TwoLaneHighways::analyze`] would apply.
+    pub fn check_inputs(&self) -> crate::Result<Vec<AppliedDefault>> {
+        if self.segments.is_empty() {
+            return Err(TransportationError::InvalidInput(
+                "two-lane input: the facility has no segments".into(),
+            ));
+        }
+        let mut c = Checker { defaults: Vec::new() };
+        let f = Site::FACILITY;
+
+        let lw = c.take(f, "lane_width", self.lane_width, 12.0);
+        Checker::require(f, "lane_width", lw, (9.0..=12.0).contains(&lw), "9-12 ft", "Exhibit 15-8")?;
+        let sw = c.take(f, "shoulder_width", self.shoulder_width, 6.0);
+        Checker::require(f, "shoulder_width", sw, (0.0..=8.0).contains(&sw), "0-8 ft", "Exhibit 15-8")?;
+        let apd = c.take(f, "apd", self.apd, 5.0);
+        Checker::require(f, "apd", apd, (0.0..f64::INFINITY).contains(&apd), "0 or more per mi", "Equation 15-6")?;
+        if self.segments.iter().any(|s| s.passing_type == 2) {
+            let p = c.take(f, "pmhvfl", self.pmhvfl, 0.0);
+            Checker::require(f, "pmhvfl", p, (0.0..=1.0).contains(&p), "0-1", "Equation 15-28")?;
+        }
+
+        for (i, seg) in self.segments.iter().enumerate() {
+            let s = Site::segment(i);
+            let pt = seg.passing_type;
+            Checker::require(s, "passing_type", pt as f64, pt <= 2, "0 (PC), 1 (PZ) or 2 (PL)", "Exhibit 15-10")?;
+            Checker::require(s, "length", seg.length, seg.length > 0.0 && seg.length.is_finite(), "more than 0 mi", "Exhibit 15-10")?;
+            Checker::require(s, "grade", seg.grade, seg.grade.is_finite(), "a finite percent", "Exhibit 15-11")?;
+            Checker::require(s, "spl", seg.spl, seg.spl > 0.0 && seg.spl.is_finite(), "more than 0 mi/h", "Exhibit 15-6")?;
+
+            let phf = c.take(s, "phf", seg.phf, 0.95);
+            Checker::require(s, "phf", phf, phf > 0.0 && phf <= 1.0, "more than 0 to 1", "Equation 15-1")?;
+            let volume = c.take(s, "volume", seg.volume, 1000.0);
+            Checker::require(s, "volume", volume, (0.0..f64::INFINITY).contains(&volume), "0 or more veh/h", "Equation 15-1")?;
+            if pt == 1 {
+                let v_o = c.take(s, "volume_op", seg.volume_op, 1500.0);
+                Checker::require(
+                    s,
+                    "volume_op",
+                    v_o,
+                    (0.0..=1700.0 * phf).contains(&v_o),
+                    "0-1,700 veh/h as a flow rate, volume_op / phf",
+                    "Exhibit 15-5",
+                )?;
+            }
+            let phv = c.take(s, "phv", seg.phv, 5.0);
+            Checker::require(s, "phv", phv, (0.0..=100.0).contains(&phv), "0-100 %", "Exhibit 15-5")?;
+            // Step 3 recomputes the class from grade and length. Before that,
+            // only the Exhibit 15-5 passing lane capacity reads it, and only
+            // between 5 and 25 % heavy vehicles.
+            let vc = if pt == 2 && (5.0..25.0).contains(&phv) {
+                Some(c.take(s, "vertical_class", seg.vertical_class, 1))
+            } else {
+                seg.vertical_class
+            };
+            if let Some(vc) = vc {
+                Checker::require(s, "vertical_class", vc as f64, (1..=5).contains(&vc), "1-5", "Exhibit 15-11")?;
+            }
+            if let Some(hc) = seg.hor_class {
+                Checker::require(s, "hor_class", hc as f64, (0..=5).contains(&hc), "0-5", "Exhibit 15-22")?;
+            }
+
+            let subsegments = seg.get_subsegments();
+            let is_hc = if subsegments.is_empty() {
+                seg.is_hc.unwrap_or(false)
+            } else {
+                c.take(s, "is_hc", seg.is_hc, false)
+            };
+            if is_hc && subsegments.is_empty() {
+                return Err(TransportationError::InvalidInput(format!(
+                    "two-lane input: segment {i} has is_hc set but no subsegments (HCM Exhibit 15-22)"
+                )));
+            }
+            if !is_hc {
+                continue;
+            }
+            for (j, sub) in subsegments.iter().enumerate() {
+                let ss = Site { segment: Some(i), subsegment: Some(j) };
+                let nonnegative = |x: f64| (0.0..f64::INFINITY).contains(&x);
+                let len = c.take(ss, "length", sub.length, 0.0);
+                Checker::require(ss, "length", len, nonnegative(len), "0 or more ft", "Exhibit 15-22")?;
+                let rad = c.take(ss, "design_rad", sub.design_rad, 0.0);
+                Checker::require(ss, "design_rad", rad, nonnegative(rad), "0 (tangent) or more ft", "Exhibit 15-22")?;
+                if rad > 0.0 {
+                    let e = c.take(ss, "sup_ele", sub.sup_ele, 0.0);
+                    Checker::require(ss, "sup_ele", e, nonnegative(e), "0 or more %", "Exhibit 15-22")?;
+                }
+            }
+        }
+        Ok(c.defaults)
+    }
+
+    /// [`TwoLaneHighways::analyze`] after [`TwoLaneHighways::check_inputs`].
+    pub fn try_analyze(&mut self) -> crate::Result<TwoLaneCheckedResult> {
+        let defaults = self.check_inputs()?;
+        Ok(TwoLaneCheckedResult { result: self.analyze(), defaults })
+    }
+}
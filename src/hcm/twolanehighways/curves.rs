@@ -0,0 +1,165 @@
+//! # Horizontal Curve Subsegments From Geometry
+//!
+//! Step 5 of Chapter 15 reduces speed on horizontal curves through the
+//! segment's [`SubSegment`]s, each a tangent or a curve with its design
+//! radius and superelevation. [`Segment::set_curves`] derives those
+//! subsegments from [`HorizontalCurve`]s placed along the segment, so a
+//! caller no longer classifies curves by hand:
+//!
+//! - Each [`PlacedCurve`] occupies its arc `length` from `start_ft`, measured
+//!   from the start of the segment. The gaps between curves, and before the
+//!   first and after the last, become tangent subsegments.
+//! - Each subsegment's horizontal class comes from Exhibit 15-22 through
+//!   [`horizontal_class`], using both radius and superelevation, the same
+//!   lookup Step 5 applies. It is not [`HorizontalCurve::horizontal_class`],
+//!   which reads the radius alone.
+//! - `is_hc` is set when there is a curve, so [`TwoLaneHighways::analyze`]
+//!   applies the curve speed effects, and the segment's `hor_class` is its
+//!   sharpest curve.
+//!
+//! Spiral transitions are counted with the adjacent tangents. VERIFY-HCM:
+//! Chapter 15 measures a curve subsegment by its length but does not say
+//! whether spirals belong to it.
+//!
+//! Every curve is also checked with [`HorizontalCurve::validate_for_speed`]
+//! at the segment's posted speed limit. A curve below the Green Book
+//! minimum radius for that speed is still analyzed, and is reported as a
+//! [`CurveSpeedFlag`].
+//!
+//! [`TwoLaneHighways::analyze`]: super::TwoLaneHighways::analyze
+
+use serde::{Deserialize, Serialize};
+
+use super::twolanehighways::{horizontal_class, Segment, SubSegment};
+use crate::hcm::utils::geometric::HorizontalCurve;
+
+/// Tolerance for abutting curves and the segment end, ft.
+const STATION_TOL_FT: f64 = 0.5;
+
+/// A horizontal curve placed along a segment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacedCurve {
+    /// Distance from the start of the segment to the start of the arc, ft.
+    pub start_ft: f64,
+    pub curve: HorizontalCurve,
+}
+
+/// A curve tighter than the Green Book minimum radius for the posted speed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveSpeedFlag {
+    /// Index into the curves given to [`Segment::set_curves`].
+    pub curve: usize,
+    pub start_ft: f64,
+    pub radius_ft: f64,
+    /// Minimum radius at the posted speed limit, ft.
+    pub min_radius_ft: f64,
+    /// Posted speed limit checked, mi/h.
+    pub speed_limit: f64,
+}
+
+impl Segment {
+    /// Replace the segment's subsegments with tangents and the given curves.
+    ///
+    /// Curves may be given in any order but may not overlap or extend past
+    /// the end of the segment. An empty list makes the segment tangent.
+    pub fn set_curves(&mut self, curves: &[PlacedCurve]) -> Result<Vec<CurveSpeedFlag>, String> {
+        let length_ft = self.length * 5280.0;
+        let mut order: Vec<usize> = (0..curves.len()).collect();
+        order.sort_by(|&a, &b| curves[a].start_ft.total_cmp(&curves[b].start_ft));
+
+        let mut spans = Vec::with_capacity(curves.len());
+        let mut flags = Vec::new();
+        let mut station = 0.0;
+        let mut sharpest = 0;
+        for i in order {
+            let PlacedCurve { start_ft, curve } = &curves[i];
+            let end_ft = start_ft + curve.length;
+            if start_ft.is_nan() || *start_ft < -STATION_TOL_FT || curve.radius.is_nan() || curve.radius <= 0.0 {
+                return Err(format!("two-lane curves: curve {i} has an invalid start or radius"));
+            }
+            if curve.length.is_nan() || curve.length <= 0.0 {
+                return Err(format!("two-lane curves: curve {i} length must be positive"));
+            }
+            if *start_ft < station - STATION_TOL_FT {
+                return Err(format!("two-lane curves: curve {i} overlaps the previous curve at {start_ft} ft"));
+            }
+            if end_ft > length_ft + STATION_TOL_FT {
+                return Err(format!(
+                    "two-lane curves: curve {i} ends at {end_ft} ft, past the {length_ft} ft segment"
+                ));
+            }
+
+            sharpest = sharpest.max(horizontal_class(curve.radius, curve.superelevation));
+            spans.push(CurveSpan {
+                start_ft: *start_ft,
+                end_ft,
+                radius_ft: curve.radius,
+                superelevation: curve.superelevation,
+            });
+            if let Err(min_radius_ft) = curve.validate_for_speed(self.spl.round() as i32) {
+                flags.push(CurveSpeedFlag {
+                    curve: i,
+                    start_ft: *start_ft,
+                    radius_ft: curve.radius,
+                    min_radius_ft,
+                    speed_limit: self.spl,
+                });
+            }
+            station = end_ft.min(length_ft);
+        }
+        self.subsegments = Some(curve_subsegments(&spans, length_ft, STATION_TOL_FT));
+        self.is_hc = Some(!curves.is_empty());
+        self.hor_class = Some(sharpest);
+        Ok(flags)
+    }
+}
+
+/// A curve's arc within a segment, ft from the start of the segment.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CurveSpan {
+    pub start_ft: f64,
+    pub end_ft: f64,
+    pub radius_ft: f64,
+    /// Superelevation, %.
+    pub superelevation: f64,
+}
+
+/// Tangent and curve subsegments over a segment `length_ft` long, shared by
+/// [`Segment::set_curves`] and [`super::segmentation`].
+///
+/// `spans` must be in station order and must not overlap. Each becomes a
+/// curve subsegment with its Exhibit 15-22 class. Every gap longer than
+/// `tol_ft`, including the ones before the first curve and after the last,
+/// becomes a tangent. A curve running past the segment end keeps its own
+/// length, and the segment ends there. No spans gives no subsegments.
+pub(crate) fn curve_subsegments(
+    spans: &[CurveSpan],
+    length_ft: f64,
+    tol_ft: f64,
+) -> Vec<SubSegment> {
+    let mut subsegments = Vec::with_capacity(2 * spans.len() + 1);
+    let mut station = 0.0;
+    for c in spans {
+        if c.start_ft - station > tol_ft {
+            subsegments.push(tangent(c.start_ft - station));
+        }
+        let length = c.end_ft - c.start_ft;
+        subsegments.push(SubSegment::new(
+            Some(length),
+            None,
+            Some(horizontal_class(c.radius_ft, c.superelevation)),
+            Some(c.radius_ft),
+            Some((length / c.radius_ft).to_degrees()),
+            Some(c.superelevation),
+        ));
+        station = c.end_ft.min(length_ft);
+    }
+    if !spans.is_empty() && length_ft - station > tol_ft {
+        subsegments.push(tangent(length_ft - station));
+    }
+    subsegments
+}
+
+fn tangent(length_ft: f64) -> SubSegment {
+    SubSegment::new(Some(length_ft), None, Some(0), Some(0.0), Some(0.0), Some(0.0))
+}
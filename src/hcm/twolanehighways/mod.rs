@@ -1,7 +1,49 @@
 //! HCM Chapter 15: Two-Lane Highways.
+//!
+//! [`reliability`] extends the single-period method to travel time
+//! reliability over a reporting period, and [`passing_lanes`] searches
+//! passing lane placements. [`segmentation`] cuts a facility into segments
+//! from station-referenced alignment and passing zone data, and
+//! [`bidirectional`] analyzes both directions of one alignment together.
+//! [`multiperiod`] runs the method over a daily or multi-period profile.
+//! [`bicycle_facility`] scores the bicycle mode over a sequence of two-lane
+//! or multilane segments. [`checked`] validates inputs against their HCM
+//! domains and records the defaults applied. [`curves`] derives a
+//! segment's curve subsegments from placed horizontal curves.
 
+pub mod bicycle_facility;
+pub mod bidirectional;
+pub mod checked;
+pub mod curves;
+pub mod multiperiod;
+pub mod passing_lanes;
+pub mod reliability;
+pub mod segmentation;
 pub mod twolanehighways;
 
+pub use bicycle_facility::{
+    BicycleConditions, BicycleFacility, BicycleFacilityResult, BicycleFacilitySegment,
+    BicycleSegmentResult,
+};
+pub use bidirectional::{
+    BidirectionalResults, BidirectionalTwoLane, DirectionInputs, DirectionResult, TravelDirection,
+};
+pub use checked::{AppliedDefault, TwoLaneCheckedResult};
+pub use curves::{CurveSpeedFlag, PlacedCurve};
+pub use multiperiod::{TwoLaneDemandProfile, TwoLanePeriodResult, TwoLaneProfileResults};
+pub use passing_lanes::{
+    PassingLaneBudget, PassingLaneCandidate, PassingLaneConstraints, PassingLaneObjective,
+    PassingLaneOptimizer, PassingLanePlacement, PassingLanePlan, PassingLaneResults,
+};
+pub use reliability::{
+    FollowerDensityDistribution, TwoLaneIncidentInputs, TwoLaneIncidentType, TwoLaneReliability,
+    TwoLaneReliabilityConfig, TwoLaneReliabilityResults, TwoLaneScenarioResult, TwoLaneWorkZone,
+    WeatherShare,
+};
+pub use segmentation::{
+    DemandStretch, GradeTangent, PassingStretch, SegmentExtent, SegmentationDecision,
+    SegmentationRule, StationedCurve, TwoLaneAlignment, TwoLaneSegmentation,
+};
 pub use twolanehighways::*;
 
 pub const CHAPTER: u8 = 15;
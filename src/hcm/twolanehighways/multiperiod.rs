@@ -0,0 +1,206 @@
+//! # Two-Lane Highway Multi-Period Analysis
+//!
+//! Chapter 15 is applied to the peak 15 minutes of a design hour, through
+//! the PHF. [`TwoLaneHighways::analyze_periods`] applies it to every period
+//! of a demand profile instead, such as the 96 quarter hours of a day, and
+//! sums the periods into VMT-weighted measures.
+//!
+//! The profile is the analysis-direction demand entering the facility,
+//! as a [`DemandProfile`] of counts per [`AnalysisPeriod`]. Each period's
+//! demand multiplier is its entering flow rate over the facility's design
+//! flow rate, `v / PHF` of the first segment. Every segment's `volume` and
+//! `volume_op` are scaled by that multiplier, so segments keep their
+//! design-hour ratios to the entry and a period whose flow rate equals the
+//! design flow rate reproduces [`TwoLaneHighways::analyze`]. An optional
+//! opposing profile scales `volume_op` instead, relative to the largest
+//! design opposing flow rate on the facility.
+//!
+//! Periods are independent. Chapter 15 has no model above capacity, so a
+//! period with demand over capacity is reported at LOS F, and no queue is
+//! carried into the next period.
+
+use serde::{Deserialize, Serialize};
+
+use super::twolanehighways::{TwoLaneFacilityResult, TwoLaneHighways};
+use crate::hcm::common::time_period::{AnalysisPeriod, DemandProfile};
+use crate::hcm::common::LevelOfService;
+
+/// Demand over a sequence of analysis periods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneDemandProfile {
+    pub period: AnalysisPeriod,
+    /// Analysis-direction demand entering the facility, veh per period.
+    pub demand: DemandProfile,
+    /// Opposing demand, veh per period. When absent, opposing volumes scale
+    /// with the analysis direction.
+    #[serde(default)]
+    pub opposing: Option<DemandProfile>,
+    /// Clock time at the start of the first period, h.
+    #[serde(default)]
+    pub start_h: f64,
+}
+
+/// Chapter 15 result for one analysis period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLanePeriodResult {
+    pub index: usize,
+    /// Clock time at the start of the period, h.
+    pub start_h: f64,
+    /// Analysis-direction flow rate entering the facility, veh/h.
+    pub entry_flow_rate: f64,
+    /// Multiplier applied to the design-hour segment volumes.
+    pub demand_multiplier: f64,
+    /// Vehicle-miles traveled in the period.
+    pub vmt: f64,
+    /// Vehicle-hours traveled in the period.
+    pub vht: f64,
+    pub result: TwoLaneFacilityResult,
+}
+
+/// Output of [`TwoLaneHighways::analyze_periods`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneProfileResults {
+    pub periods: Vec<TwoLanePeriodResult>,
+    pub total_vmt: f64,
+    pub total_vht: f64,
+    /// Space-mean speed over the profile, VMT / VHT, mi/h.
+    pub average_speed: f64,
+    /// Facility follower density weighted by period VMT, followers/mi/ln.
+    pub vmt_weighted_follower_density: f64,
+    /// Segment percent followers weighted by segment VMT, %.
+    pub vmt_weighted_percent_followers: f64,
+    /// Hours at facility LOS A through F.
+    pub hours_by_los: [f64; 6],
+    /// Hours with demand over capacity on some segment.
+    pub hours_over_capacity: f64,
+    /// Period with the worst LOS, then the highest follower density.
+    pub worst_period: usize,
+}
+
+impl TwoLaneDemandProfile {
+    /// Check periods and counts.
+    pub fn validate(&self) -> Result<(), String> {
+        let d = self.period.duration_h;
+        if d.is_nan() || d <= 0.0 {
+            return Err("two-lane multi-period: period duration_h must be positive".into());
+        }
+        let n = self.period.num_periods as usize;
+        if n == 0 {
+            return Err("two-lane multi-period: num_periods must be at least 1".into());
+        }
+        let profiles = std::iter::once(("demand", &self.demand))
+            .chain(self.opposing.iter().map(|o| ("opposing", o)));
+        for (name, profile) in profiles {
+            if profile.period_volumes.len() != n {
+                return Err(format!(
+                    "two-lane multi-period: {name} has {} periods but num_periods is {n}",
+                    profile.period_volumes.len()
+                ));
+            }
+            if let Some(i) = profile.period_volumes.iter().position(|v| v.is_nan() || *v < 0.0) {
+                return Err(format!("two-lane multi-period: {name} volume in period {i} is invalid"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TwoLaneHighways {
+    /// Run Chapter 15 Steps 1-11 for every period of `profile`.
+    pub fn analyze_periods(&self, profile: &TwoLaneDemandProfile) -> Result<TwoLaneProfileResults, String> {
+        profile.validate()?;
+        let first = self
+            .segments
+            .first()
+            .ok_or("two-lane multi-period: the facility has no segments")?;
+        let design_flow = first.get_volume() / first.get_phf();
+        if design_flow.is_nan() || design_flow <= 0.0 {
+            return Err("two-lane multi-period: the first segment needs a positive volume".into());
+        }
+        let design_opposing = self
+            .segments
+            .iter()
+            .map(|s| s.get_volume_op() / s.get_phf())
+            .fold(0.0, f64::max);
+        if profile.opposing.is_some() && design_opposing <= 0.0 {
+            return Err("two-lane multi-period: an opposing profile needs a segment with volume_op".into());
+        }
+
+        let duration = profile.period.duration_h;
+        let entry = profile.demand.flow_rates(&profile.period);
+        let opposing = profile.opposing.as_ref().map(|o| o.flow_rates(&profile.period));
+
+        let mut periods = Vec::with_capacity(entry.len());
+        let mut pf_vmt = 0.0;
+        for (index, &entry_flow_rate) in entry.iter().enumerate() {
+            let m = entry_flow_rate / design_flow;
+            let m_op = opposing.as_ref().map_or(m, |o| o[index] / design_opposing);
+            let mut highway = self.clone();
+            for seg in &mut highway.segments {
+                seg.volume = Some(seg.get_volume() * m);
+                seg.volume_op = Some(seg.get_volume_op() * m_op);
+            }
+            let result = highway.analyze();
+
+            let vmt: f64 = result.segments.iter().map(|s| s.flow_rate * duration * s.length).sum();
+            let vht: f64 = result
+                .segments
+                .iter()
+                .filter(|s| s.avg_speed > 0.0)
+                .map(|s| s.flow_rate * duration * s.length / s.avg_speed)
+                .sum();
+            pf_vmt += result
+                .segments
+                .iter()
+                .map(|s| s.flow_rate * duration * s.length * s.percent_followers)
+                .sum::<f64>();
+            periods.push(TwoLanePeriodResult {
+                index,
+                start_h: profile.start_h + duration * index as f64,
+                entry_flow_rate,
+                demand_multiplier: m,
+                vmt,
+                vht,
+                result,
+            });
+        }
+
+        let total_vmt: f64 = periods.iter().map(|p| p.vmt).sum();
+        let total_vht: f64 = periods.iter().map(|p| p.vht).sum();
+        let weighted = |x: f64| if total_vmt > 0.0 { x / total_vmt } else { 0.0 };
+        let mut hours_by_los = [0.0; 6];
+        for p in &periods {
+            hours_by_los[p.result.los as usize] += duration;
+        }
+        let worst_period = periods
+            .iter()
+            .max_by(|a, b| {
+                a.result
+                    .los
+                    .cmp(&b.result.los)
+                    .then(a.result.follower_density.total_cmp(&b.result.follower_density))
+            })
+            .map_or(0, |p| p.index);
+
+        Ok(TwoLaneProfileResults {
+            total_vmt,
+            total_vht,
+            average_speed: if total_vht > 0.0 { total_vmt / total_vht } else { 0.0 },
+            vmt_weighted_follower_density: weighted(
+                periods.iter().map(|p| p.vmt * p.result.follower_density).sum(),
+            ),
+            vmt_weighted_percent_followers: weighted(pf_vmt),
+            hours_by_los,
+            hours_over_capacity: duration * periods.iter().filter(|p| p.result.over_capacity).count() as f64,
+            worst_period,
+            periods,
+        })
+    }
+}
+
+impl TwoLaneProfileResults {
+    /// Hours at `los`.
+    pub fn hours_at(&self, los: LevelOfService) -> f64 {
+        self.hours_by_los[los as usize]
+    }
+}
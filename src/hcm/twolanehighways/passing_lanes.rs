@@ -0,0 +1,359 @@
+//! # Passing Lane Placement
+//!
+//! Chapter 15 evaluates a facility with its passing lanes already placed;
+//! choosing where to put them and how long to make them is left to the
+//! analyst. This module searches that choice exhaustively. Each candidate
+//! names a segment and the passing lane lengths that may be tried there,
+//! and every combination of candidates that fits the budget is run through
+//! [`TwoLaneHighways::analyze`]. The benefit of a placement is the
+//! Step 9 downstream adjustment (Equations 15-36 to 15-38) plus the
+//! passing lane's own midpoint density, as aggregated by Equation 15-39.
+//!
+//! A passing lane shorter than its segment occupies the segment's upstream
+//! end, so the rest of the segment, which keeps its passing type, lies
+//! within the effective length. Horizontal curve subsegments are divided at
+//! the same station. A passing lane in the first segment has no upstream
+//! segment to take its entering percent followers from and produces no
+//! Step 9 adjustment, so the first segment is not a valid candidate.
+//!
+//! Exhibit 15-10 gives passing lane segments a 0.5-mi minimum and a 3.0-mi
+//! maximum (1.1 mi on vertical class 3). Candidate lengths are not held to
+//! that range, but each evaluated segment reports its Step 1 limits.
+
+use serde::{Deserialize, Serialize};
+
+use super::twolanehighways::{SubSegment, TwoLaneFacilityResult, TwoLaneHighways};
+
+/// Passing type code of a passing lane segment.
+const PASSING_LANE: usize = 2;
+
+/// A segment that may receive a passing lane.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassingLaneCandidate {
+    /// Segment index in the input facility.
+    pub segment: usize,
+    /// Passing lane lengths to try, mi. Each must fit within the segment.
+    pub lengths: Vec<f64>,
+}
+
+/// What a plan may spend.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PassingLaneBudget {
+    /// At most this many new passing lanes.
+    Count(usize),
+    /// At most this many new passing lane miles.
+    LaneMiles(f64),
+}
+
+/// How plans are ranked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PassingLaneObjective {
+    /// Lowest facility follower density first.
+    #[default]
+    FollowerDensity,
+    /// Best facility LOS first, then lowest follower density.
+    LevelOfService,
+}
+
+/// Search limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PassingLaneConstraints {
+    /// Minimum gap between the end of one passing lane and the start of the
+    /// next, existing passing lanes included, mi.
+    pub min_spacing_mi: f64,
+    /// The search fails rather than evaluate more placements than this.
+    pub max_evaluations: usize,
+    /// Number of ranked plans returned.
+    pub max_plans: usize,
+}
+
+impl Default for PassingLaneConstraints {
+    fn default() -> Self {
+        Self {
+            min_spacing_mi: 0.0,
+            max_evaluations: 100_000,
+            max_plans: 10,
+        }
+    }
+}
+
+/// Passing lane placement search over one facility.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassingLaneOptimizer {
+    pub highway: TwoLaneHighways,
+    pub candidates: Vec<PassingLaneCandidate>,
+    pub budget: PassingLaneBudget,
+    #[serde(default)]
+    pub objective: PassingLaneObjective,
+    #[serde(default)]
+    pub constraints: PassingLaneConstraints,
+}
+
+/// One new passing lane.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PassingLanePlacement {
+    /// Segment index in the input facility.
+    pub segment: usize,
+    /// Passing lane length, mi.
+    pub length: f64,
+}
+
+/// An evaluated set of placements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassingLanePlan {
+    pub placements: Vec<PassingLanePlacement>,
+    /// Total new passing lane length, mi.
+    pub lane_miles: f64,
+    /// Baseline facility follower density minus this plan's.
+    pub fd_reduction: f64,
+    /// LOS letters gained over the baseline.
+    pub los_improvement: i32,
+    /// Chapter 15 result for the facility with the placements applied. Its
+    /// segments are those of the modified facility, so a split segment
+    /// appears twice.
+    pub result: TwoLaneFacilityResult,
+}
+
+/// Search output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassingLaneResults {
+    /// The facility as given.
+    pub baseline: TwoLaneFacilityResult,
+    /// Best plans first, at most [`PassingLaneConstraints::max_plans`].
+    pub plans: Vec<PassingLanePlan>,
+    /// Plans evaluated.
+    pub evaluated: usize,
+}
+
+impl PassingLaneOptimizer {
+    pub fn new(
+        highway: TwoLaneHighways,
+        candidates: Vec<PassingLaneCandidate>,
+        budget: PassingLaneBudget,
+    ) -> Self {
+        Self {
+            highway,
+            candidates,
+            budget,
+            objective: PassingLaneObjective::default(),
+            constraints: PassingLaneConstraints::default(),
+        }
+    }
+
+    /// Check candidates, budget and constraints against the facility.
+    pub fn validate(&self) -> Result<(), String> {
+        let segments = &self.highway.segments;
+        let n = segments.len();
+        if n == 0 {
+            return Err("passing lane optimizer: facility has no segments".into());
+        }
+        match self.budget {
+            PassingLaneBudget::Count(0) => {
+                return Err("passing lane optimizer: budget allows no passing lanes".into())
+            }
+            PassingLaneBudget::LaneMiles(m) if m.is_nan() || m <= 0.0 => {
+                return Err("passing lane optimizer: lane-mile budget must be positive".into())
+            }
+            _ => {}
+        }
+        let c = &self.constraints;
+        if c.min_spacing_mi.is_nan() || c.min_spacing_mi < 0.0 {
+            return Err("passing lane optimizer: min_spacing_mi must be non-negative".into());
+        }
+        if c.max_plans == 0 {
+            return Err("passing lane optimizer: max_plans must be at least 1".into());
+        }
+        if self.candidates.is_empty() {
+            return Err("passing lane optimizer: no candidates".into());
+        }
+        for (k, cand) in self.candidates.iter().enumerate() {
+            let s = cand.segment;
+            if s >= n {
+                return Err(format!("passing lane optimizer: candidate {k} names segment {s} of {n}"));
+            }
+            if s == 0 {
+                return Err(format!(
+                    "passing lane optimizer: candidate {k} is the first segment, which has no upstream segment"
+                ));
+            }
+            if segments[s].get_passing_type() == PASSING_LANE {
+                return Err(format!("passing lane optimizer: segment {s} is already a passing lane"));
+            }
+            if self.candidates[..k].iter().any(|o| o.segment == s) {
+                return Err(format!("passing lane optimizer: segment {s} is a candidate twice"));
+            }
+            if cand.lengths.is_empty() {
+                return Err(format!("passing lane optimizer: candidate {k} has no lengths"));
+            }
+            let seg_len = segments[s].get_length();
+            if let Some(l) = cand.lengths.iter().find(|&&l| l.is_nan() || l <= 0.0 || l > seg_len + 1e-9) {
+                return Err(format!(
+                    "passing lane optimizer: candidate {k} length {l} mi does not fit segment {s} ({seg_len} mi)"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate every placement within the budget and rank them.
+    pub fn run(&self) -> Result<PassingLaneResults, String> {
+        self.validate()?;
+        let baseline = self.highway.clone().analyze();
+
+        let mut combos = Vec::new();
+        self.enumerate(0, &mut Vec::new(), 0.0, &mut combos)?;
+
+        let mut plans: Vec<PassingLanePlan> = combos
+            .into_iter()
+            .filter(|placements| self.spacing_ok(placements))
+            .map(|placements| {
+                let result = apply_placements(&self.highway, &placements).analyze();
+                PassingLanePlan {
+                    lane_miles: placements.iter().map(|p| p.length).sum(),
+                    fd_reduction: baseline.follower_density - result.follower_density,
+                    los_improvement: baseline.los as i32 - result.los as i32,
+                    placements,
+                    result,
+                }
+            })
+            .collect();
+        let evaluated = plans.len();
+
+        plans.sort_by(|a, b| {
+            let by_los = match self.objective {
+                PassingLaneObjective::LevelOfService => a.result.los.cmp(&b.result.los),
+                PassingLaneObjective::FollowerDensity => std::cmp::Ordering::Equal,
+            };
+            by_los
+                .then(a.result.follower_density.total_cmp(&b.result.follower_density))
+                .then(a.lane_miles.total_cmp(&b.lane_miles))
+        });
+        plans.truncate(self.constraints.max_plans);
+
+        Ok(PassingLaneResults {
+            baseline,
+            plans,
+            evaluated,
+        })
+    }
+
+    /// Depth-first over candidates: each is skipped or takes one of its
+    /// lengths, as long as the budget allows.
+    fn enumerate(
+        &self,
+        k: usize,
+        current: &mut Vec<PassingLanePlacement>,
+        miles: f64,
+        out: &mut Vec<Vec<PassingLanePlacement>>,
+    ) -> Result<(), String> {
+        if k == self.candidates.len() {
+            if !current.is_empty() {
+                if out.len() == self.constraints.max_evaluations {
+                    return Err(format!(
+                        "passing lane optimizer: more than {} placements; narrow the candidates or raise max_evaluations",
+                        self.constraints.max_evaluations
+                    ));
+                }
+                out.push(current.clone());
+            }
+            return Ok(());
+        }
+        self.enumerate(k + 1, current, miles, out)?;
+        let cand = &self.candidates[k];
+        for &length in &cand.lengths {
+            let fits = match self.budget {
+                PassingLaneBudget::Count(max) => current.len() < max,
+                PassingLaneBudget::LaneMiles(max) => miles + length <= max + 1e-9,
+            };
+            if fits {
+                current.push(PassingLanePlacement { segment: cand.segment, length });
+                self.enumerate(k + 1, current, miles + length, out)?;
+                current.pop();
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether every gap between passing lanes, new and existing, meets
+    /// [`PassingLaneConstraints::min_spacing_mi`].
+    fn spacing_ok(&self, placements: &[PassingLanePlacement]) -> bool {
+        let min = self.constraints.min_spacing_mi;
+        if min <= 0.0 {
+            return true;
+        }
+        let mut spans = Vec::new();
+        let mut station = 0.0;
+        for (i, seg) in self.highway.segments.iter().enumerate() {
+            if seg.get_passing_type() == PASSING_LANE {
+                spans.push((station, station + seg.get_length()));
+            } else if let Some(p) = placements.iter().find(|p| p.segment == i) {
+                spans.push((station, station + p.length));
+            }
+            station += seg.get_length();
+        }
+        spans.windows(2).all(|w| w[1].0 - w[0].1 >= min - 1e-9)
+    }
+}
+
+/// The facility with each placement's passing lane applied, splitting a
+/// segment when the passing lane is shorter than it.
+pub fn apply_placements(
+    highway: &TwoLaneHighways,
+    placements: &[PassingLanePlacement],
+) -> TwoLaneHighways {
+    let mut hw = highway.clone();
+    hw.segments.clear();
+    for (i, seg) in highway.segments.iter().enumerate() {
+        match placements.iter().find(|p| p.segment == i) {
+            None => hw.segments.push(seg.clone()),
+            Some(p) if p.length >= seg.get_length() - 1e-9 => {
+                let mut pl = seg.clone();
+                pl.passing_type = PASSING_LANE;
+                hw.segments.push(pl);
+            }
+            Some(p) => {
+                let (upstream, downstream) = split_subsegments(seg.get_subsegments(), p.length * 5280.0);
+                let mut pl = seg.clone();
+                pl.passing_type = PASSING_LANE;
+                pl.length = p.length;
+                pl.subsegments = Some(upstream);
+                let mut rest = seg.clone();
+                rest.length = seg.get_length() - p.length;
+                rest.subsegments = Some(downstream);
+                hw.segments.push(pl);
+                hw.segments.push(rest);
+            }
+        }
+    }
+    hw
+}
+
+/// Divide subsegments at `station_ft` from the segment start. A curve cut
+/// in two keeps its radius, and its central angle is split in proportion.
+fn split_subsegments(subsegments: &[SubSegment], station_ft: f64) -> (Vec<SubSegment>, Vec<SubSegment>) {
+    let mut upstream = Vec::new();
+    let mut downstream = Vec::new();
+    let mut start = 0.0;
+    for sub in subsegments {
+        let len = sub.get_length();
+        let end = start + len;
+        if end <= station_ft + 1e-6 {
+            upstream.push(sub.clone());
+        } else if start >= station_ft - 1e-6 {
+            downstream.push(sub.clone());
+        } else {
+            let share = (station_ft - start) / len;
+            let mut a = sub.clone();
+            let mut b = sub.clone();
+            a.length = Some(len * share);
+            b.length = Some(len * (1.0 - share));
+            a.central_angle = sub.central_angle.map(|c| c * share);
+            b.central_angle = sub.central_angle.map(|c| c * (1.0 - share));
+            upstream.push(a);
+            downstream.push(b);
+        }
+        start = end;
+    }
+    (upstream, downstream)
+}
@@ -0,0 +1,502 @@
+//! # Two-Lane Highway Travel Time Reliability
+//!
+//! The HCM gives freeways (Chapter 11) and urban streets (Chapter 17) a
+//! reliability method but has none for two-lane highways. This module wraps
+//! the Chapter 15 single-period method in a scenario loop in the same way,
+//! using the shared adjustment factors of
+//! [`crate::hcm::common::adjustment_factors`] and the shared
+//! [`TravelTimeDistribution`]:
+//!
+//! 1. **Demand variation.** One demand pattern per month and weekday of the
+//!    reliability reporting period. The demand multiplier is the Exhibit 11-19
+//!    rural demand ratio of the pattern divided by the ratio of the seed
+//!    month and weekday, so the segment volumes are read as seed-day demand.
+//!    Each pattern is weighted by the days it covers in a non-leap year.
+//! 2. **Weather.** Each pattern is split into the weather conditions of
+//!    [`TwoLaneReliabilityConfig::weather`], by share of the study period.
+//!    The CAF and SAF come from Exhibits 11-20 and 11-21 at the segment FFS.
+//! 3. **Incidents.** The expected number of incidents in progress is the
+//!    incident rate times the pattern's VMT times the mean incident
+//!    duration. The shoulder/lane-blocked split and the durations come from
+//!    Exhibit 11-22. An incident is placed on each segment in proportion to
+//!    its length.
+//! 4. **Work zones.** A work zone applies its CAF and SAF to its segments in
+//!    every scenario of its months.
+//!
+//! Each scenario is evaluated with Steps 1 through 11 of Chapter 15, through
+//! [`TwoLaneHighways::analyze_adjusted`]. The SAF scales the Step 4 FFS, and
+//! the CAF scales the Step 2 capacity. Chapter 15
+//! has no speed model above capacity, so when a segment's demand flow rate
+//! exceeds its adjusted capacity the scenario also carries the average
+//! deterministic queuing delay of the analysis period, `T/2 × (v/c − 1)`.
+//! The scenario's TTI is its facility travel time over one facility
+//! free-flow travel time, taken at the Step 4 FFS of the seed conditions.
+//! Step 4 FFS falls with opposing demand, so a per-scenario FFS would move
+//! the TTI base with the demand pattern. The TTI distribution is
+//! VMT-weighted, as Chapter 11 weights it. The facility follower density
+//! (Equation 15-39) distribution is weighted by probability alone.
+//!
+//! VERIFY-HCM: Exhibits 11-19 through 11-23 are freeway values. There is no
+//! HCM capacity for a two-lane highway with its only directional lane
+//! blocked, so [`TwoLaneIncidentInputs::lane_blocked_caf`] defaults to 0.50
+//! (alternating one-way operation past the incident). The shoulder-closure
+//! default of 0.81 is the narrowest (two-lane) row of Exhibit 11-23.
+
+use serde::{Deserialize, Serialize};
+
+use super::twolanehighways::TwoLaneHighways;
+use crate::hcm::common::adjustment_factors::{
+    get_rural_demand_ratio, DayOfWeek, IncidentSeverity, Month, WeatherCondition,
+};
+use crate::hcm::common::reliability::{ReliabilityMetrics, TravelTimeDistribution};
+use crate::hcm::common::LevelOfService;
+
+/// Months in calendar order.
+pub const MONTHS: [Month; 12] = [
+    Month::January,
+    Month::February,
+    Month::March,
+    Month::April,
+    Month::May,
+    Month::June,
+    Month::July,
+    Month::August,
+    Month::September,
+    Month::October,
+    Month::November,
+    Month::December,
+];
+
+/// Days in each month of a non-leap year.
+const DAYS_IN_MONTH: [f64; 12] = [
+    31.0, 28.0, 31.0, 30.0, 31.0, 30.0, 31.0, 31.0, 30.0, 31.0, 30.0, 31.0,
+];
+
+/// Share of the study period spent in one weather condition.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WeatherShare {
+    pub condition: WeatherCondition,
+    /// Fraction of the study period (0–1).
+    pub probability: f64,
+}
+
+/// Incident inputs for a two-lane highway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TwoLaneIncidentInputs {
+    /// Incidents per 100 million VMT in the analysis direction.
+    pub incident_rate_per_100mvmt: f64,
+    /// CAF while an incident occupies the shoulder.
+    pub shoulder_closed_caf: f64,
+    /// CAF while an incident blocks the directional lane.
+    pub lane_blocked_caf: f64,
+}
+
+impl Default for TwoLaneIncidentInputs {
+    fn default() -> Self {
+        Self {
+            incident_rate_per_100mvmt: 0.0,
+            shoulder_closed_caf: 0.81,
+            lane_blocked_caf: 0.50,
+        }
+    }
+}
+
+/// A work zone active in the listed months.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneWorkZone {
+    pub months: Vec<Month>,
+    /// Affected segment indices; empty means every segment.
+    #[serde(default)]
+    pub segments: Vec<usize>,
+    pub caf: f64,
+    #[serde(default = "one")]
+    pub saf: f64,
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+/// Incident states a scenario can carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TwoLaneIncidentType {
+    ShoulderClosed,
+    LaneBlocked,
+}
+
+/// Scenario generation inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TwoLaneReliabilityConfig {
+    /// Month of the segment volumes.
+    pub seed_month: Month,
+    /// Weekday of the segment volumes.
+    pub seed_day: DayOfWeek,
+    /// Months of the reliability reporting period.
+    pub months: Vec<Month>,
+    /// Weekdays of the reliability reporting period.
+    pub days: Vec<DayOfWeek>,
+    /// Non-normal weather shares; the remainder is non-severe weather, which
+    /// is therefore not listed here.
+    pub weather: Vec<WeatherShare>,
+    pub incidents: Option<TwoLaneIncidentInputs>,
+    pub work_zones: Vec<TwoLaneWorkZone>,
+    /// Analysis period duration for the queuing delay, h.
+    pub analysis_period_h: f64,
+}
+
+impl Default for TwoLaneReliabilityConfig {
+    fn default() -> Self {
+        Self {
+            seed_month: Month::January,
+            seed_day: DayOfWeek::Monday,
+            months: MONTHS.to_vec(),
+            days: vec![
+                DayOfWeek::Monday,
+                DayOfWeek::Tuesday,
+                DayOfWeek::Wednesday,
+                DayOfWeek::Thursday,
+                DayOfWeek::Friday,
+            ],
+            weather: Vec::new(),
+            incidents: None,
+            work_zones: Vec::new(),
+            analysis_period_h: 0.25,
+        }
+    }
+}
+
+/// One evaluated scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneScenarioResult {
+    pub month: Month,
+    pub day: DayOfWeek,
+    pub weather: WeatherCondition,
+    /// Incident type and segment index, if any.
+    pub incident: Option<(TwoLaneIncidentType, usize)>,
+    pub work_zone: bool,
+    pub probability: f64,
+    pub demand_multiplier: f64,
+    /// Facility travel time, h.
+    pub travel_time_h: f64,
+    /// Facility travel time at the seed-condition Step 4 FFS, h. The same
+    /// for every scenario.
+    pub free_flow_travel_time_h: f64,
+    pub tti: f64,
+    /// Analysis-direction VMT of the hour.
+    pub vmt: f64,
+    /// Facility follower density (Equation 15-39), followers/mi/ln.
+    pub follower_density: f64,
+    pub los: LevelOfService,
+    /// Whether any segment's demand flow rate exceeds its adjusted capacity.
+    pub over_capacity: bool,
+}
+
+/// Probability-weighted distribution of facility follower density.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerDensityDistribution {
+    pub mean: f64,
+    pub fd_50: f64,
+    pub fd_80: f64,
+    pub fd_95: f64,
+    pub max: f64,
+    /// Percentage of the reporting period at facility LOS A through F.
+    pub pct_by_los: [f64; 6],
+}
+
+impl FollowerDensityDistribution {
+    /// Percentage of the reporting period at `los`.
+    pub fn pct_at(&self, los: LevelOfService) -> f64 {
+        self.pct_by_los[los as usize]
+    }
+}
+
+/// Reliability results for a two-lane highway facility.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneReliabilityResults {
+    pub scenarios: Vec<TwoLaneScenarioResult>,
+    /// VMT-weighted TTI measures.
+    pub metrics: ReliabilityMetrics,
+    pub follower_density: FollowerDensityDistribution,
+    /// Percentage of the reporting period with a segment over capacity.
+    pub pct_over_capacity: f64,
+}
+
+/// Scenario-level adjustments applied to one Chapter 15 evaluation.
+#[derive(Debug, Clone)]
+struct ScenarioFactors {
+    demand_multiplier: f64,
+    caf: Vec<f64>,
+    saf: Vec<f64>,
+}
+
+/// Travel time reliability of a two-lane highway facility.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneReliability {
+    pub highway: TwoLaneHighways,
+    #[serde(default)]
+    pub config: TwoLaneReliabilityConfig,
+}
+
+impl TwoLaneReliability {
+    pub fn new(highway: TwoLaneHighways, config: TwoLaneReliabilityConfig) -> Self {
+        Self { highway, config }
+    }
+
+    /// Check the facility and configuration before generating scenarios.
+    pub fn validate(&self) -> Result<(), String> {
+        let c = &self.config;
+        let n = self.highway.segments.len();
+        if n == 0 {
+            return Err("two-lane reliability: facility has no segments".into());
+        }
+        if c.months.is_empty() || c.days.is_empty() {
+            return Err("two-lane reliability: months and days must not be empty".into());
+        }
+        if c.weather.iter().any(|w| w.condition == WeatherCondition::NonSevereWeather) {
+            return Err("two-lane reliability: non-severe weather is the remainder of the \
+                        weather shares and must not be listed"
+                .into());
+        }
+        if c.weather.iter().any(|w| w.probability.is_nan() || w.probability < 0.0) {
+            return Err("two-lane reliability: weather probabilities must be non-negative".into());
+        }
+        if c.weather.iter().map(|w| w.probability).sum::<f64>() > 1.0 + 1e-9 {
+            return Err("two-lane reliability: weather probabilities sum to more than 1".into());
+        }
+        if let Some(inc) = &c.incidents {
+            if inc.incident_rate_per_100mvmt.is_nan() || inc.incident_rate_per_100mvmt < 0.0 {
+                return Err("two-lane reliability: incident rate must be non-negative".into());
+            }
+            for caf in [inc.shoulder_closed_caf, inc.lane_blocked_caf] {
+                if caf.is_nan() || caf <= 0.0 || caf > 1.0 {
+                    return Err("two-lane reliability: incident CAFs must be in (0, 1]".into());
+                }
+            }
+        }
+        for (k, wz) in c.work_zones.iter().enumerate() {
+            if wz.caf.is_nan() || wz.caf <= 0.0 || wz.saf.is_nan() || wz.saf <= 0.0 {
+                return Err(format!("two-lane reliability: work zone {k} CAF and SAF must be positive"));
+            }
+            if let Some(&s) = wz.segments.iter().find(|&&s| s >= n) {
+                return Err(format!("two-lane reliability: work zone {k} names segment {s} of {n}"));
+            }
+        }
+        if c.analysis_period_h.is_nan() || c.analysis_period_h <= 0.0 {
+            return Err("two-lane reliability: analysis_period_h must be positive".into());
+        }
+        Ok(())
+    }
+
+    /// Generate and evaluate every scenario of the reporting period.
+    pub fn run(&self) -> Result<TwoLaneReliabilityResults, String> {
+        self.validate()?;
+        let c = &self.config;
+        let n = self.highway.segments.len();
+        let lengths: Vec<f64> = self.highway.segments.iter().map(|s| s.get_length()).collect();
+        let total_length: f64 = lengths.iter().sum();
+        let seed_ratio = get_rural_demand_ratio(c.seed_month, c.seed_day);
+        let free_flow_time = self.free_flow_travel_time_h();
+        let base_vmt: f64 = self
+            .highway
+            .segments
+            .iter()
+            .map(|s| s.get_volume() * s.get_length())
+            .sum();
+
+        let mut weather: Vec<(WeatherCondition, f64)> =
+            c.weather.iter().map(|w| (w.condition, w.probability)).collect();
+        let p_normal = 1.0 - weather.iter().map(|w| w.1).sum::<f64>();
+        weather.insert(0, (WeatherCondition::NonSevereWeather, p_normal));
+
+        let month_weight: f64 = c.months.iter().map(|&m| DAYS_IN_MONTH[m as usize]).sum();
+        let mut scenarios = Vec::new();
+        for &month in &c.months {
+            let active_wz: Vec<&TwoLaneWorkZone> =
+                c.work_zones.iter().filter(|wz| wz.months.contains(&month)).collect();
+            for &day in &c.days {
+                let p_pattern =
+                    DAYS_IN_MONTH[month as usize] / month_weight / c.days.len() as f64;
+                let dm = get_rural_demand_ratio(month, day) / seed_ratio;
+                let incidents = self.incident_states(dm * base_vmt, &lengths, total_length);
+                for &(condition, p_weather) in &weather {
+                    if p_weather <= 0.0 {
+                        continue;
+                    }
+                    for &(incident, p_incident) in &incidents {
+                        let mut factors = ScenarioFactors {
+                            demand_multiplier: dm,
+                            caf: vec![1.0; n],
+                            saf: vec![1.0; n],
+                        };
+                        for wz in &active_wz {
+                            for s in 0..n {
+                                if wz.segments.is_empty() || wz.segments.contains(&s) {
+                                    factors.caf[s] *= wz.caf;
+                                    factors.saf[s] *= wz.saf;
+                                }
+                            }
+                        }
+                        if let (Some((kind, s)), Some(inc)) = (incident, &c.incidents) {
+                            factors.caf[s] *= match kind {
+                                TwoLaneIncidentType::ShoulderClosed => inc.shoulder_closed_caf,
+                                TwoLaneIncidentType::LaneBlocked => inc.lane_blocked_caf,
+                            };
+                        }
+                        let mut r = self.evaluate(condition, &factors, free_flow_time);
+                        r.month = month;
+                        r.day = day;
+                        r.incident = incident;
+                        r.work_zone = !active_wz.is_empty();
+                        r.probability = p_pattern * p_weather * p_incident;
+                        scenarios.push(r);
+                    }
+                }
+            }
+        }
+
+        let mut tti = TravelTimeDistribution::new();
+        let mut fd = TravelTimeDistribution::new();
+        let mut pct_by_los = [0.0; 6];
+        let mut pct_over_capacity = 0.0;
+        for s in &scenarios {
+            tti.add(s.tti, s.probability * s.vmt);
+            fd.add(s.follower_density, s.probability);
+            pct_by_los[s.los as usize] += 100.0 * s.probability;
+            if s.over_capacity {
+                pct_over_capacity += 100.0 * s.probability;
+            }
+        }
+        Ok(TwoLaneReliabilityResults {
+            metrics: tti.metrics(),
+            follower_density: FollowerDensityDistribution {
+                mean: fd.mean(),
+                fd_50: fd.percentile(50.0),
+                fd_80: fd.percentile(80.0),
+                fd_95: fd.percentile(95.0),
+                max: fd.max(),
+                pct_by_los,
+            },
+            pct_over_capacity,
+            scenarios,
+        })
+    }
+
+    /// Incident states of one demand pattern with their probabilities: no
+    /// incident, then each type on each segment.
+    fn incident_states(
+        &self,
+        vmt: f64,
+        lengths: &[f64],
+        total_length: f64,
+    ) -> Vec<(Option<(TwoLaneIncidentType, usize)>, f64)> {
+        let rate = match &self.config.incidents {
+            Some(inc) if inc.incident_rate_per_100mvmt > 0.0 => inc.incident_rate_per_100mvmt,
+            _ => return vec![(None, 1.0)],
+        };
+        // Exhibit 11-22: every lane-blocking severity collapses into the one
+        // directional lane, with the share-weighted mean duration.
+        let blocking = [
+            IncidentSeverity::OneLaneClosed,
+            IncidentSeverity::TwoLanesClosed,
+            IncidentSeverity::ThreeLanesClosed,
+        ];
+        let share_blocked: f64 = blocking.iter().map(|s| s.get_distribution_pct()).sum();
+        let duration_blocked = blocking
+            .iter()
+            .map(|s| s.get_distribution_pct() * s.get_duration_params().mean)
+            .sum::<f64>()
+            / share_blocked;
+        let shoulder = IncidentSeverity::ShoulderClosed;
+        let share_shoulder = shoulder.get_distribution_pct();
+        let share_total = share_shoulder + share_blocked;
+        let per_hour = rate * vmt / 1e8;
+        let mut active = [
+            (
+                TwoLaneIncidentType::ShoulderClosed,
+                per_hour * share_shoulder / share_total * shoulder.get_duration_params().mean / 60.0,
+            ),
+            (
+                TwoLaneIncidentType::LaneBlocked,
+                per_hour * share_blocked / share_total * duration_blocked / 60.0,
+            ),
+        ];
+        let p_any: f64 = active.iter().map(|a| a.1).sum();
+        if p_any > 1.0 {
+            for a in &mut active {
+                a.1 /= p_any;
+            }
+        }
+        let mut states = vec![(None, 1.0 - p_any.min(1.0))];
+        for (kind, p) in active {
+            for (s, len) in lengths.iter().enumerate() {
+                states.push((Some((kind, s)), p * len / total_length));
+            }
+        }
+        states
+    }
+
+    /// Facility travel time at the Step 4 FFS of the seed conditions, h: the
+    /// facility as given, with no demand, weather, incident or work zone
+    /// adjustment.
+    fn free_flow_travel_time_h(&self) -> f64 {
+        let mut hw = self.highway.clone();
+        (0..hw.segments.len())
+            .map(|i| {
+                hw.identify_vertical_class(i);
+                hw.determine_demand_flow(i);
+                hw.determine_vertical_alignment(i);
+                hw.segments[i].get_length() / hw.determine_free_flow_speed(i)
+            })
+            .sum()
+    }
+
+    /// Run Chapter 15 Steps 1–11 ([`TwoLaneHighways::analyze_adjusted`]) on a
+    /// copy of the facility under one set of scenario adjustments, adding the
+    /// queuing delay of each segment over capacity. `free_flow_time` is the
+    /// facility's seed-condition free-flow travel time, the TTI base of every
+    /// scenario.
+    fn evaluate(
+        &self,
+        weather: WeatherCondition,
+        f: &ScenarioFactors,
+        free_flow_time: f64,
+    ) -> TwoLaneScenarioResult {
+        let mut hw = self.highway.clone();
+        for seg in &mut hw.segments {
+            seg.volume = Some(seg.get_volume() * f.demand_multiplier);
+            seg.volume_op = Some(seg.get_volume_op() * f.demand_multiplier);
+        }
+        let facility = hw.analyze_adjusted(|i, ffs| {
+            (f.caf[i] * weather.get_caf(ffs), f.saf[i] * weather.get_saf(ffs))
+        });
+
+        let mut travel_time = facility.travel_time_h;
+        let mut vmt = 0.0;
+        for seg in &facility.segments {
+            vmt += hw.segments[seg.index].get_volume() * seg.length;
+            let cap = f64::from(seg.capacity);
+            if seg.flow_rate > cap {
+                travel_time += 0.5 * self.config.analysis_period_h * (seg.flow_rate / cap - 1.0);
+            }
+        }
+
+        TwoLaneScenarioResult {
+            month: Month::January,
+            day: DayOfWeek::Monday,
+            weather,
+            incident: None,
+            work_zone: false,
+            probability: 0.0,
+            demand_multiplier: f.demand_multiplier,
+            travel_time_h: travel_time,
+            free_flow_travel_time_h: free_flow_time,
+            tti: travel_time / free_flow_time,
+            vmt,
+            follower_density: facility.follower_density,
+            los: facility.los,
+            over_capacity: facility.over_capacity,
+        }
+    }
+}
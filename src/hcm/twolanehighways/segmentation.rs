@@ -0,0 +1,641 @@
+//! # Two-Lane Highway Segmentation
+//!
+//! Chapter 15 analyzes a facility already cut into homogeneous segments.
+//! This module cuts one from station-referenced alignment data: grade
+//! tangents, horizontal curves, passing zone striping (and passing lanes),
+//! and directional demand. Stations are in feet from the start of the
+//! facility in the analysis direction.
+//!
+//! The rules, applied in order, and recorded in the report as they fire:
+//!
+//! 1. **Passing type and demand.** A segment boundary goes at every change
+//!    in passing type and every change in demand. Stretches not covered by
+//!    a passing zone or passing lane are Passing Constrained.
+//! 2. **Short passing zones.** A passing zone shorter than its Exhibit 15-10
+//!    minimum is analyzed as Passing Constrained. VERIFY-HCM: Chapter 15
+//!    gives the minimum but not what to do with a shorter zone; a zone too
+//!    short to analyze is treated as one too short to pass in.
+//! 3. **Vertical class.** Within a stretch, adjacent grade tangents are
+//!    combined, at their length-weighted grade, when both and the
+//!    combination fall in the same Exhibit 15-11 class. A boundary remains
+//!    wherever the class changes.
+//! 4. **Minimum length.** A piece shorter than its Exhibit 15-10 minimum is
+//!    combined with the neighbour in the same stretch whose grade is
+//!    closer. A stretch that is itself too short is kept and reported; the
+//!    engine analyzes it at the minimum length in Steps 2 to 9.
+//! 5. **Maximum length.** A Passing Constrained or Passing Zone piece longer
+//!    than its Exhibit 15-10 maximum is divided into equal segments within
+//!    it. A passing lane is not divided, since a second passing lane
+//!    segment would restart the Step 9 effective length; one longer than
+//!    the maximum is reported, as Chapter 15 suggests analyzing it as a
+//!    multilane highway.
+//! 6. **Horizontal curves.** Curves become [`SubSegment`]s of the segment
+//!    they lie in, with tangent subsegments between them, built as
+//!    [`Segment::set_curves`] builds them. A curve crossing a segment
+//!    boundary is divided there.
+
+use serde::{Deserialize, Serialize};
+
+use super::curves::{curve_subsegments, CurveSpan};
+use super::twolanehighways::{
+    segment_length_limits, vertical_alignment_class, Segment, SubSegment, TwoLaneHighways,
+};
+
+const FT_PER_MI: f64 = 5280.0;
+
+/// Stations closer than this are the same station, ft.
+const STATION_TOL_FT: f64 = 1e-6;
+
+/// A grade tangent running from `start_ft` to the next tangent.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GradeTangent {
+    pub start_ft: f64,
+    /// Percent grade in the analysis direction, negative for a downgrade.
+    pub grade: f64,
+}
+
+/// A horizontal curve placed by station along the facility.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StationedCurve {
+    pub start_ft: f64,
+    pub end_ft: f64,
+    pub radius_ft: f64,
+    /// Superelevation, %.
+    pub superelevation: f64,
+}
+
+/// A passing zone (`passing_type` 1) or passing lane (2).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PassingStretch {
+    pub start_ft: f64,
+    pub end_ft: f64,
+    pub passing_type: usize,
+}
+
+/// Directional demand from `start_ft` to the next demand entry.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DemandStretch {
+    pub start_ft: f64,
+    /// Analysis direction demand, veh/h.
+    pub volume: f64,
+    /// Opposing direction demand, veh/h (used on passing zones).
+    #[serde(default)]
+    pub opposing_volume: f64,
+    /// Peak hour factor here, if it differs from the alignment's.
+    #[serde(default)]
+    pub phf: Option<f64>,
+    /// Heavy vehicles here, %, if it differs from the alignment's.
+    #[serde(default)]
+    pub phv: Option<f64>,
+}
+
+/// Station-referenced alignment and traffic data for one direction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneAlignment {
+    /// Facility length, ft.
+    pub length_ft: f64,
+    /// Grade tangents in station order; empty means level.
+    #[serde(default)]
+    pub grades: Vec<GradeTangent>,
+    #[serde(default)]
+    pub curves: Vec<StationedCurve>,
+    /// Passing zones and passing lanes; the rest is Passing Constrained.
+    #[serde(default)]
+    pub passing: Vec<PassingStretch>,
+    /// Demand in station order, the first entry at station 0.
+    pub demand: Vec<DemandStretch>,
+    /// Posted speed limit, mi/h.
+    pub spl: f64,
+    /// Peak hour factor (Exhibit 15-8 default 0.94).
+    #[serde(default = "default_phf")]
+    pub phf: f64,
+    /// Heavy vehicles, % (Exhibit 15-8 default 6).
+    #[serde(default = "default_phv")]
+    pub phv: f64,
+    pub lane_width: Option<f64>,
+    pub shoulder_width: Option<f64>,
+    pub apd: Option<f64>,
+    pub pmhvfl: Option<f64>,
+}
+
+fn default_phf() -> f64 {
+    0.94
+}
+
+fn default_phv() -> f64 {
+    6.0
+}
+
+/// Segmentation rule that produced a [`SegmentationDecision`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SegmentationRule {
+    PassingTypeChange,
+    DemandChange,
+    ShortPassingZone,
+    VerticalClassChange,
+    GradesCombined,
+    ShortSegmentCombined,
+    ShortSegmentKept,
+    LongSegmentDivided,
+    LongPassingLane,
+    CurveDivided,
+    /// Set by [`super::bidirectional`]: a passing zone facing an opposing
+    /// passing lane.
+    OpposingPassingLane,
+}
+
+/// One decision made while cutting the facility.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentationDecision {
+    pub station_ft: f64,
+    pub rule: SegmentationRule,
+    pub detail: String,
+}
+
+/// A segment as cut, before analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentExtent {
+    pub start_ft: f64,
+    pub end_ft: f64,
+    pub passing_type: usize,
+    /// Length-weighted grade, %.
+    pub grade: f64,
+    /// Exhibit 15-11 class at the segment's length and grade.
+    pub vertical_class: i32,
+    pub curves: usize,
+}
+
+/// Output of [`TwoLaneAlignment::build`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneSegmentation {
+    pub highway: TwoLaneHighways,
+    /// One entry per segment of `highway`, in order.
+    pub segments: Vec<SegmentExtent>,
+    pub decisions: Vec<SegmentationDecision>,
+}
+
+/// A piece of the facility during cutting.
+#[derive(Debug, Clone, Copy)]
+struct Piece {
+    start: f64,
+    end: f64,
+    passing_type: usize,
+    grade: f64,
+    demand: usize,
+}
+
+impl Piece {
+    fn miles(&self) -> f64 {
+        (self.end - self.start) / FT_PER_MI
+    }
+
+    fn class(&self) -> i32 {
+        vertical_alignment_class(self.miles(), self.grade)
+    }
+
+    fn limits(&self) -> (f64, f64) {
+        segment_length_limits(self.class(), self.passing_type)
+    }
+
+    /// The two pieces as one, at their length-weighted grade.
+    fn join(&self, next: &Piece) -> Piece {
+        let (a, b) = (self.end - self.start, next.end - next.start);
+        Piece {
+            end: next.end,
+            grade: (self.grade * a + next.grade * b) / (a + b),
+            ..*self
+        }
+    }
+}
+
+fn type_name(pt: usize) -> &'static str {
+    match pt {
+        1 => "Passing Zone",
+        2 => "Passing Lane",
+        _ => "Passing Constrained",
+    }
+}
+
+impl TwoLaneAlignment {
+    /// Check stations and values.
+    pub fn validate(&self) -> Result<(), String> {
+        let len = self.length_ft;
+        if len.is_nan() || len <= 0.0 {
+            return Err("two-lane segmentation: length_ft must be positive".into());
+        }
+        let in_range = |x: f64| (0.0..=len).contains(&x);
+        let negative = |x: f64| x.is_nan() || x < 0.0;
+        let bad_phf = |x: f64| x.is_nan() || x <= 0.0 || x > 1.0;
+        if self.demand.is_empty() || self.demand[0].start_ft.abs() > STATION_TOL_FT {
+            return Err("two-lane segmentation: demand must start at station 0".into());
+        }
+        if let Some(g) = self.grades.first() {
+            if g.start_ft.abs() > STATION_TOL_FT {
+                return Err("two-lane segmentation: grades must start at station 0".into());
+            }
+        }
+        let ascending = |starts: Vec<f64>| starts.windows(2).all(|w| w[1] > w[0]);
+        if !ascending(self.grades.iter().map(|g| g.start_ft).collect())
+            || !ascending(self.demand.iter().map(|d| d.start_ft).collect())
+        {
+            return Err("two-lane segmentation: grade and demand stations must increase".into());
+        }
+        if let Some(g) = self.grades.iter().find(|g| !in_range(g.start_ft) || g.grade.is_nan()) {
+            return Err(format!("two-lane segmentation: grade at station {} is invalid", g.start_ft));
+        }
+        if let Some(d) = self
+            .demand
+            .iter()
+            .find(|d| {
+                !in_range(d.start_ft)
+                    || negative(d.volume)
+                    || negative(d.opposing_volume)
+                    || d.phf.is_some_and(bad_phf)
+                    || d.phv.is_some_and(negative)
+            })
+        {
+            return Err(format!("two-lane segmentation: demand at station {} is invalid", d.start_ft));
+        }
+        for p in &self.passing {
+            if p.start_ft >= p.end_ft || !in_range(p.start_ft) || !in_range(p.end_ft) {
+                return Err(format!("two-lane segmentation: passing stretch at station {} is invalid", p.start_ft));
+            }
+            if p.passing_type != 1 && p.passing_type != 2 {
+                return Err(format!(
+                    "two-lane segmentation: passing stretch at station {} must be a passing zone (1) or passing lane (2)",
+                    p.start_ft
+                ));
+            }
+        }
+        let mut passing = self.passing.clone();
+        passing.sort_by(|a, b| a.start_ft.total_cmp(&b.start_ft));
+        if let Some(w) = passing.windows(2).find(|w| w[1].start_ft < w[0].end_ft - STATION_TOL_FT) {
+            return Err(format!("two-lane segmentation: passing stretches overlap at station {}", w[1].start_ft));
+        }
+        let mut curves = self.curves.clone();
+        curves.sort_by(|a, b| a.start_ft.total_cmp(&b.start_ft));
+        for c in &curves {
+            if c.start_ft >= c.end_ft
+                || !in_range(c.start_ft)
+                || !in_range(c.end_ft)
+                || c.radius_ft.is_nan()
+                || c.radius_ft <= 0.0
+            {
+                return Err(format!("two-lane segmentation: curve at station {} is invalid", c.start_ft));
+            }
+        }
+        if let Some(w) = curves.windows(2).find(|w| w[1].start_ft < w[0].end_ft - STATION_TOL_FT) {
+            return Err(format!("two-lane segmentation: curves overlap at station {}", w[1].start_ft));
+        }
+        if self.spl.is_nan() || self.spl <= 0.0 || bad_phf(self.phf) || negative(self.phv) {
+            return Err("two-lane segmentation: spl, phf and phv must be valid".into());
+        }
+        Ok(())
+    }
+
+    /// Cut the facility into Chapter 15 segments.
+    pub fn build(&self) -> Result<TwoLaneSegmentation, String> {
+        self.validate()?;
+        let mut decisions = Vec::new();
+
+        // Rules 1 and 2: stretches of constant passing type and demand.
+        let mut stretches = self.stretches(&mut decisions);
+
+        // Rules 3 to 5 within each stretch.
+        let mut pieces = Vec::new();
+        for stretch in stretches.drain(..) {
+            let mut within = self.grade_pieces(stretch);
+            combine_by_class(&mut within, &mut decisions);
+            combine_short(&mut within, &mut decisions);
+            divide_long(&mut within, &mut decisions);
+            pieces.extend(within);
+        }
+        for w in pieces.windows(2) {
+            if w[0].passing_type == w[1].passing_type && w[0].demand == w[1].demand && w[0].class() != w[1].class() {
+                decisions.push(SegmentationDecision {
+                    station_ft: w[1].start,
+                    rule: SegmentationRule::VerticalClassChange,
+                    detail: format!("vertical class {} to {}", w[0].class(), w[1].class()),
+                });
+            }
+        }
+
+        // Rule 6 and assembly.
+        let mut segments = Vec::with_capacity(pieces.len());
+        let mut extents = Vec::with_capacity(pieces.len());
+        for p in &pieces {
+            let subsegments = self.subsegments(p, &mut decisions);
+            let demand = self.demand[p.demand];
+            let curves = subsegments.iter().filter(|s| s.get_design_rad() > 0.0).count();
+            extents.push(SegmentExtent {
+                start_ft: p.start,
+                end_ft: p.end,
+                passing_type: p.passing_type,
+                grade: p.grade,
+                vertical_class: p.class(),
+                curves,
+            });
+            segments.push(Segment::new(
+                p.passing_type,
+                p.miles(),
+                p.grade,
+                self.spl,
+                Some(curves > 0),
+                Some(demand.volume),
+                Some(demand.opposing_volume),
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(p.class()),
+                Some(subsegments),
+                Some(demand.phf.unwrap_or(self.phf)),
+                Some(demand.phv.unwrap_or(self.phv)),
+                None,
+                None,
+                None,
+                Some(0),
+            ));
+        }
+        decisions.sort_by(|a, b| a.station_ft.total_cmp(&b.station_ft));
+
+        Ok(TwoLaneSegmentation {
+            highway: TwoLaneHighways::new(
+                segments,
+                self.lane_width,
+                self.shoulder_width,
+                self.apd,
+                self.pmhvfl,
+                None,
+            ),
+            segments: extents,
+            decisions,
+        })
+    }
+
+    /// Grade at a station.
+    fn grade_at(&self, station: f64) -> f64 {
+        self.grades
+            .iter()
+            .rev()
+            .find(|g| g.start_ft <= station + STATION_TOL_FT)
+            .map_or(0.0, |g| g.grade)
+    }
+
+    /// Length-weighted grade over `[start, end]`.
+    fn average_grade(&self, start: f64, end: f64) -> f64 {
+        let mut stations: Vec<f64> = vec![start, end];
+        stations.extend(self.grades.iter().map(|g| g.start_ft).filter(|&s| s > start && s < end));
+        stations.sort_by(f64::total_cmp);
+        let rise: f64 = stations.windows(2).map(|w| self.grade_at(w[0]) * (w[1] - w[0])).sum();
+        rise / (end - start)
+    }
+
+    fn passing_type_at(&self, station: f64) -> usize {
+        self.passing
+            .iter()
+            .find(|p| p.start_ft <= station + STATION_TOL_FT && station < p.end_ft - STATION_TOL_FT)
+            .map_or(0, |p| p.passing_type)
+    }
+
+    fn demand_at(&self, station: f64) -> usize {
+        self.demand.iter().rposition(|d| d.start_ft <= station + STATION_TOL_FT).unwrap_or(0)
+    }
+
+    /// Rules 1 and 2.
+    fn stretches(&self, decisions: &mut Vec<SegmentationDecision>) -> Vec<Piece> {
+        let mut stations = vec![0.0, self.length_ft];
+        stations.extend(self.passing.iter().flat_map(|p| [p.start_ft, p.end_ft]));
+        stations.extend(self.demand.iter().map(|d| d.start_ft));
+        stations.sort_by(f64::total_cmp);
+        stations.dedup_by(|a, b| (*a - *b).abs() <= STATION_TOL_FT);
+
+        let mut stretches: Vec<Piece> = stations
+            .windows(2)
+            .map(|w| Piece {
+                start: w[0],
+                end: w[1],
+                passing_type: self.passing_type_at(w[0]),
+                grade: self.average_grade(w[0], w[1]),
+                demand: self.demand_at(w[0]),
+            })
+            .collect();
+
+        for s in &mut stretches {
+            if s.passing_type == 1 && s.miles() < s.limits().0 - 1e-9 {
+                decisions.push(SegmentationDecision {
+                    station_ft: s.start,
+                    rule: SegmentationRule::ShortPassingZone,
+                    detail: format!(
+                        "{:.3} mi passing zone is below the {:.2} mi minimum; analyzed as Passing Constrained",
+                        s.miles(),
+                        s.limits().0
+                    ),
+                });
+                s.passing_type = 0;
+            }
+        }
+
+        let mut merged: Vec<Piece> = Vec::new();
+        for s in stretches {
+            match merged.last_mut() {
+                Some(last) if last.passing_type == s.passing_type && last.demand == s.demand => {
+                    *last = last.join(&s);
+                }
+                Some(last) => {
+                    if last.passing_type != s.passing_type {
+                        decisions.push(SegmentationDecision {
+                            station_ft: s.start,
+                            rule: SegmentationRule::PassingTypeChange,
+                            detail: format!("{} to {}", type_name(last.passing_type), type_name(s.passing_type)),
+                        });
+                    }
+                    if last.demand != s.demand {
+                        decisions.push(SegmentationDecision {
+                            station_ft: s.start,
+                            rule: SegmentationRule::DemandChange,
+                            detail: format!(
+                                "{} to {} veh/h",
+                                self.demand[last.demand].volume, self.demand[s.demand].volume
+                            ),
+                        });
+                    }
+                    merged.push(s);
+                }
+                None => merged.push(s),
+            }
+        }
+        merged
+    }
+
+    /// The stretch cut at every grade break inside it.
+    fn grade_pieces(&self, stretch: Piece) -> Vec<Piece> {
+        let mut stations = vec![stretch.start, stretch.end];
+        stations.extend(
+            self.grades
+                .iter()
+                .map(|g| g.start_ft)
+                .filter(|&s| s > stretch.start + STATION_TOL_FT && s < stretch.end - STATION_TOL_FT),
+        );
+        stations.sort_by(f64::total_cmp);
+        stations
+            .windows(2)
+            .map(|w| Piece {
+                start: w[0],
+                end: w[1],
+                grade: self.grade_at(w[0]),
+                ..stretch
+            })
+            .collect()
+    }
+
+    /// Rule 6: curves and the tangents between them within one segment.
+    fn subsegments(&self, p: &Piece, decisions: &mut Vec<SegmentationDecision>) -> Vec<SubSegment> {
+        let mut curves: Vec<&StationedCurve> = self
+            .curves
+            .iter()
+            .filter(|c| c.start_ft < p.end - STATION_TOL_FT && c.end_ft > p.start + STATION_TOL_FT)
+            .collect();
+        if curves.is_empty() {
+            return Vec::new();
+        }
+        curves.sort_by(|a, b| a.start_ft.total_cmp(&b.start_ft));
+
+        let mut spans = Vec::with_capacity(curves.len());
+        for c in curves {
+            if c.end_ft > p.end + STATION_TOL_FT {
+                decisions.push(SegmentationDecision {
+                    station_ft: p.end,
+                    rule: SegmentationRule::CurveDivided,
+                    detail: format!("curve from station {} divided at the segment boundary", c.start_ft),
+                });
+            }
+            spans.push(CurveSpan {
+                start_ft: c.start_ft.max(p.start) - p.start,
+                end_ft: c.end_ft.min(p.end) - p.start,
+                radius_ft: c.radius_ft,
+                superelevation: c.superelevation,
+            });
+        }
+        curve_subsegments(&spans, p.end - p.start, STATION_TOL_FT)
+    }
+}
+
+/// Rule 3: join neighbours that share a vertical class, joined or not.
+fn combine_by_class(pieces: &mut Vec<Piece>, decisions: &mut Vec<SegmentationDecision>) {
+    while let Some(i) = (0..pieces.len().saturating_sub(1)).find(|&i| {
+        let (a, b) = (pieces[i], pieces[i + 1]);
+        a.class() == b.class() && a.join(&b).class() == a.class()
+    }) {
+        let joined = pieces[i].join(&pieces[i + 1]);
+        if pieces[i].grade != pieces[i + 1].grade {
+            decisions.push(SegmentationDecision {
+                station_ft: pieces[i + 1].start,
+                rule: SegmentationRule::GradesCombined,
+                detail: format!(
+                    "{:.2}% and {:.2}% grades combined at {:.2}% (vertical class {})",
+                    pieces[i].grade,
+                    pieces[i + 1].grade,
+                    joined.grade,
+                    joined.class()
+                ),
+            });
+        }
+        pieces[i] = joined;
+        pieces.remove(i + 1);
+    }
+}
+
+/// Rule 4: join pieces below their minimum length to the closer-graded
+/// neighbour.
+fn combine_short(pieces: &mut Vec<Piece>, decisions: &mut Vec<SegmentationDecision>) {
+    while pieces.len() > 1 {
+        let Some(i) = pieces.iter().position(|p| p.miles() < p.limits().0 - 1e-9) else {
+            break;
+        };
+        let short = pieces[i];
+        let left = i.checked_sub(1);
+        let right = (i + 1 < pieces.len()).then_some(i + 1);
+        let j = match (left, right) {
+            (Some(l), Some(r)) => {
+                if (pieces[l].grade - short.grade).abs() <= (pieces[r].grade - short.grade).abs() {
+                    l
+                } else {
+                    r
+                }
+            }
+            (Some(l), None) => l,
+            (None, Some(r)) => r,
+            (None, None) => unreachable!(),
+        };
+        let (first, second) = (i.min(j), i.max(j));
+        let joined = pieces[first].join(&pieces[second]);
+        decisions.push(SegmentationDecision {
+            station_ft: short.start,
+            rule: SegmentationRule::ShortSegmentCombined,
+            detail: format!(
+                "{:.3} mi below the {:.2} mi minimum; combined with its neighbour at {:.2}% (vertical class {})",
+                short.miles(),
+                short.limits().0,
+                joined.grade,
+                joined.class()
+            ),
+        });
+        pieces[first] = joined;
+        pieces.remove(second);
+    }
+    if let [p] = pieces.as_slice() {
+        let (min, _) = p.limits();
+        if p.miles() < min - 1e-9 {
+            decisions.push(SegmentationDecision {
+                station_ft: p.start,
+                rule: SegmentationRule::ShortSegmentKept,
+                detail: format!(
+                    "{:.3} mi {} segment is below the {:.2} mi minimum; Steps 2-9 use the minimum",
+                    p.miles(),
+                    type_name(p.passing_type),
+                    min
+                ),
+            });
+        }
+    }
+}
+
+/// Rule 5: divide long PC and PZ pieces; report long passing lanes.
+fn divide_long(pieces: &mut Vec<Piece>, decisions: &mut Vec<SegmentationDecision>) {
+    let mut out = Vec::with_capacity(pieces.len());
+    for p in pieces.drain(..) {
+        let (_, max) = p.limits();
+        if p.miles() <= max + 1e-9 {
+            out.push(p);
+        } else if p.passing_type == 2 {
+            decisions.push(SegmentationDecision {
+                station_ft: p.start,
+                rule: SegmentationRule::LongPassingLane,
+                detail: format!(
+                    "{:.3} mi passing lane exceeds the {:.2} mi maximum; consider a multilane highway analysis",
+                    p.miles(),
+                    max
+                ),
+            });
+            out.push(p);
+        } else {
+            let n = (p.miles() / max).ceil();
+            let step = (p.end - p.start) / n;
+            decisions.push(SegmentationDecision {
+                station_ft: p.start,
+                rule: SegmentationRule::LongSegmentDivided,
+                detail: format!("{:.3} mi exceeds the {:.2} mi maximum; divided into {} segments", p.miles(), max, n),
+            });
+            for k in 0..n as usize {
+                out.push(Piece {
+                    start: p.start + step * k as f64,
+                    end: if k + 1 == n as usize { p.end } else { p.start + step * (k + 1) as f64 },
+                    ..p
+                });
+            }
+        }
+    }
+    *pieces = out;
+}
@@ -87,6 +87,7 @@
 //! - HCM 7th Edition, Chapter 15: Two-Lane Highways
 //! - NCHRP Project 17-65: Improved Analysis of Two-Lane Highway Capacity and Operational Performance
 
+use crate::hcm::common::LevelOfService;
 use crate::utils::math;
 use serde::{Deserialize, Serialize};
 
@@ -222,6 +223,9 @@ pub struct Segment {
 /// 10. **Step 10**: Determine segment LOS with `determine_segment_los()`
 /// 11. **Step 11**: Combine segments for facility analysis with `determine_facility_follower_density()` and `determine_facility_los()`
 ///
+/// [`TwoLaneHighways::analyze`] runs the whole sequence and returns a
+/// [`TwoLaneFacilityResult`].
+///
 /// # Base Conditions (Exhibit 15-8)
 ///
 /// Default values when site-specific data is unavailable:
@@ -440,7 +444,7 @@ impl Segment {
         self.capacity.unwrap_or(1700)
     }
 
-    fn set_capacity(&mut self, capacity: i32) {
+    pub(crate) fn set_capacity(&mut self, capacity: i32) {
         self.capacity = Some(capacity)
     }
 
@@ -448,7 +452,7 @@ impl Segment {
         return self.ffs.unwrap_or(0.0);
     }
 
-    fn set_ffs(&mut self, ffs: f64) {
+    pub(crate) fn set_ffs(&mut self, ffs: f64) {
         self.ffs = Some(ffs);
     }
 
@@ -598,45 +602,8 @@ impl TwoLaneHighways {
     /// - Passing lanes exceeding 3 mi may be better analyzed as multilane highways
     /// - Class 3 segments have shorter max length (1.1 mi) due to transitional nature
     pub fn identify_vertical_class(&mut self, seg_num: usize) -> (f64, f64) {
-        let mut _min = 0.0;
-        let mut _max = 0.0;
-        let vc = self.segments[seg_num].get_vertical_class();
-        let pt = self.segments[seg_num].get_passing_type();
-        if (vc == 1) || (vc == 2) {
-            if pt == 0 {
-                _min = 0.25;
-                _max = 3.0;
-            } else if pt == 1 {
-                _min = 0.25;
-                _max = 2.0;
-            } else if pt == 2 {
-                _min = 0.5;
-                _max = 3.0;
-            }
-        } else if vc == 3 {
-            if pt == 0 {
-                _min = 0.25;
-                _max = 1.1;
-            } else if pt == 1 {
-                _min = 0.25;
-                _max = 1.1;
-            } else if pt == 2 {
-                _min = 0.5;
-                _max = 1.1;
-            }
-        } else if (vc == 4) || (vc == 5) {
-            if pt == 0 {
-                _min = 0.5;
-                _max = 3.0;
-            } else if pt == 1 {
-                _min = 0.5;
-                _max = 2.0;
-            } else if pt == 2 {
-                _min = 0.5;
-                _max = 3.0;
-            }
-        };
-        (_min, _max)
+        let segment = &self.segments[seg_num];
+        segment_length_limits(segment.get_vertical_class(), segment.get_passing_type())
     }
 
     /// Step 2: Determine demand flow rates and segment capacity.
@@ -759,224 +726,11 @@ impl TwoLaneHighways {
     /// - Upgrades and downgrades have different classification thresholds
     /// - If the computed class differs from stored value, it updates and re-runs Step 1
     pub fn determine_vertical_alignment(&mut self, seg_num: usize) -> i32 {
-        let seg_length = self.segments[seg_num].get_length();
-        let seg_grade = self.segments[seg_num].get_grade();
-
-        let ver_align: i32;
+        let ver_align = vertical_alignment_class(
+            self.segments[seg_num].get_length(),
+            self.segments[seg_num].get_grade(),
+        );
 
-        if seg_grade >= 0.0 {
-            if seg_length <= 0.1 {
-                if seg_grade <= 7.0 {
-                    ver_align = 1
-                } else {
-                    ver_align = 2
-                };
-            } else if seg_length > 0.1 && seg_length <= 0.2 {
-                if seg_grade <= 4.0 {
-                    ver_align = 1
-                } else if seg_grade <= 7.0 {
-                    ver_align = 2
-                } else {
-                    ver_align = 3
-                };
-            } else if seg_length > 0.2 && seg_length <= 0.3 {
-                if seg_grade <= 3.0 {
-                    ver_align = 1
-                } else if seg_grade <= 5.0 {
-                    ver_align = 2
-                } else if seg_grade <= 7.0 {
-                    ver_align = 3
-                } else if seg_grade <= 9.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.3 && seg_length <= 0.4 {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 4.0 {
-                    ver_align = 2
-                } else if seg_grade <= 6.0 {
-                    ver_align = 3
-                } else if seg_grade <= 7.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.4 && seg_length <= 0.5 {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 4.0 {
-                    ver_align = 2
-                } else if seg_grade <= 5.0 {
-                    ver_align = 3
-                } else if seg_grade <= 6.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.5 && seg_length <= 0.6 {
-                // HCM Exhibit 15-11 row >0.5-0.6 mi (upgrades)
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 3.0 {
-                    ver_align = 2
-                } else if seg_grade <= 5.0 {
-                    ver_align = 3
-                } else if seg_grade <= 6.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.6 && seg_length <= 0.7 {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 3.0 {
-                    ver_align = 2
-                } else if seg_grade <= 4.0 {
-                    ver_align = 3
-                } else if seg_grade <= 6.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.7 && seg_length <= 1.1 {
-                // HCM Exhibit 15-11 rows >0.7-0.8 through >1.0-1.1 mi share these
-                // upgrade thresholds; the previous chain skipped >0.7-0.8 entirely.
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 3.0 {
-                    ver_align = 2
-                } else if seg_grade <= 4.0 {
-                    ver_align = 3
-                } else if seg_grade <= 5.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 3.0 {
-                    ver_align = 2
-                } else if seg_grade <= 5.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            }
-        } else {
-            // Downgrades: Exhibit 15-11 parenthesized values, looked up with the
-            // grade magnitude. (Previously the LENGTH was negated instead, which
-            // sent every downgrade into the first length bucket with an
-            // always-true grade test, so all downgrades returned class 1.)
-            let seg_grade = -1.0 * seg_grade;
-            if seg_length <= 0.1 {
-                if seg_grade <= 8.0 {
-                    ver_align = 1
-                } else {
-                    ver_align = 2
-                };
-            } else if seg_length > 0.1 && seg_length <= 0.2 {
-                if seg_grade <= 5.0 {
-                    ver_align = 1
-                } else if seg_grade <= 8.0 {
-                    ver_align = 2
-                } else {
-                    ver_align = 3
-                };
-            } else if seg_length > 0.2 && seg_length <= 0.3 {
-                if seg_grade <= 4.0 {
-                    ver_align = 1
-                } else if seg_grade <= 6.0 {
-                    ver_align = 2
-                } else if seg_grade <= 8.0 {
-                    ver_align = 3
-                } else if seg_grade <= 9.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.3 && seg_length <= 0.4 {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 5.0 {
-                    ver_align = 2
-                } else if seg_grade <= 6.0 {
-                    ver_align = 3
-                } else if seg_grade <= 8.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.4 && seg_length <= 0.5 {
-                if seg_grade <= 3.0 {
-                    ver_align = 1
-                } else if seg_grade <= 4.0 {
-                    ver_align = 2
-                } else if seg_grade <= 6.0 {
-                    ver_align = 3
-                } else if seg_grade <= 7.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.5 && seg_length <= 0.7 {
-                if seg_grade <= 3.0 {
-                    ver_align = 1
-                } else if seg_grade <= 4.0 {
-                    ver_align = 2
-                } else if seg_grade <= 5.0 {
-                    ver_align = 3
-                } else if seg_grade <= 6.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.7 && seg_length <= 0.8 {
-                if seg_grade <= 3.0 {
-                    ver_align = 1
-                } else if seg_grade <= 4.0 {
-                    ver_align = 3
-                } else if seg_grade <= 6.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.8 && seg_length <= 0.9 {
-                if seg_grade <= 3.0 {
-                    ver_align = 1
-                } else if seg_grade <= 4.0 {
-                    ver_align = 3
-                } else if seg_grade <= 5.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else if seg_length > 0.9 && seg_length <= 1.1 {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 3.0 {
-                    ver_align = 2
-                } else if seg_grade <= 4.0 {
-                    ver_align = 3
-                } else if seg_grade <= 5.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            } else {
-                if seg_grade <= 2.0 {
-                    ver_align = 1
-                } else if seg_grade <= 3.0 {
-                    ver_align = 2
-                } else if seg_grade <= 5.0 {
-                    ver_align = 4
-                } else {
-                    ver_align = 5
-                };
-            }
-        }
         if ver_align != self.segments[seg_num].get_vertical_class() {
             self.segments[seg_num].set_vertical_class(ver_align);
             // Run step 1 again.
@@ -1221,7 +975,6 @@ impl TwoLaneHighways {
         ffs = math::round_up_to_n_decimal(ffs, 1);
 
         let mut s: f64;
-        let mut hor_class: i32 = 0;
 
         if pt == 0 || pt == 1 {
             if vc == 1 {
@@ -1415,91 +1168,7 @@ impl TwoLaneHighways {
 
         // Length of horizontal curves = radius x central angle x pi/180
         // determine horizontal class
-        if rad == 0.0 {
-            hor_class = 0;
-        } else if rad > 0.0 && rad < 300.0 {
-            hor_class = 5;
-        } else if rad >= 300.0 && rad < 450.0 {
-            hor_class = 4;
-        } else if rad >= 450.0 && rad < 600.0 {
-            if sup_ele < 1.0 {
-                hor_class = 4
-            } else {
-                hor_class = 3
-            };
-        } else if rad >= 600.0 && rad < 750.0 {
-            if sup_ele < 6.0 {
-                hor_class = 3
-            } else {
-                hor_class = 2
-            };
-        } else if rad >= 750.0 && rad < 900.0 {
-            hor_class = 2;
-        } else if rad >= 900.0 && rad < 1050.0 {
-            if sup_ele < 8.0 {
-                hor_class = 2
-            } else {
-                hor_class = 1
-            };
-        } else if rad >= 1050.0 && rad < 1200.0 {
-            if sup_ele < 4.0 {
-                hor_class = 2
-            } else {
-                hor_class = 1
-            };
-        } else if rad >= 1200.0 && rad < 1350.0 {
-            if sup_ele < 2.0 {
-                hor_class = 2
-            } else {
-                hor_class = 1
-            };
-        } else if rad >= 1350.0 && rad < 1500.0 {
-            hor_class = 1;
-        } else if rad >= 1500.0 && rad < 1750.0 {
-            if sup_ele < 8.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 1750.0 && rad < 1800.0 {
-            if sup_ele < 6.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 1800.0 && rad < 1950.0 {
-            if sup_ele < 5.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 1950.0 && rad < 2100.0 {
-            if sup_ele < 4.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 2100.0 && rad < 2250.0 {
-            if sup_ele < 3.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 2250.0 && rad < 2400.0 {
-            if sup_ele < 2.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 2400.0 && rad < 2550.0 {
-            if sup_ele < 1.0 {
-                hor_class = 1
-            } else {
-                hor_class = 0
-            };
-        } else if rad >= 2550.0 {
-            hor_class = 0;
-        }
+        let hor_class = horizontal_class(rad, sup_ele);
 
         if vd <= 100.0 {
             let st = ffs;
@@ -2038,96 +1707,101 @@ impl TwoLaneHighways {
         fd
     }
 
+    /// Step 9: Adjusted follower density downstream of a passing lane
+    /// (Equations 15-36 to 15-38).
+    ///
+    /// Measured from the nearest passing lane at or upstream of `seg_num`.
+    /// On the passing lane segment itself this solves for and records the
+    /// effective length `l_de`; on a later segment within that length it
+    /// returns the adjusted density. A passing lane in the first segment has
+    /// no upstream segment to take its entering percent followers from, so
+    /// it produces no adjustment.
+    ///
+    /// # Returns
+    ///
+    /// Adjusted follower density in followers/mi/ln, or 0.0 when the segment
+    /// is not adjusted.
     pub fn determine_adjustment_to_follower_density(&mut self, seg_num: usize) -> f64 {
-        let seg_len = self.segments.len();
-        let mut is_pl_list: Vec<bool> = Vec::new();
         let s = self.segments[seg_num].get_avg_speed();
-        let mut pl_loc = 100;
         let pass_type = self.segments[seg_num].get_passing_type();
 
-        for s_num in 0..seg_len {
-            let p_type = self.segments[s_num].get_passing_type();
-            if p_type == 2 {
-                is_pl_list.push(true);
-                pl_loc = s_num; // TODO: if there are more than three PL section
-            } else {
-                is_pl_list.push(false);
-            }
-        }
+        // Nearest passing lane at or upstream of this segment. Each passing
+        // lane re-solves `l_de` when the facility pass reaches it, so the
+        // segments behind a second passing lane are measured from that one.
+        let pl_loc = match (0..=seg_num).rev().find(|&i| self.segments[i].get_passing_type() == 2) {
+            Some(pl_loc) if pl_loc > 0 => pl_loc,
+            _ => return 0.0,
+        };
 
         // Accumulate segments length from PL on upstream
         let mut l_d: f64 = 0.0;
-        if pl_loc <= seg_num {
-            for s_num in pl_loc..seg_num + 1 {
-                l_d += self.segments[s_num].get_length();
-            }
+        for s_num in pl_loc..seg_num + 1 {
+            l_d += self.segments[s_num].get_length();
         }
 
         // Calculate downstream distance from start of passing lane
         let mut fd_adj: f64 = 0.0;
         let pf = self.segments[seg_num].get_percent_followers();
 
-        if seg_num > 0 && is_pl_list.iter().filter(|&&x| x).count() > 0 {
-            // let pf_u = self.segments[seg_num-1].get_percent_followers();
-            let pf_u = self.segments[pl_loc - 1].get_percent_followers();
-            let vd = self.segments[seg_num].get_flow_rate();
-            let vd_u = self.segments[seg_num - 1].get_flow_rate();
-            let _fd_u = self.segments[seg_num - 1].get_followers_density();
-            let l_de: f64; // effective distance
-
-            let x_2 = 0.1 * f64::max(0.0, pf_u - 30.0);
-            let x_3a = 3.5 * f64::ln(f64::max(0.3, self.segments[pl_loc].get_length()));
-            let x_3b = 0.75 * self.segments[pl_loc].get_length();
-
-            // Determine effective distance of PL
-            if pass_type == 2 {
-                let x_4a = 0.01 * vd_u;
-                let x_4b = 0.005 * vd_u;
-                let y_1a = 27.0 + x_2 + x_3a - x_4a;
-                let y_2a = 3.0 + x_2 + x_3b - x_4b;
-                let _y_3 =
-                    (95.0 * self.segments[seg_num - 1].get_followers_density() * s) / (pf_u * vd_u);
-
-                // Solve for downstream effective length of passing lane from start of PL (LDE)
-                // The percentage improvement to the percent followers becomes zero
-                let l_de_1 = f64::exp(y_1a / 8.75);
-
-                // Follower density is at least 95% of the level entering the passing lane
-                let l_de_2 = f64::max(
-                    0.1,
-                    f64::exp(-1.0 * (f64::max(0.0, -1.0 * y_1a + 32.0) - 27.0) / 8.75),
-                );
+        // let pf_u = self.segments[seg_num-1].get_percent_followers();
+        let pf_u = self.segments[pl_loc - 1].get_percent_followers();
+        let vd = self.segments[seg_num].get_flow_rate();
+        let vd_u = self.segments[pl_loc - 1].get_flow_rate();
+        let _fd_u = self.segments[pl_loc - 1].get_followers_density();
+        let l_de: f64; // effective distance
+
+        let x_2 = 0.1 * f64::max(0.0, pf_u - 30.0);
+        let x_3a = 3.5 * f64::ln(f64::max(0.3, self.segments[pl_loc].get_length()));
+        let x_3b = 0.75 * self.segments[pl_loc].get_length();
+
+        // Determine effective distance of PL
+        if pass_type == 2 {
+            let x_4a = 0.01 * vd_u;
+            let x_4b = 0.005 * vd_u;
+            let y_1a = 27.0 + x_2 + x_3a - x_4a;
+            let y_2a = 3.0 + x_2 + x_3b - x_4b;
+            let _y_3 =
+                (95.0 * self.segments[seg_num - 1].get_followers_density() * s) / (pf_u * vd_u);
+
+            // Solve for downstream effective length of passing lane from start of PL (LDE)
+            // The percentage improvement to the percent followers becomes zero
+            let l_de_1 = f64::exp(y_1a / 8.75);
+
+            // Follower density is at least 95% of the level entering the passing lane
+            let l_de_2 = f64::max(
+                0.1,
+                f64::exp(-1.0 * (f64::max(0.0, -1.0 * y_1a + 32.0) - 27.0) / 8.75),
+            );
 
-                l_de = math::round_up_to_n_decimal(f64::min(l_de_1, l_de_2), 1);
-                self.l_de = Some(l_de);
+            l_de = math::round_up_to_n_decimal(f64::min(l_de_1, l_de_2), 1);
+            self.l_de = Some(l_de);
 
-                let pf_improve = f64::max(0.0, y_1a - 8.75 * f64::ln(f64::max(0.1, l_de)));
-                let s_improve = f64::max(0.0, y_2a - 0.8 * l_de);
-                let _y_3 = (100.0 - pf_improve) / (100.0 + s_improve);
+            let pf_improve = f64::max(0.0, y_1a - 8.75 * f64::ln(f64::max(0.1, l_de)));
+            let s_improve = f64::max(0.0, y_2a - 0.8 * l_de);
+            let _y_3 = (100.0 - pf_improve) / (100.0 + s_improve);
 
-                fd_adj = (pf_u / 100.0) * (1.0 - pf_improve / 100.0) * vd_u
-                    / (s * (1.0 + s_improve / 100.0));
-                // fd_adj = (pf_u / 100.0) * (1.0 - pf_improve / 100.0) * vd_u / (58.8 * (1.0 + s_improve / 100.0));
-            } else {
-                // Determine adjustment to follower density
-                // if segment is within effective distance of neaest upstream passing lane
-                // Passing Lane itself can also be placed within the effective length
-
-                if l_d < self.l_de.unwrap_or(0.0) {
-                    let x_1a = 8.75 * f64::ln(f64::max(0.1, l_d));
-                    let x_1b = 0.8 * l_d;
-                    let x_4c = 0.01 * self.segments[seg_num].get_flow_rate();
-                    let x_4d = 0.005 * self.segments[seg_num].get_flow_rate();
-                    let y_1b = 27.0 - x_1a + x_2 + x_3a - x_4c;
-                    let y_2b = 3.0 - x_1b + x_2 + x_3b - x_4d;
-                    let pf_improve = math::round_up_to_n_decimal(f64::max(0.0, y_1b), 1);
-                    let s_improve = math::round_up_to_n_decimal(f64::max(0.0, y_2b), 1);
-
-                    fd_adj = math::round_up_to_n_decimal(pf, 1) / 100.0
-                        * (1.0 - pf_improve / 100.0)
-                        * math::round_to_significant_digits(vd, 3)
-                        / (math::round_up_to_n_decimal(s, 1) * (1.0 + s_improve / 100.0));
-                }
+            fd_adj = (pf_u / 100.0) * (1.0 - pf_improve / 100.0) * vd_u
+                / (s * (1.0 + s_improve / 100.0));
+            // fd_adj = (pf_u / 100.0) * (1.0 - pf_improve / 100.0) * vd_u / (58.8 * (1.0 + s_improve / 100.0));
+        } else {
+            // Determine adjustment to follower density
+            // if segment is within effective distance of neaest upstream passing lane
+            // Passing Lane itself can also be placed within the effective length
+
+            if l_d < self.l_de.unwrap_or(0.0) {
+                let x_1a = 8.75 * f64::ln(f64::max(0.1, l_d));
+                let x_1b = 0.8 * l_d;
+                let x_4c = 0.01 * self.segments[seg_num].get_flow_rate();
+                let x_4d = 0.005 * self.segments[seg_num].get_flow_rate();
+                let y_1b = 27.0 - x_1a + x_2 + x_3a - x_4c;
+                let y_2b = 3.0 - x_1b + x_2 + x_3b - x_4d;
+                let pf_improve = math::round_up_to_n_decimal(f64::max(0.0, y_1b), 1);
+                let s_improve = math::round_up_to_n_decimal(f64::max(0.0, y_2b), 1);
+
+                fd_adj = math::round_up_to_n_decimal(pf, 1) / 100.0
+                    * (1.0 - pf_improve / 100.0)
+                    * math::round_to_significant_digits(vd, 3)
+                    / (math::round_up_to_n_decimal(s, 1) * (1.0 + s_improve / 100.0));
             }
         }
         fd_adj
@@ -2242,21 +1916,10 @@ impl TwoLaneHighways {
         let mut weighted = 0.0;
         let mut total_length = 0.0;
 
-        for seg_num in 0..self.segments.len() {
-            // Called on every segment, including passing lanes, for the l_de
-            // side effect that the downstream segments depend on.
-            let fd_adj = self.determine_adjustment_to_follower_density(seg_num);
-            let segment = &self.segments[seg_num];
-            let fd = if segment.get_passing_type() == 2 {
-                segment.get_followers_density_mid()
-            } else if fd_adj > 0.0 {
-                fd_adj
-            } else {
-                segment.get_followers_density()
-            };
-
-            weighted += fd * segment.get_length();
-            total_length += segment.get_length();
+        for (seg_num, (_, fd)) in self.equation_15_39_densities().into_iter().enumerate() {
+            let length = self.segments[seg_num].get_length();
+            weighted += fd * length;
+            total_length += length;
         }
 
         if total_length > 0.0 {
@@ -2266,6 +1929,28 @@ impl TwoLaneHighways {
         }
     }
 
+    /// Step 9 adjustment and Equation 15-39 term for every segment, in
+    /// order: `(fd_adj, fd_i)`, where `fd_adj` is 0.0 outside the effective
+    /// length of an upstream passing lane.
+    fn equation_15_39_densities(&mut self) -> Vec<(f64, f64)> {
+        (0..self.segments.len())
+            .map(|seg_num| {
+                // Called on every segment, including passing lanes, for the
+                // l_de side effect that the downstream segments depend on.
+                let fd_adj = self.determine_adjustment_to_follower_density(seg_num);
+                let segment = &self.segments[seg_num];
+                let fd = if segment.get_passing_type() == 2 {
+                    segment.get_followers_density_mid()
+                } else if fd_adj > 0.0 {
+                    fd_adj
+                } else {
+                    segment.get_followers_density()
+                };
+                (fd_adj, fd)
+            })
+            .collect()
+    }
+
     /// Step 11: Determine facility-level Level of Service (Equation 15-39).
     ///
     /// For multi-segment facility analysis, converts a facility follower
@@ -2331,6 +2016,571 @@ impl TwoLaneHighways {
 
         los
     }
+
+    /// Runs Chapter 15 Steps 1 to 11 over every segment and returns the
+    /// segment and facility measures.
+    ///
+    /// This is the sequence in the struct-level workflow, in the order the
+    /// steps depend on each other: Steps 1 to 8 segment by segment, then
+    /// the Step 9 passing-lane adjustment in a second pass, since it needs
+    /// the percent followers upstream of each passing lane and the
+    /// effective length recorded when the pass reaches it.
+    ///
+    /// Segment LOS (Step 10) is read from the same density Equation 15-39
+    /// aggregates: FD_PLmid on a passing lane, the adjusted density within
+    /// a passing lane's effective length, the Step 8 density elsewhere. That
+    /// is the density column Exhibits 26-27 and 26-36 grade. Demand above
+    /// capacity is LOS F for the segment and for the facility.
+    ///
+    /// The facility average speed is the space-mean speed, total length
+    /// over total travel time. Exhibit 15-6 splits on posted speed limit
+    /// and Step 11 defines no facility limit, so the facility LOS uses the
+    /// length-weighted posted limit, which is the common value when every
+    /// segment shares one.
+    ///
+    /// The computed values are also stored on the segments, as the
+    /// individual step methods do. Any effective passing lane length left
+    /// from an earlier analysis is discarded first, so repeated calls give
+    /// the same result.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let result = highway.analyze();
+    /// println!("FD {:.1}, LOS {}", result.follower_density, result.los);
+    /// for seg in &result.segments {
+    ///     println!("{}: {:.1} mi/h, LOS {}", seg.index + 1, seg.avg_speed, seg.los);
+    /// }
+    /// ```
+    pub fn analyze(&mut self) -> TwoLaneFacilityResult {
+        self.analyze_adjusted(|_, _| (1.0, 1.0))
+    }
+
+    /// [`Self::analyze`] with a capacity and a speed adjustment factor on
+    /// each segment, as the reliability scenarios apply them.
+    ///
+    /// `factors(seg_num, ffs)` returns the segment's `(CAF, SAF)` given its
+    /// Step 4 FFS. The CAF scales the Step 2 capacity, rounded to the
+    /// nearest veh/h, and the SAF scales the FFS that Steps 5 to 8 read.
+    /// The reported `capacity` and `ffs` are the adjusted ones.
+    pub fn analyze_adjusted(
+        &mut self,
+        factors: impl Fn(usize, f64) -> (f64, f64),
+    ) -> TwoLaneFacilityResult {
+        let mut segments = Vec::with_capacity(self.segments.len());
+        for seg_num in 0..self.segments.len() {
+            let (min_length, max_length) = self.identify_vertical_class(seg_num);
+            let (flow_rate, flow_rate_opposing, capacity) = self.determine_demand_flow(seg_num);
+            let vertical_class = self.determine_vertical_alignment(seg_num);
+            let ffs = self.determine_free_flow_speed(seg_num);
+            let (caf, saf) = factors(seg_num, ffs);
+            let capacity = (f64::from(capacity) * caf).round().max(1.0) as i32;
+            let ffs = ffs * saf;
+            self.segments[seg_num].set_capacity(capacity);
+            self.segments[seg_num].set_ffs(ffs);
+            let (avg_speed, horizontal_class) = self.estimate_average_speed(seg_num);
+            let percent_followers = self.estimate_percent_followers(seg_num);
+            let (follower_density, follower_density_mid) =
+                if self.segments[seg_num].get_passing_type() == 2 {
+                    let (fd, fd_mid) = self.determine_follower_density_pl(seg_num);
+                    (fd, Some(fd_mid))
+                } else {
+                    (self.determine_follower_density_pc_pz(seg_num), None)
+                };
+
+            let segment = &self.segments[seg_num];
+            segments.push(TwoLaneSegmentResult {
+                index: seg_num,
+                passing_type: segment.get_passing_type(),
+                length: segment.get_length(),
+                min_length,
+                max_length,
+                vertical_class,
+                horizontal_class,
+                flow_rate,
+                flow_rate_opposing,
+                capacity,
+                vc_ratio: flow_rate / capacity as f64,
+                ffs,
+                avg_speed,
+                percent_followers,
+                follower_density,
+                follower_density_mid,
+                adjusted_follower_density: None,
+                los_follower_density: follower_density,
+                los: LevelOfService::F,
+            });
+        }
+
+        self.l_de = None;
+        for (seg, (fd_adj, fd)) in segments.iter_mut().zip(self.equation_15_39_densities()) {
+            if seg.passing_type != 2 && fd_adj > 0.0 {
+                seg.adjusted_follower_density = Some(fd_adj);
+            }
+            seg.los_follower_density = fd;
+            let spl = self.segments[seg.index].get_spl();
+            seg.los = if seg.flow_rate > seg.capacity as f64 {
+                LevelOfService::F
+            } else {
+                self.determine_facility_los(fd, spl).into()
+            };
+        }
+
+        let total_length: f64 = segments.iter().map(|s| s.length).sum();
+        let travel_time_h: f64 = segments.iter().map(|s| s.length / s.avg_speed).sum();
+        let follower_density = if total_length > 0.0 {
+            segments.iter().map(|s| s.los_follower_density * s.length).sum::<f64>() / total_length
+        } else {
+            0.0
+        };
+        let (average_speed, posted_speed_limit) = if total_length > 0.0 {
+            let spl = self.segments.iter().map(|s| s.get_spl() * s.get_length()).sum::<f64>();
+            (total_length / travel_time_h, spl / total_length)
+        } else {
+            (0.0, 0.0)
+        };
+        let over_capacity = segments.iter().any(|s| s.flow_rate > s.capacity as f64);
+        let los = if over_capacity {
+            LevelOfService::F
+        } else {
+            self.determine_facility_los(follower_density, posted_speed_limit).into()
+        };
+
+        TwoLaneFacilityResult {
+            total_length,
+            travel_time_h,
+            average_speed,
+            follower_density,
+            posted_speed_limit,
+            over_capacity,
+            los,
+            segments,
+        }
+    }
+}
+
+/// Exhibit 15-10 minimum and maximum segment lengths, mi, for a vertical
+/// class (1-5) and passing type (0 = PC, 1 = PZ, 2 = PL). Step 1 of
+/// [`TwoLaneHighways::identify_vertical_class`].
+pub fn segment_length_limits(vc: i32, pt: usize) -> (f64, f64) {
+    let mut _min = 0.0;
+    let mut _max = 0.0;
+    if (vc == 1) || (vc == 2) {
+        if pt == 0 {
+            _min = 0.25;
+            _max = 3.0;
+        } else if pt == 1 {
+            _min = 0.25;
+            _max = 2.0;
+        } else if pt == 2 {
+            _min = 0.5;
+            _max = 3.0;
+        }
+    } else if vc == 3 {
+        if pt == 0 {
+            _min = 0.25;
+            _max = 1.1;
+        } else if pt == 1 {
+            _min = 0.25;
+            _max = 1.1;
+        } else if pt == 2 {
+            _min = 0.5;
+            _max = 1.1;
+        }
+    } else if (vc == 4) || (vc == 5) {
+        if pt == 0 {
+            _min = 0.5;
+            _max = 3.0;
+        } else if pt == 1 {
+            _min = 0.5;
+            _max = 2.0;
+        } else if pt == 2 {
+            _min = 0.5;
+            _max = 3.0;
+        }
+    };
+    (_min, _max)
+}
+
+/// Exhibit 15-22 horizontal class (0-5) of a curve of radius `rad` ft and
+/// superelevation `sup_ele` percent, 0 for a tangent. Step 5 of
+/// [`TwoLaneHighways::estimate_average_speed`].
+pub fn horizontal_class(rad: f64, sup_ele: f64) -> i32 {
+    let mut hor_class = 0;
+    if rad == 0.0 {
+        hor_class = 0;
+    } else if rad > 0.0 && rad < 300.0 {
+        hor_class = 5;
+    } else if rad >= 300.0 && rad < 450.0 {
+        hor_class = 4;
+    } else if rad >= 450.0 && rad < 600.0 {
+        if sup_ele < 1.0 {
+            hor_class = 4
+        } else {
+            hor_class = 3
+        };
+    } else if rad >= 600.0 && rad < 750.0 {
+        if sup_ele < 6.0 {
+            hor_class = 3
+        } else {
+            hor_class = 2
+        };
+    } else if rad >= 750.0 && rad < 900.0 {
+        hor_class = 2;
+    } else if rad >= 900.0 && rad < 1050.0 {
+        if sup_ele < 8.0 {
+            hor_class = 2
+        } else {
+            hor_class = 1
+        };
+    } else if rad >= 1050.0 && rad < 1200.0 {
+        if sup_ele < 4.0 {
+            hor_class = 2
+        } else {
+            hor_class = 1
+        };
+    } else if rad >= 1200.0 && rad < 1350.0 {
+        if sup_ele < 2.0 {
+            hor_class = 2
+        } else {
+            hor_class = 1
+        };
+    } else if rad >= 1350.0 && rad < 1500.0 {
+        hor_class = 1;
+    } else if rad >= 1500.0 && rad < 1750.0 {
+        if sup_ele < 8.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 1750.0 && rad < 1800.0 {
+        if sup_ele < 6.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 1800.0 && rad < 1950.0 {
+        if sup_ele < 5.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 1950.0 && rad < 2100.0 {
+        if sup_ele < 4.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 2100.0 && rad < 2250.0 {
+        if sup_ele < 3.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 2250.0 && rad < 2400.0 {
+        if sup_ele < 2.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 2400.0 && rad < 2550.0 {
+        if sup_ele < 1.0 {
+            hor_class = 1
+        } else {
+            hor_class = 0
+        };
+    } else if rad >= 2550.0 {
+        hor_class = 0;
+    }
+    hor_class
+}
+
+/// Exhibit 15-11 vertical alignment class (1-5) of a segment `seg_length`
+/// mi long at `seg_grade` percent, negative for a downgrade. Step 3 of
+/// [`TwoLaneHighways::determine_vertical_alignment`].
+pub fn vertical_alignment_class(seg_length: f64, seg_grade: f64) -> i32 {
+    let ver_align: i32;
+
+    if seg_grade >= 0.0 {
+        if seg_length <= 0.1 {
+            if seg_grade <= 7.0 {
+                ver_align = 1
+            } else {
+                ver_align = 2
+            };
+        } else if seg_length > 0.1 && seg_length <= 0.2 {
+            if seg_grade <= 4.0 {
+                ver_align = 1
+            } else if seg_grade <= 7.0 {
+                ver_align = 2
+            } else {
+                ver_align = 3
+            };
+        } else if seg_length > 0.2 && seg_length <= 0.3 {
+            if seg_grade <= 3.0 {
+                ver_align = 1
+            } else if seg_grade <= 5.0 {
+                ver_align = 2
+            } else if seg_grade <= 7.0 {
+                ver_align = 3
+            } else if seg_grade <= 9.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.3 && seg_length <= 0.4 {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 4.0 {
+                ver_align = 2
+            } else if seg_grade <= 6.0 {
+                ver_align = 3
+            } else if seg_grade <= 7.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.4 && seg_length <= 0.5 {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 4.0 {
+                ver_align = 2
+            } else if seg_grade <= 5.0 {
+                ver_align = 3
+            } else if seg_grade <= 6.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.5 && seg_length <= 0.6 {
+            // HCM Exhibit 15-11 row >0.5-0.6 mi (upgrades)
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 3.0 {
+                ver_align = 2
+            } else if seg_grade <= 5.0 {
+                ver_align = 3
+            } else if seg_grade <= 6.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.6 && seg_length <= 0.7 {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 3.0 {
+                ver_align = 2
+            } else if seg_grade <= 4.0 {
+                ver_align = 3
+            } else if seg_grade <= 6.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.7 && seg_length <= 1.1 {
+            // HCM Exhibit 15-11 rows >0.7-0.8 through >1.0-1.1 mi share these
+            // upgrade thresholds; the previous chain skipped >0.7-0.8 entirely.
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 3.0 {
+                ver_align = 2
+            } else if seg_grade <= 4.0 {
+                ver_align = 3
+            } else if seg_grade <= 5.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 3.0 {
+                ver_align = 2
+            } else if seg_grade <= 5.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        }
+    } else {
+        // Downgrades: Exhibit 15-11 parenthesized values, looked up with the
+        // grade magnitude. (Previously the LENGTH was negated instead, which
+        // sent every downgrade into the first length bucket with an
+        // always-true grade test, so all downgrades returned class 1.)
+        let seg_grade = -1.0 * seg_grade;
+        if seg_length <= 0.1 {
+            if seg_grade <= 8.0 {
+                ver_align = 1
+            } else {
+                ver_align = 2
+            };
+        } else if seg_length > 0.1 && seg_length <= 0.2 {
+            if seg_grade <= 5.0 {
+                ver_align = 1
+            } else if seg_grade <= 8.0 {
+                ver_align = 2
+            } else {
+                ver_align = 3
+            };
+        } else if seg_length > 0.2 && seg_length <= 0.3 {
+            if seg_grade <= 4.0 {
+                ver_align = 1
+            } else if seg_grade <= 6.0 {
+                ver_align = 2
+            } else if seg_grade <= 8.0 {
+                ver_align = 3
+            } else if seg_grade <= 9.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.3 && seg_length <= 0.4 {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 5.0 {
+                ver_align = 2
+            } else if seg_grade <= 6.0 {
+                ver_align = 3
+            } else if seg_grade <= 8.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.4 && seg_length <= 0.5 {
+            if seg_grade <= 3.0 {
+                ver_align = 1
+            } else if seg_grade <= 4.0 {
+                ver_align = 2
+            } else if seg_grade <= 6.0 {
+                ver_align = 3
+            } else if seg_grade <= 7.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.5 && seg_length <= 0.7 {
+            if seg_grade <= 3.0 {
+                ver_align = 1
+            } else if seg_grade <= 4.0 {
+                ver_align = 2
+            } else if seg_grade <= 5.0 {
+                ver_align = 3
+            } else if seg_grade <= 6.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.7 && seg_length <= 0.8 {
+            if seg_grade <= 3.0 {
+                ver_align = 1
+            } else if seg_grade <= 4.0 {
+                ver_align = 3
+            } else if seg_grade <= 6.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.8 && seg_length <= 0.9 {
+            if seg_grade <= 3.0 {
+                ver_align = 1
+            } else if seg_grade <= 4.0 {
+                ver_align = 3
+            } else if seg_grade <= 5.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else if seg_length > 0.9 && seg_length <= 1.1 {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 3.0 {
+                ver_align = 2
+            } else if seg_grade <= 4.0 {
+                ver_align = 3
+            } else if seg_grade <= 5.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        } else {
+            if seg_grade <= 2.0 {
+                ver_align = 1
+            } else if seg_grade <= 3.0 {
+                ver_align = 2
+            } else if seg_grade <= 5.0 {
+                ver_align = 4
+            } else {
+                ver_align = 5
+            };
+        }
+    }
+
+    ver_align
+}
+
+/// Segment measures from [`TwoLaneHighways::analyze`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneSegmentResult {
+    /// Position of the segment in the facility, from 0.
+    pub index: usize,
+    /// Passing type: 0 = PC, 1 = PZ, 2 = PL.
+    pub passing_type: usize,
+    /// Segment length, mi.
+    pub length: f64,
+    /// Step 1 minimum analysis length for the vertical class, mi.
+    pub min_length: f64,
+    /// Step 1 maximum analysis length for the vertical class, mi.
+    pub max_length: f64,
+    /// Step 3 vertical alignment class (1-5).
+    pub vertical_class: i32,
+    /// Step 5 horizontal alignment class (0-5).
+    pub horizontal_class: i32,
+    /// Step 2 demand flow rate in the analysis direction, veh/h.
+    pub flow_rate: f64,
+    /// Step 2 demand flow rate in the opposing direction, veh/h.
+    pub flow_rate_opposing: f64,
+    /// Step 2 capacity, veh/h.
+    pub capacity: i32,
+    /// Demand flow rate over capacity.
+    pub vc_ratio: f64,
+    /// Step 4 free-flow speed, mi/h.
+    pub ffs: f64,
+    /// Step 5 average speed, mi/h.
+    pub avg_speed: f64,
+    /// Step 6 percent followers, %.
+    pub percent_followers: f64,
+    /// Step 8 follower density, followers/mi/ln.
+    pub follower_density: f64,
+    /// Step 7 passing lane midpoint follower density (PL segments only).
+    pub follower_density_mid: Option<f64>,
+    /// Step 9 adjusted follower density, when the segment lies within the
+    /// effective length of an upstream passing lane.
+    pub adjusted_follower_density: Option<f64>,
+    /// Density graded for LOS and aggregated by Equation 15-39.
+    pub los_follower_density: f64,
+    /// Step 10 segment LOS.
+    pub los: LevelOfService,
+}
+
+/// Facility measures from [`TwoLaneHighways::analyze`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoLaneFacilityResult {
+    /// Facility length, mi.
+    pub total_length: f64,
+    /// Travel time over the facility, h.
+    pub travel_time_h: f64,
+    /// Space-mean average speed, mi/h.
+    pub average_speed: f64,
+    /// Equation 15-39 facility follower density, followers/mi/ln.
+    pub follower_density: f64,
+    /// Length-weighted posted speed limit used for Exhibit 15-6, mi/h.
+    pub posted_speed_limit: f64,
+    /// Whether demand exceeds capacity on any segment.
+    pub over_capacity: bool,
+    /// Step 11 facility LOS.
+    pub los: LevelOfService,
+    /// Per-segment results, in facility order.
+    pub segments: Vec<TwoLaneSegmentResult>,
 }
 
 /// Bicycle Level of Service analysis for Two-Lane and Multilane Highways (Section 4, Chapter 15).
@@ -2532,34 +2782,76 @@ impl BicycleLOS {
             + 0.760
     }
 
-    /// Determine Bicycle LOS based on BLOS score (Exhibit 15-7)
-    pub fn determine_bicycle_los(&self) -> char {
-        let blos = self.calculate_blos_score();
-
-        if blos <= 1.5 {
-            'A'
-        } else if blos <= 2.5 {
-            'B'
-        } else if blos <= 3.5 {
-            'C'
-        } else if blos <= 4.5 {
-            'D'
-        } else if blos <= 5.5 {
-            'E'
-        } else {
-            'F'
+    /// Determine Bicycle LOS based on BLOS score (Exhibit 15-7). Fails on
+    /// the inputs [`BicycleLOS::validate`] rejects.
+    pub fn determine_bicycle_los(&self) -> Result<char, String> {
+        self.validate()?;
+        Ok(bicycle_los_from_score(self.calculate_blos_score()))
+    }
+
+    /// Check the inputs Equations 15-40 to 15-47 cannot take.
+    ///
+    /// Equation 15-46 is `ln(Spl - 20)`, so a posted speed at or below
+    /// 20 mi/h has no score; without this check it gives -inf (LOS A) or
+    /// NaN (LOS F) rather than an error. Equations 15-40 and 15-47 divide by
+    /// PHF, the number of lanes and the pavement rating.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.speed_limit.is_nan() || self.speed_limit <= 20.0 {
+            return Err(format!(
+                "bicycle LOS: speed_limit {} mi/h is at or below the 20 mi/h singularity of Equation 15-46",
+                self.speed_limit
+            ));
+        }
+        if self.num_lanes < 1 {
+            return Err("bicycle LOS: num_lanes must be at least 1".into());
         }
+        if self.phf.is_nan() || self.phf <= 0.0 || self.phf > 1.0 {
+            return Err("bicycle LOS: phf must be in (0, 1]".into());
+        }
+        if self.pavement_condition.is_nan() || self.pavement_condition <= 0.0 {
+            return Err("bicycle LOS: pavement_condition must be positive".into());
+        }
+        let non_negative = [
+            ("lane_width", self.lane_width),
+            ("shoulder_width", self.shoulder_width),
+            ("hourly_volume", self.hourly_volume),
+            ("heavy_vehicle_pct", self.heavy_vehicle_pct),
+            ("pct_on_highway_parking", self.pct_on_highway_parking),
+        ];
+        if let Some((name, _)) = non_negative.iter().find(|(_, v)| v.is_nan() || *v < 0.0) {
+            return Err(format!("bicycle LOS: {name} must be non-negative"));
+        }
+        Ok(())
     }
 
-    /// Get all bicycle performance measures
-    pub fn analyze(&self) -> BicycleLOSResult {
-        BicycleLOSResult {
+    /// Get all bicycle performance measures. Fails on the inputs
+    /// [`BicycleLOS::validate`] rejects, so no LOS letter is graded from a
+    /// -inf or NaN score.
+    pub fn analyze(&self) -> Result<BicycleLOSResult, String> {
+        Ok(BicycleLOSResult {
             flow_rate_outside_lane: self.calculate_flow_rate_outside_lane(),
             effective_width: self.calculate_effective_width(),
             effective_speed_factor: self.calculate_effective_speed_factor(),
             blos_score: self.calculate_blos_score(),
-            los: self.determine_bicycle_los(),
-        }
+            los: self.determine_bicycle_los()?,
+        })
+    }
+}
+
+/// Exhibit 15-7 bicycle LOS for a BLOS score.
+pub fn bicycle_los_from_score(blos: f64) -> char {
+    if blos <= 1.5 {
+        'A'
+    } else if blos <= 2.5 {
+        'B'
+    } else if blos <= 3.5 {
+        'C'
+    } else if blos <= 4.5 {
+        'D'
+    } else if blos <= 5.5 {
+        'E'
+    } else {
+        'F'
     }
 }
 
@@ -0,0 +1,219 @@
+//! Minimal CSV reading and writing for the table-based import and export
+//! paths (scenario tables, batch facility records).
+//!
+//! Supports the subset of RFC 4180 those tables need: a header row,
+//! comma-separated fields, double-quoted fields (which may contain commas
+//! and `""` escapes), and `\n` or `\r\n` line endings. Blank lines and
+//! lines starting with `#` are skipped. Fields are trimmed. Quoted fields
+//! spanning several lines are not supported.
+
+/// A parsed CSV table: the header and the data rows, each row carrying
+/// its 1-based line number in the source text for error messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTable {
+    /// Column names from the header row.
+    pub header: Vec<String>,
+    /// `(line number, fields)` for every data row, or the error that
+    /// stopped the line from being split.
+    pub rows: Vec<(usize, Result<Vec<String>, String>)>,
+}
+
+impl CsvTable {
+    /// Parse CSV text. Fails only on an empty table or a header that
+    /// cannot be split. A data line with an unterminated quote is kept
+    /// with its error, and rows with the wrong number of fields are kept
+    /// as they are; both are reported by [`CsvRow`] lookups instead, so a
+    /// caller can reject them one row at a time.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim_end_matches('\r')))
+            .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+        let (header_line, header) = lines.next().ok_or("CSV text has no header row")?;
+        let header = split_line(header).map_err(|e| format!("line {header_line}: {e}"))?;
+        let mut rows = Vec::new();
+        for (n, line) in lines {
+            rows.push((n, split_line(line).map_err(|e| format!("line {n}: {e}"))));
+        }
+        Ok(Self { header, rows })
+    }
+
+    /// Index of a column by case-insensitive name.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header.iter().position(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    /// Fail naming every required column the header lacks.
+    pub fn require_columns(&self, names: &[&str]) -> Result<(), String> {
+        let missing: Vec<&str> = names.iter().copied().filter(|n| self.column(n).is_none()).collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("CSV header is missing column(s): {}", missing.join(", ")))
+        }
+    }
+
+    /// Iterate the data rows with header-aware field lookup.
+    pub fn iter(&self) -> impl Iterator<Item = CsvRow<'_>> {
+        self.rows.iter().map(move |(line, fields)| CsvRow {
+            table: self,
+            line: *line,
+            fields: fields.as_deref().map_err(String::as_str),
+        })
+    }
+}
+
+/// One data row of a [`CsvTable`].
+#[derive(Debug, Clone, Copy)]
+pub struct CsvRow<'a> {
+    table: &'a CsvTable,
+    /// 1-based line number in the source text.
+    pub line: usize,
+    fields: Result<&'a [String], &'a str>,
+}
+
+impl CsvRow<'_> {
+    /// The error that stopped this line from being split into fields, if
+    /// any. Every required and optional lookup on such a row returns it.
+    pub fn error(&self) -> Option<&str> {
+        self.fields.err()
+    }
+
+    /// Raw field by column name; `None` when the column is absent from the
+    /// header, the field is empty or the line could not be split.
+    pub fn get(&self, column: &str) -> Option<&str> {
+        let idx = self.table.column(column)?;
+        self.fields.ok()?.get(idx).map(String::as_str).filter(|f| !f.is_empty())
+    }
+
+    /// Required field by column name.
+    pub fn str(&self, column: &str) -> Result<&str, String> {
+        self.fields?;
+        self.get(column)
+            .ok_or_else(|| format!("line {}: missing value for `{column}`", self.line))
+    }
+
+    /// Required numeric field.
+    pub fn f64(&self, column: &str) -> Result<f64, String> {
+        let raw = self.str(column)?;
+        raw.parse::<f64>()
+            .map_err(|_| format!("line {}: `{column}` is not a number: {raw:?}", self.line))
+    }
+
+    /// Optional numeric field (absent column or empty field gives `None`).
+    pub fn opt_f64(&self, column: &str) -> Result<Option<f64>, String> {
+        self.fields?;
+        match self.get(column) {
+            None => Ok(None),
+            Some(_) => self.f64(column).map(Some),
+        }
+    }
+
+    /// Required non-negative integer field.
+    pub fn usize(&self, column: &str) -> Result<usize, String> {
+        let raw = self.str(column)?;
+        raw.parse::<usize>().map_err(|_| {
+            format!("line {}: `{column}` is not a non-negative integer: {raw:?}", self.line)
+        })
+    }
+
+    /// Optional non-negative integer field.
+    pub fn opt_usize(&self, column: &str) -> Result<Option<usize>, String> {
+        self.fields?;
+        match self.get(column) {
+            None => Ok(None),
+            Some(_) => self.usize(column).map(Some),
+        }
+    }
+}
+
+/// Split one CSV line into trimmed fields.
+fn split_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            (',', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("unterminated quoted field".into());
+    }
+    fields.push(field.trim().to_string());
+    Ok(fields)
+}
+
+/// Quote a field for output when it contains a comma, quote or newline.
+pub fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Join fields into one CSV line (no trailing newline).
+pub fn join_line<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|f| escape_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_header_quotes_and_skips_comments() {
+        let text = "# comment\nid, name ,value\r\n1,\"a, b\",2.5\n\n2,\"say \"\"hi\"\"\",\n";
+        let t = CsvTable::parse(text).unwrap();
+        assert_eq!(t.header, vec!["id", "name", "value"]);
+        let rows: Vec<_> = t.iter().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 3);
+        assert_eq!(rows[0].get("NAME"), Some("a, b"));
+        assert_eq!(rows[0].f64("value").unwrap(), 2.5);
+        assert_eq!(rows[1].get("name"), Some("say \"hi\""));
+        assert_eq!(rows[1].opt_f64("value").unwrap(), None);
+        assert!(rows[1].f64("value").unwrap_err().contains("line 5"));
+    }
+
+    #[test]
+    fn an_unterminated_quote_fails_only_its_own_row() {
+        let t = CsvTable::parse("id,value\n1,\"open\n2,3\n").unwrap();
+        let rows: Vec<_> = t.iter().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].error(), Some("line 2: unterminated quoted field"));
+        assert_eq!(rows[0].get("id"), None);
+        assert_eq!(rows[0].str("id").unwrap_err(), "line 2: unterminated quoted field");
+        assert!(rows[0].opt_f64("value").is_err());
+        assert_eq!(rows[1].error(), None);
+        assert_eq!(rows[1].f64("value").unwrap(), 3.0);
+        assert!(CsvTable::parse("\"id,value\n1,2\n").is_err());
+    }
+
+    #[test]
+    fn escapes_round_trip() {
+        let line = join_line(&["plain", "a,b", "q\"q"]);
+        assert_eq!(line, "plain,\"a,b\",\"q\"\"q\"");
+        assert_eq!(split_line(&line).unwrap(), vec!["plain", "a,b", "q\"q"]);
+        assert!(split_line("\"open").is_err());
+        let t = CsvTable::parse("a,b\n").unwrap();
+        assert!(t.require_columns(&["a", "c"]).unwrap_err().contains('c'));
+    }
+}
@@ -1,8 +1,10 @@
 //! Cross-cutting utility modules: geometry, network topology, traffic-flow
-//! fundamentals, semantic validation, and the core facility abstractions.
+//! fundamentals, semantic validation, CSV table I/O, and the core facility
+//! abstractions.
 //! These are not tied to a single HCM chapter.
 
 pub mod constraints;
+pub mod csv;
 pub mod geometric;
 pub mod topology;
 pub mod traffic_flow;
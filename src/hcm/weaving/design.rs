@@ -0,0 +1,347 @@
+//! Design analysis for weaving segments: the shortest length and the lane count that deliver a
+//! target LOS for given demands.
+//!
+//! Chapter 13 is operational. Chapter 27 Example Problem 4 shows the design use: fix the demands,
+//! try a configuration and a geometry, and change them until the target LOS is met. This module
+//! runs that loop over every candidate configuration, lane count and HCM edition. For each, it
+//! scans short lengths L_S from `min_length` in steps of `length_step`, up to but not including the
+//! Equation 13-4 maximum weaving length, and reports the shortest one that meets the target.
+//!
+//! Lengths are scanned rather than bisected because neither edition's density is guaranteed to
+//! fall monotonically with L_S: Equation 13-13 raises the nonweaving lane-changing rate with
+//! length. The reported length is therefore a grid point, at most one step above the true minimum.
+//!
+//! At L_S >= L_MAX, Chapter 13 says the segment does not operate as a weave and its merge and
+//! diverge are analyzed separately under Chapter 14. A candidate whose L_MAX is at or below
+//! `min_length` is flagged for that. One that misses the target at every length below L_MAX is
+//! still a weave, and reports the longest length it tried instead of a design.
+//!
+//! VERIFY-HCM: Edition 7.1 prints no maximum weaving length of its own in the pages this library
+//! implements, so Equation 13-4 bounds the Edition 7.1 search too.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hcm::common::length_search::{
+    self, both_editions, default_length_step, default_target, length_grid, validate_search,
+};
+use crate::hcm::common::{HcmVersion, LevelOfService};
+
+use super::weaving::{WeavingSegment, WeavingType};
+
+/// The configuration inputs of a weaving segment (Chapter 13, Step 3), which a design changes by
+/// moving lanes and ramp connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeavingConfiguration {
+    pub weaving_type: WeavingType,
+    /// N_WL, 2 or 3 for a one-sided segment. Ignored for a two-sided one.
+    pub num_weaving_lanes: u32,
+    pub lc_rf: u32,
+    pub lc_fr: u32,
+    pub lc_rr: u32,
+    /// Edition 7.1 only.
+    pub nw_rf: u32,
+    /// Edition 7.1 only.
+    pub nw_fr: u32,
+    /// Edition 7.1, two-sided segments only.
+    pub nw_rr: u32,
+}
+
+impl WeavingConfiguration {
+    /// The configuration a segment already carries.
+    pub fn of(segment: &WeavingSegment) -> Self {
+        Self {
+            weaving_type: segment.weaving_type,
+            num_weaving_lanes: segment.num_weaving_lanes,
+            lc_rf: segment.lc_rf,
+            lc_fr: segment.lc_fr,
+            lc_rr: segment.lc_rr,
+            nw_rf: segment.nw_rf,
+            nw_fr: segment.nw_fr,
+            nw_rr: segment.nw_rr,
+        }
+    }
+
+    fn apply(&self, segment: &mut WeavingSegment) {
+        segment.weaving_type = self.weaving_type;
+        segment.num_weaving_lanes = self.num_weaving_lanes;
+        segment.lc_rf = self.lc_rf;
+        segment.lc_fr = self.lc_fr;
+        segment.lc_rr = self.lc_rr;
+        segment.nw_rf = self.nw_rf;
+        segment.nw_fr = self.nw_fr;
+        segment.nw_rr = self.nw_rr;
+    }
+
+    /// Whether the configuration can exist on `lanes` lanes.
+    fn fits(&self, lanes: u32) -> bool {
+        match self.weaving_type {
+            WeavingType::OneSided => {
+                self.num_weaving_lanes <= lanes && self.nw_rf <= lanes && self.nw_fr <= lanes
+            }
+            WeavingType::TwoSided => self.nw_rr <= lanes,
+        }
+    }
+}
+
+fn default_min_length() -> f64 {
+    300.0
+}
+
+/// What to search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeavingDesignSpec {
+    /// Demands, FFS, PHF, heavy vehicles, terrain, facility type, interchange density and
+    /// adjustment factors. Its length, lane count and edition are replaced by the search, and its
+    /// configuration too when `configurations` is given.
+    pub template: WeavingSegment,
+    /// Worst acceptable LOS, A to E.
+    #[serde(default = "default_target")]
+    pub target_los: LevelOfService,
+    /// Editions to design under.
+    #[serde(default = "both_editions")]
+    pub versions: Vec<HcmVersion>,
+    /// Candidate configurations. Empty means the template's own.
+    #[serde(default)]
+    pub configurations: Vec<WeavingConfiguration>,
+    /// Candidate lane counts N. A configuration is not tried on fewer lanes than it needs.
+    pub lanes: Vec<u32>,
+    /// Shortest L_S to try (ft).
+    #[serde(default = "default_min_length")]
+    pub min_length: f64,
+    /// Step between the lengths tried (ft).
+    #[serde(default = "default_length_step")]
+    pub length_step: f64,
+}
+
+/// Operational results at one length.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeavingDesignPoint {
+    /// Short length L_S (ft).
+    pub length_short: f64,
+    /// Density (pc/mi/ln). Infinite where Edition 7.1 has no speed.
+    pub density: f64,
+    /// Average speed of all vehicles (mi/h).
+    pub speed: Option<f64>,
+    /// v/c (7th Edition, Equation 13-10) or d/c (Edition 7.1, Equation 13-20).
+    pub vc_ratio: Option<f64>,
+    pub los: LevelOfService,
+}
+
+/// One candidate: an edition, a configuration and a lane count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeavingDesignCandidate {
+    pub version: HcmVersion,
+    /// Index into the spec's `configurations`, or 0 for the template's.
+    pub configuration: usize,
+    pub num_lanes: u32,
+    /// Equation 13-4 maximum weaving length (ft).
+    pub l_max: f64,
+    /// Shortest length meeting the target.
+    pub design: Option<WeavingDesignPoint>,
+    /// The longest length tried, when no length below L_MAX meets the target.
+    pub target_not_reached: Option<WeavingDesignPoint>,
+    /// L_MAX is at or below `min_length`, so every length tried has L_S >= L_MAX and the ramps
+    /// are to be analyzed as a separate merge and diverge (Chapter 14).
+    pub merge_diverge: bool,
+}
+
+/// Every candidate, in the order editions, configurations, lane counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeavingDesignResult {
+    pub target_los: LevelOfService,
+    pub configurations: Vec<WeavingConfiguration>,
+    pub candidates: Vec<WeavingDesignCandidate>,
+}
+
+impl WeavingDesignResult {
+    /// The candidates that meet the target as a weave.
+    pub fn feasible(&self) -> impl Iterator<Item = &WeavingDesignCandidate> {
+        self.candidates.iter().filter(|c| c.design.is_some())
+    }
+}
+
+impl WeavingDesignSpec {
+    /// Run the search.
+    pub fn search(&self) -> Result<WeavingDesignResult, String> {
+        self.validate()?;
+        let configurations = self.candidate_configurations();
+
+        let mut candidates = Vec::new();
+        for &version in &self.versions {
+            for (index, configuration) in configurations.iter().enumerate() {
+                for &lanes in &self.lanes {
+                    if !configuration.fits(lanes) {
+                        continue;
+                    }
+                    candidates.push(self.candidate(version, index, configuration, lanes));
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Err("no configuration fits any of the candidate lane counts".to_string());
+        }
+
+        Ok(WeavingDesignResult { target_los: self.target_los, configurations, candidates })
+    }
+
+    fn candidate_configurations(&self) -> Vec<WeavingConfiguration> {
+        if self.configurations.is_empty() {
+            vec![WeavingConfiguration::of(&self.template)]
+        } else {
+            self.configurations.clone()
+        }
+    }
+
+    fn candidate(
+        &self,
+        version: HcmVersion,
+        configuration: usize,
+        config: &WeavingConfiguration,
+        lanes: u32,
+    ) -> WeavingDesignCandidate {
+        let mut segment = self.template.clone();
+        config.apply(&mut segment);
+        segment.num_lanes = lanes;
+        segment.version = version;
+        // Equation 13-4 depends on VR and N_WL only, so one evaluation serves every length.
+        segment.determine_demand_flow();
+        let l_max = segment.determine_max_weaving_length();
+
+        let mut result = WeavingDesignCandidate {
+            version,
+            configuration,
+            num_lanes: lanes,
+            l_max,
+            design: None,
+            target_not_reached: None,
+            merge_diverge: l_max <= self.min_length,
+        };
+        for length in length_grid(self.min_length, self.length_step).take_while(|&l| l < l_max) {
+            let point = evaluate(&segment, length);
+            if point.los <= self.target_los {
+                result.design = Some(point);
+                result.target_not_reached = None;
+                return result;
+            }
+            result.target_not_reached = Some(point);
+        }
+        result
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        validate_search(
+            self.target_los,
+            &self.versions,
+            self.min_length,
+            self.length_step,
+            self.template.phf,
+        )?;
+        if self.lanes.is_empty() {
+            return Err("at least one candidate lane count is needed".to_string());
+        }
+        if let Some(n) = self.lanes.iter().find(|&&n| n < 2) {
+            return Err(format!("a weaving segment has at least two lanes, got {n}"));
+        }
+        for (i, c) in self.candidate_configurations().iter().enumerate() {
+            if c.weaving_type == WeavingType::OneSided && !(2..=3).contains(&c.num_weaving_lanes) {
+                return Err(format!(
+                    "configuration {i}: a one-sided segment has N_WL of 2 or 3 (Equation 13-7), got {}",
+                    c.num_weaving_lanes
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse from JSON, for the Python binding.
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        length_search::from_json(s)
+    }
+}
+
+/// Run `segment`'s edition at `length`.
+fn evaluate(segment: &WeavingSegment, length: f64) -> WeavingDesignPoint {
+    let mut seg = segment.clone();
+    seg.length_short = length;
+    match seg.version {
+        HcmVersion::V7 => {
+            let los = seg.run_analysis();
+            WeavingDesignPoint {
+                length_short: length,
+                density: seg.get_density(),
+                speed: seg.speed_avg,
+                vc_ratio: seg.vc_ratio,
+                los,
+            }
+        }
+        HcmVersion::V7_1 => {
+            let a = seg.analyze_v7_1();
+            WeavingDesignPoint {
+                length_short: length,
+                density: a.density,
+                speed: a.speed_avg,
+                vc_ratio: a.dc_ratio,
+                los: a.los,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> WeavingDesignSpec {
+        WeavingDesignSpec {
+            template: WeavingSegment::default(),
+            target_los: LevelOfService::C,
+            versions: vec![HcmVersion::V7],
+            configurations: Vec::new(),
+            lanes: vec![3, 4],
+            min_length: 300.0,
+            length_step: 50.0,
+        }
+    }
+
+    #[test]
+    fn the_weave_design_is_the_shortest_length_below_l_max() {
+        let r = spec().search().unwrap();
+        assert_eq!(r.candidates.len(), 2);
+        for c in &r.candidates {
+            let d = c.design.expect("the default demands reach LOS C on three or four lanes");
+            assert!(d.los <= LevelOfService::C && d.length_short < c.l_max);
+            assert!(!c.merge_diverge && c.target_not_reached.is_none());
+            if d.length_short > 300.0 {
+                let shorter = evaluate(
+                    &WeavingSegment { num_lanes: c.num_lanes, ..WeavingSegment::default() },
+                    d.length_short - 50.0,
+                );
+                assert!(shorter.los > LevelOfService::C);
+            }
+        }
+        // A fourth lane never needs a longer segment here.
+        let len = |i: usize| r.candidates[i].design.unwrap().length_short;
+        assert!(len(1) <= len(0));
+    }
+
+    #[test]
+    fn a_short_l_max_flags_merge_diverge() {
+        let r = WeavingDesignSpec { min_length: 20_000.0, ..spec() }.search().unwrap();
+        assert!(r.feasible().next().is_none());
+        assert!(r.candidates.iter().all(|c| c.merge_diverge && c.target_not_reached.is_none()));
+    }
+
+    #[test]
+    fn a_target_missed_below_l_max_and_bad_weaving_inputs() {
+        let r = WeavingDesignSpec { target_los: LevelOfService::A, lanes: vec![2], ..spec() }
+            .search()
+            .unwrap();
+        let c = &r.candidates[0];
+        assert!(!c.merge_diverge && c.design.is_none());
+        assert!(c.target_not_reached.unwrap().length_short < c.l_max);
+        assert!(WeavingDesignSpec { target_los: LevelOfService::F, ..spec() }.search().is_err());
+        assert!(WeavingDesignSpec { lanes: vec![1], ..spec() }.search().is_err());
+        assert!(WeavingDesignSpec { length_step: 0.0, ..spec() }.search().is_err());
+    }
+}
@@ -3,8 +3,10 @@
 //! Two editions of this chapter are implemented. [`weaving`] holds the 7th Edition methodology and
 //! [`v7_1`] the Edition 7.1 replacement chapter (November 2025, NCHRP Research Report 1038). A
 //! [`WeavingSegment`](weaving::WeavingSegment) carries the edition it should be analyzed under; see
-//! [`crate::hcm::common::HcmVersion`].
+//! [`crate::hcm::common::HcmVersion`]. [`design`] searches configuration, length and lane count
+//! for a target LOS under either edition.
 
+pub mod design;
 pub mod v7_1;
 pub mod weaving;
 
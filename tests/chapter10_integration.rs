@@ -478,7 +478,12 @@ fn ep2_queue_lifecycle() {
 // Example Problem 5: managed-lane facility (Exhibits 25-78 through 25-87)
 // ═════════════════════════════════════════════════════════════════════════
 
-use transportations_library::hcm::freeway_facilities::managed_lanes::ManagedLaneFacility;
+use transportations_library::hcm::freeway_facilities::managed_lanes::{
+    ManagedLaneFacility, MlRampJunction,
+};
+use transportations_library::hcm::merge_diverge::merge_diverge::{
+    RampLanes, RampSegment, RampSide, RampType,
+};
 
 fn load_ml_case(name: &str) -> ManagedLaneFacility {
     let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -600,6 +605,62 @@ fn ep5_facility_performance_matches_exhibit_25_87() {
     }
 }
 
+/// Example Problem 5 with a direct-access on-ramp into the managed lane at
+/// Segment 3 and a direct-access off-ramp at Segment 9, both from the median.
+/// Each junction is the Chapter 14 engine run on the ML lane group, and the
+/// rest of the facility is analyzed end to end around it.
+#[test]
+fn ep5_direct_access_ml_ramps() {
+    let mut fac = load_ml_case("ml_case1.json");
+    let ramp_demand = vec![300.0; 5];
+    let merge_seg = fac.ml[2].as_mut().unwrap();
+    merge_seg.on_ramp_demand = ramp_demand.clone();
+    merge_seg.ramp = Some(MlRampJunction { ramp_type: RampType::OnRamp, ..Default::default() });
+    let diverge_seg = fac.ml[8].as_mut().unwrap();
+    diverge_seg.off_ramp_demand = ramp_demand;
+    diverge_seg.ramp = Some(MlRampJunction { ramp_type: RampType::OffRamp, ..Default::default() });
+    fac.run_analysis().unwrap();
+
+    let entry = [1000.0, 1100.0, 1160.0, 1040.0, 840.0];
+    for (p, &entry) in entry.iter().enumerate() {
+        assert_approx(fac.ml_demand[1][p], entry, 1e-9, "ML demand upstream of the on-ramp");
+        assert_approx(fac.ml_demand[2][p], entry + 300.0, 1e-9, "ML demand at the on-ramp");
+        assert_approx(fac.ml_demand[8][p], entry, 1e-9, "ML demand at the off-ramp");
+        for i in 0..11 {
+            assert_eq!(fac.ml_ramp[i][p].is_some(), i == 2 || i == 8, "ramp results seg {}", i + 1);
+        }
+
+        // The merge reproduces a standalone Chapter 14 run on a one-lane
+        // left-hand on-ramp to the 60 mi/h managed lane.
+        let mut merge = RampSegment {
+            ramp_type: RampType::OnRamp,
+            ramp_side: RampSide::Left,
+            ramp_lanes: RampLanes::OneLane,
+            freeway_lanes: 1,
+            freeway_ffs: 60.0,
+            ramp_ffs: 45.0,
+            accel_lane_length: Some(800.0),
+            freeway_demand: entry,
+            ramp_demand: 300.0,
+            phf: fac.gp.phf,
+            heavy_vehicle_pct: fac.gp.heavy_vehicle_pct,
+            ..Default::default()
+        };
+        let los = merge.run_analysis().unwrap();
+        let r = fac.ml_ramp[2][p].as_ref().unwrap();
+        assert_approx(r.influence_density_pc, merge.get_density(), 1e-9, "merge D_R");
+        assert_approx(r.ramp_capacity_pc, 2100.0, 1e-9, "ramp roadway capacity");
+        // The ML checkpoint is against the ML capacity, as the segment d/c is.
+        assert_approx(r.ml_dc, fac.ml_dc_ratio[2][p], 1e-6, "ML d/c at the merge");
+        assert_eq!(r.los, los);
+        assert_eq!(fac.ml_los[2][p], los);
+        assert!(fac.ml_speed[2][p] <= merge.get_speed_avg() + 1e-9);
+    }
+    // The ML group is still under capacity at the peak.
+    assert!(!fac.ml_ramp[2][2].as_ref().unwrap().demand_exceeds_capacity);
+    assert_ne!(fac.ml_group_performance[2].los, LevelOfService::F);
+}
+
 // ═════════════════════════════════════════════════════════════════════════
 // Example Problem 6: planning-level method (Exhibits 25-88 through 25-96)
 // ═════════════════════════════════════════════════════════════════════════
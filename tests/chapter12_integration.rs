@@ -29,7 +29,14 @@
 
 use transportations_library::math;
 use transportations_library::basicfreeways::BasicFreeways;
+use transportations_library::basicfreeways::managed_lanes::{
+    ManagedLaneType, PairedManagedLaneSegment,
+};
 use transportations_library::basicfreeways::mixed_flow::MixedFlowSegment;
+use transportations_library::basicfreeways::multilane::{
+    MultilaneFacility, MultilaneSegment, MultilaneTerrain,
+};
+use transportations_library::common::MedianType;
 use transportations_library::common::LevelOfService;
 
 use std::fs::{self, File};
@@ -476,6 +483,46 @@ fn test_ch26_ep7_managed_lane_case2_gp_friction() {
     assert_eq!(los, LevelOfService::E, "ML Case 2 LOS");
 }
 
+/// Example Problem 7 end to end through `PairedManagedLaneSegment`, from the
+/// `ep7_case{1,2}.json` fixtures: both lane groups' demands go in, the general purpose
+/// density comes out of Equation 12-1, and Equation 12-18 switches on by itself in Case 2.
+/// Same published values and tolerances as the two hand-chained tests above.
+#[test]
+fn ch26_ep7_paired_managed_lane_fixtures() {
+    // (fixture, GP v_p, GP S, GP D, GP LOS, friction, ML S, ML D, ML LOS)
+    let cases = [
+        ("ep7_case1.json", 1169.0, 60.0, 19.5, LevelOfService::C, false, 56.3, 27.0, LevelOfService::D),
+        ("ep7_case2.json", 2221.0, 53.0, 41.9, LevelOfService::E, true, 41.9, 36.3, LevelOfService::E),
+    ];
+
+    for (name, gp_v_p, gp_s, gp_d, gp_los, friction, ml_s, ml_d, ml_los) in cases {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/ExampleCases/hcm/Chapter26");
+        path.push(name);
+        let f = File::open(&path).unwrap_or_else(|_| panic!("Unable to open {path:?}"));
+        let seg: PairedManagedLaneSegment =
+            serde_json::from_reader(BufReader::new(f)).expect("Failed to parse fixture JSON");
+        let r = seg.analyze().expect(name);
+
+        assert_eq!(ManagedLaneType::ContinuousAccess, r.lane_type, "{name} type");
+        assert_approx(r.f_hv, 0.93, 0.005, &format!("{name} f_HV"));
+        assert_approx(r.gp.capacity_adj, 2300.0, 1e-9, &format!("{name} GP capacity"));
+        assert_approx(r.gp.breakpoint, 1600.0, 1e-9, &format!("{name} GP breakpoint"));
+        assert_approx(r.gp.v_p, gp_v_p, 1.0, &format!("{name} GP v_p"));
+        assert_approx(r.gp.speed.unwrap(), gp_s, 0.1, &format!("{name} GP S"));
+        assert_approx(r.gp.density.unwrap(), gp_d, 0.1, &format!("{name} GP D"));
+        assert_eq!(gp_los, r.gp.los, "{name} GP LOS");
+
+        assert_eq!(friction, r.friction_active, "{name} I_c");
+        assert_approx(r.ml.capacity_adj, 1650.0, 1e-9, &format!("{name} ML capacity"));
+        assert_approx(r.ml.breakpoint, 500.0, 1e-9, &format!("{name} ML breakpoint"));
+        assert_approx(r.ml.v_p, 1519.0, 1.0, &format!("{name} ML v_p"));
+        assert_approx(r.ml.speed.unwrap(), ml_s, 0.1, &format!("{name} ML S"));
+        assert_approx(r.ml.density.unwrap(), ml_d, 0.1, &format!("{name} ML D"));
+        assert_eq!(ml_los, r.ml.los, "{name} ML LOS");
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Chapter 26, Example Problems 4-6
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -571,6 +618,58 @@ fn ch26_ep4_five_lane_highway_with_twltl() {
     }
 }
 
+/// Example Problem 4 again, through the typed `MultilaneSegment`, with each direction as a
+/// one-segment `MultilaneFacility`. The typed model takes the TWLTL's left clearance as 6 ft
+/// itself, so no clearance is given, and it keeps f_HV unrounded, hence the same v_p and
+/// density tolerances as above.
+#[test]
+fn ch26_ep4_typed_multilane_facility() {
+    let direction = |grade: f64, access_point_density: f64| MultilaneSegment {
+        length: 1.25,
+        lanes: 2,
+        bffs: None,
+        speed_limit: Some(45.0),
+        lane_width: 12.0,
+        lc_right: 6.0,
+        lc_left: 0.0,
+        median: MedianType::TWLTL,
+        access_point_density,
+        demand: 1500.0,
+        phf: 0.90,
+        heavy_vehicle_pct: 0.06,
+        terrain: MultilaneTerrain::SpecificGrade { grade, sut_percentage: 30 },
+        saf: 1.0,
+        caf: 1.0,
+    };
+    // (label, grade, APD, FFS, capacity, E_T, f_HV, v_p, density)
+    let cases = [
+        ("EB downgrade", -3.5, 10.0, 49.5, 1990.0, 2.24, 0.93, 896.0, 18.1),
+        ("WB upgrade", 3.5, 0.0, 52.0, 2040.0, 3.97, 0.85, 980.0, 18.8),
+    ];
+
+    for (name, grade, apd, ffs, capacity, e_t, f_hv, v_p, density) in cases {
+        let facility = MultilaneFacility { segments: vec![direction(grade, apd)] };
+        let r = facility.analyze().expect(name);
+        let seg = &r.segments[0];
+
+        assert_approx(seg.bffs, 52.0, 1e-9, &format!("{name} BFFS (mi/h)"));
+        assert_approx(seg.total_lateral_clearance, 12.0, 1e-9, &format!("{name} TLC (ft)"));
+        assert_approx(seg.ffs, ffs, 0.05, &format!("{name} FFS (mi/h)"));
+        assert_approx(seg.capacity, capacity, 0.5, &format!("{name} capacity (pc/h/ln)"));
+        assert_approx(seg.e_t, e_t, 1e-9, &format!("{name} E_T"));
+        assert_approx(seg.f_hv, f_hv, 0.005, &format!("{name} f_HV"));
+        assert_approx(seg.v_p, v_p, 2.0, &format!("{name} v_p (pc/h/ln)"));
+        assert_approx(seg.speed.unwrap(), ffs, 0.05, &format!("{name} S (mi/h)"));
+        assert_approx(seg.density.unwrap(), density, 0.1, &format!("{name} D (pc/mi/ln)"));
+        assert_eq!(LevelOfService::C, seg.los, "{name} LOS");
+
+        // One segment: the facility is that segment.
+        assert_approx(r.speed.unwrap(), seg.speed.unwrap(), 1e-9, &format!("{name} facility S"));
+        assert_approx(r.density.unwrap(), seg.density.unwrap(), 1e-9, &format!("{name} facility D"));
+        assert_eq!(seg.los, r.los, "{name} facility LOS");
+    }
+}
+
 /// HCM Chapter 26, Example Problem 5, the "Comparison with the PCE-Based Approach" half.
 ///
 /// The mixed-flow half of this example (Steps 2 through 8, Equations 26-1 through 26-16) is
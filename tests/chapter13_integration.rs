@@ -13,7 +13,8 @@ use std::path::PathBuf;
 use transportations_library::hcm::weaving::weaving::{
     cross_weave_gp_capacity, service_flow_rate_ideal, service_volumes, DemandSplit, WeavingSegment,
 };
-use transportations_library::hcm::common::LevelOfService;
+use transportations_library::hcm::common::{HcmVersion, LevelOfService};
+use transportations_library::hcm::weaving::design::{WeavingConfiguration, WeavingDesignSpec};
 
 fn load_case(name: &str) -> WeavingSegment {
     let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -329,6 +330,44 @@ fn example_problem_7_ml_access_downstream_offramp() {
     assert_approx(cw.c_gpa, 4750.0, 5.0, "c_GPA (pc/h)");
 }
 
+/// Example Problem 4 as a design search rather than two hand-built trials. Both trials'
+/// configurations go in at the published five lanes with a LOS C target. Trial 1 has no design
+/// at any length below L_MAX: the Equation 13-7 weaving-flow limit does not depend on length, so
+/// it stays at LOS F while still operating as a weave. Trial 2 meets LOS C at
+/// the published 1,000 ft or shorter.
+#[test]
+fn example_problem_4_design_search() {
+    let template = load_case("case4b.json");
+    let trial_2 = WeavingConfiguration::of(&template);
+    let trial_1 = WeavingConfiguration { num_weaving_lanes: 2, lc_fr: 2, ..trial_2 };
+    let spec = WeavingDesignSpec {
+        template,
+        target_los: LevelOfService::C,
+        versions: vec![HcmVersion::V7],
+        configurations: vec![trial_1, trial_2],
+        lanes: vec![5],
+        min_length: 300.0,
+        length_step: 50.0,
+    };
+    let r = spec.search().unwrap();
+    assert_eq!(r.candidates.len(), 2);
+
+    let t1 = &r.candidates[0];
+    assert!(t1.design.is_none());
+    assert!(!t1.merge_diverge, "Trial 1 L_MAX is above the lengths tried");
+    let longest = t1.target_not_reached.expect("Trial 1 misses the target below L_MAX");
+    assert_eq!(longest.los, LevelOfService::F);
+    assert!(longest.vc_ratio.unwrap() > 1.0);
+    assert!(longest.length_short < t1.l_max);
+
+    let t2 = &r.candidates[1];
+    assert_approx(t2.l_max, 5391.0, 5.0, "Trial 2 L_MAX (ft)");
+    let design = t2.design.expect("Trial 2 reaches LOS C");
+    assert!(design.length_short <= 1000.0, "design length {}", design.length_short);
+    assert!(design.density <= 28.0);
+    assert!(!t2.merge_diverge && t2.target_not_reached.is_none());
+}
+
 /// Round a flow rate down to the nearest 100, matching the HCM's service-volume
 /// presentation convention (Chapter 27, Example Problem 5).
 fn round_down_100(x: f64) -> f64 {
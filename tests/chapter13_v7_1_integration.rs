@@ -10,6 +10,7 @@
 //! away from the printed totals.
 
 use transportations_library::hcm::common::{HcmVersion, LevelOfService};
+use transportations_library::hcm::weaving::design::{WeavingConfiguration, WeavingDesignSpec};
 use transportations_library::hcm::weaving::v7_1::WeavingClass;
 use transportations_library::hcm::weaving::weaving::{TerrainType, WeavingSegment, WeavingType};
 
@@ -252,6 +253,55 @@ fn example_problem_4_trial_2_complex_0_1() {
     assert_eq!(los, LevelOfService::C);
 }
 
+/// Example Problem 4 under Edition 7.1 as a design search, both trials' configurations on five
+/// lanes with a LOS C target. The published 1,320 ft gives Trial 1 LOS D and Trial 2 LOS C, so
+/// the search puts Trial 2's shortest length at or below 1,320 ft and Trial 1's above it. That
+/// Trial 1 reaches LOS C at all, by lengthening instead of adding the exit lane, is derived from
+/// Equation 13-9 and is not a published result.
+#[test]
+fn example_problem_4_design_search() {
+    let template = WeavingSegment {
+        weaving_type: WeavingType::OneSided,
+        length_short: 1320.0,
+        num_lanes: 5,
+        ffs: 60.0,
+        v_ff: 2000.0,
+        v_fr: 1450.0,
+        v_rf: 1500.0,
+        v_rr: 1750.0,
+        phf: 1.00,
+        heavy_vehicle_pct: 0.0,
+        terrain: TerrainType::Level,
+        lc_rf: 0,
+        lc_fr: 1,
+        nw_rf: 2,
+        nw_fr: 1,
+        num_weaving_lanes: 3,
+        ..Default::default()
+    };
+    let trial_2 = WeavingConfiguration::of(&template);
+    let trial_1 = WeavingConfiguration { num_weaving_lanes: 2, lc_fr: 2, nw_fr: 0, ..trial_2 };
+    let spec = WeavingDesignSpec {
+        template,
+        target_los: LevelOfService::C,
+        versions: vec![HcmVersion::V7_1],
+        configurations: vec![trial_1, trial_2],
+        lanes: vec![5],
+        min_length: 300.0,
+        length_step: 10.0,
+    };
+    let r = spec.search().unwrap();
+
+    let t1 = r.candidates[0].design.expect("Trial 1 reaches LOS C below L_MAX");
+    let t2 = r.candidates[1].design.expect("Trial 2 reaches LOS C");
+    assert!(t1.length_short > 1320.0, "Trial 1 length {}", t1.length_short);
+    assert!(t2.length_short <= 1320.0, "Trial 2 length {}", t2.length_short);
+    for d in [t1, t2] {
+        assert_eq!(d.los, LevelOfService::C);
+        assert!(d.density <= 25.0);
+    }
+}
+
 /// Chapter 28's Example Problem 3 discussion (pp. 28-16 to 28-18) re-analyzes its merge/diverge
 /// pair as a weaving segment, formed by connecting the two ramps with an auxiliary lane. It is a
 /// Chapter 13 problem carried in the Chapter 28 text, and it is the only published Edition 7.1
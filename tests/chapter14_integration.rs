@@ -13,7 +13,8 @@ use transportations_library::hcm::merge_diverge::merge_diverge::{
     ramp_service_flow_rate_ideal, ramp_service_volumes, AdjacentRampType, RampLanes, RampSegment,
     RampSide, RampType, ServiceDemandBasis, TerrainType,
 };
-use transportations_library::hcm::common::LevelOfService;
+use transportations_library::hcm::common::{HcmVersion, LevelOfService};
+use transportations_library::hcm::merge_diverge::design::RampDesignSpec;
 
 fn load_case(name: &str) -> RampSegment {
     let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -385,3 +386,30 @@ fn example_problem_5_case2_fixed_freeway_demand() {
     assert_approx(sf_e, 1878.0, 3.0, "SF LOS E (veh/h)");
     assert_approx(sv_e, 1633.0, 3.0, "SV LOS E (veh/h)");
 }
+
+/// Example Problem 1 inverted: the acceleration lane that brings the on-ramp from LOS D
+/// (28.2 pc/mi/ln at 740 ft) to LOS C. Equation 14-22 loses 0.00627 pc/mi/ln per foot, so the
+/// published 740 ft is about 30 ft short.
+#[test]
+fn example_problem_1_acceleration_lane_design() {
+    let spec = RampDesignSpec {
+        template: load_case("case1.json"),
+        target_los: LevelOfService::C,
+        target_vc: None,
+        versions: vec![HcmVersion::V7],
+        ramp_lanes: Vec::new(),
+        min_length: 500.0,
+        max_length: 1500.0,
+        length_step: 10.0,
+    };
+    let r = spec.search().unwrap();
+    let c = &r.candidates[0];
+    assert_eq!(c.curve.len(), 101);
+    let at_740 = c.curve.iter().find(|p| p.length == 740.0).unwrap();
+    assert_approx(at_740.density, 28.2, 0.5, "D_R at 740 ft (pc/mi/ln)");
+    assert_eq!(at_740.los, LevelOfService::D);
+
+    let design = c.design.expect("LOS C is reachable");
+    assert_approx(design.length, 770.0, 1e-9, "design L_A (ft)");
+    assert!(design.density <= 28.0);
+}
@@ -6,6 +6,7 @@
 //! and f_HV to three decimals.
 
 use transportations_library::hcm::common::{HcmVersion, LevelOfService};
+use transportations_library::hcm::merge_diverge::design::RampDesignSpec;
 use transportations_library::hcm::merge_diverge::merge_diverge::{
     RampLanes, RampSegment, RampSide, RampType, TerrainType,
 };
@@ -362,3 +363,48 @@ fn major_merge_under_capacity_has_no_seventh_edition_los() {
     assert!(los.is_some(), "Edition 7.1 defines LOS for a major merge");
     assert_eq!(seg.get_los(), los);
 }
+
+/// Example Problem 1 inverted under Edition 7.1. At 740 ft the on-ramp is LOS E (32.1 pc/mi/ln,
+/// d/c 0.94). No acceleration lane reaches LOS D: the merge impedance falls toward zero with
+/// length, so density falls only toward the equivalent basic segment's (v/N)/S_b, about
+/// 29.8 pc/mi/ln. A d/c target is reachable.
+#[test]
+fn example_problem_1_acceleration_lane_design() {
+    let template = RampSegment {
+        ramp_type: RampType::OnRamp,
+        ramp_lanes: RampLanes::OneLane,
+        freeway_lanes: 2,
+        freeway_ffs: 60.0,
+        ramp_ffs: 45.0,
+        freeway_demand: 2500.0,
+        ramp_demand: 535.0,
+        phf: 0.90,
+        heavy_vehicle_pct: 0.05,
+        terrain: TerrainType::Level,
+        ..Default::default()
+    };
+    let spec = RampDesignSpec {
+        template,
+        target_los: LevelOfService::D,
+        target_vc: None,
+        versions: vec![HcmVersion::V7_1],
+        ramp_lanes: Vec::new(),
+        min_length: 500.0,
+        max_length: 3000.0,
+        length_step: 10.0,
+    };
+    let r = spec.search().unwrap();
+    let c = &r.candidates[0];
+    let at_740 = c.curve.iter().find(|p| p.length == 740.0).unwrap();
+    approx(at_740.density, 32.1, 0.1, "D_M at 740 ft");
+    assert_eq!(at_740.los, LevelOfService::E);
+    assert!(c.design.is_none());
+    let longest = c.curve.last().unwrap();
+    assert_eq!(longest.length, 3000.0);
+    assert!(longest.density > 1771.0 / 59.47);
+
+    let spec = RampDesignSpec { target_los: LevelOfService::E, target_vc: Some(0.93), ..spec };
+    let d = spec.search().unwrap().candidates[0].design.expect("d/c 0.93 is reachable");
+    approx(d.length, 910.0, 1e-9, "design L_A (ft)");
+    assert!(d.vc_ratio.unwrap() <= 0.93);
+}
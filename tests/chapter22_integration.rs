@@ -2,12 +2,20 @@
 //! against the published answers of HCM Chapter 33, Example Problems 1
 //! and 2.
 //!
+//! The same fixtures are also run through the N-leg model
+//! (`MultiLegRoundabout`), which must reproduce the published answers, and
+//! Example Problem 2 through the lane-use design search.
+//!
 //! Tolerances: LOS exact; control delays within +-0.5 s/veh; capacities
 //! within +-5 veh/h.
 
 use std::fs;
 
-use transportations_library::hcm::roundabouts::roundabouts::Roundabouts;
+use transportations_library::hcm::roundabouts::design::{LaneUseDesignSpec, LaneUseObjective};
+use transportations_library::hcm::roundabouts::multileg::{MultiLegRoundabout, RoundaboutLeg};
+use transportations_library::hcm::roundabouts::roundabouts::{
+    BypassType, LaneAssignment, RoundaboutApproach, Roundabouts,
+};
 
 const DELAY_TOL: f64 = 0.5;
 const CAPACITY_TOL: f64 = 5.0;
@@ -129,3 +137,162 @@ fn test_roundabout_fixture_roundtrip() {
     let back = Roundabouts::from_json(&json).unwrap();
     assert_eq!(back.intersection_delay, r.intersection_delay);
 }
+
+/// Both Example Problems converted to the N-leg model (legs in circulating
+/// order NB, WB, SB, EB) reproduce the published entry capacities and
+/// delays: EP1 c_NB = 597, c_WB = 694, c_bypass,WB = 851 veh/h, intersection
+/// 17.5 s LOS C; EP2 c_SB,L = 651, c_SB,R = 723 veh/h, intersection 12.3 s
+/// LOS B.
+#[test]
+fn test_roundabout_example_problems_as_n_leg() {
+    let mut m = load("case1").to_multileg();
+    m.analyze().unwrap();
+    let nb = m.leg_index("NB").unwrap();
+    let wb = m.leg_index("WB").unwrap();
+    assert_close(m.legs[nb].approach.lanes[0].capacity_veh, 597.0, CAPACITY_TOL, "c_NB");
+    assert_close(m.legs[nb].approach.lanes[0].control_delay, 22.6, DELAY_TOL, "d_NB");
+    assert_close(m.legs[wb].approach.lanes[0].capacity_veh, 694.0, CAPACITY_TOL, "c_WB");
+    let bypass = m.legs[wb].approach.bypass_lane.as_ref().unwrap();
+    assert_close(bypass.capacity_veh, 851.0, CAPACITY_TOL, "c_bypass,WB");
+    assert_close(m.intersection_delay.unwrap(), 17.5, DELAY_TOL, "d_I");
+    assert_eq!(m.intersection_los.unwrap(), 'C');
+
+    let mut m = load("case2").to_multileg();
+    m.analyze().unwrap();
+    let sb = &m.legs[m.leg_index("SB").unwrap()].approach;
+    assert_close(sb.lanes[0].capacity_veh, 651.0, CAPACITY_TOL, "c_SB,L");
+    assert_close(sb.lanes[1].capacity_veh, 723.0, CAPACITY_TOL, "c_SB,R");
+    assert_close(m.intersection_delay.unwrap(), 12.3, DELAY_TOL, "d_I");
+    assert_eq!(m.intersection_los.unwrap(), 'B');
+}
+
+/// Example Problem 1 with the west leg closed: every movement to or from it
+/// is dropped and its share of the other entries' demand removed. A
+/// three-leg roundabout carrying the same O-D flows must give the same
+/// circulating flows and single-lane results as the four-leg model.
+#[test]
+fn test_three_leg_matches_four_leg_with_closed_leg() {
+    let mut four = load("case1");
+    // West leg (EB entry) closed: no EB entry, no NB left, SB right or WB
+    // through (each of which exits on the west leg).
+    four.eb = RoundaboutApproach::default();
+    four.nb.v_l = 0.0;
+    four.sb.v_r = 0.0;
+    four.sb.bypass = Default::default();
+    four.wb.v_t = 0.0;
+    let mut three_from_four = four.to_multileg();
+    four.analyze();
+
+    // Legs in circulating order: south (NB entry), east (WB), north (SB).
+    let legs: Vec<RoundaboutLeg> = three_from_four
+        .legs
+        .drain(..)
+        .filter(|l| l.name != "EB")
+        .collect();
+    let demand = vec![
+        vec![four.nb.v_u, four.nb.v_r, four.nb.v_t],
+        vec![four.wb.v_l, four.wb.v_u, four.wb.v_r],
+        vec![four.sb.v_t, four.sb.v_l, four.sb.v_u],
+    ];
+    let mut three = MultiLegRoundabout::new(legs, demand);
+    three.phf = four.phf;
+    three.analyze().unwrap();
+
+    for (i, a) in [&four.nb, &four.wb, &four.sb].into_iter().enumerate() {
+        let b = &three.legs[i].approach;
+        let what = format!("leg {}", three.legs[i].name);
+        assert_close(
+            b.circulating_flow_pce.unwrap(),
+            a.circulating_flow_pce.unwrap(),
+            1e-9,
+            &what,
+        );
+        assert_close(b.lanes[0].capacity_veh, a.lanes[0].capacity_veh, 1e-9, &what);
+        assert_close(b.lanes[0].control_delay, a.lanes[0].control_delay, 1e-9, &what);
+    }
+    assert_close(
+        three.intersection_delay.unwrap(),
+        four.intersection_delay.unwrap(),
+        1e-9,
+        "d_I",
+    );
+}
+
+/// Lane-use design search on Example Problem 2 without bypass lanes. The
+/// as-built configurations reproduce the published approach delays (NB
+/// 11.8, SB 13.9, EB 15.1, WB 8.3 s), and no entry's best is worse. SB does
+/// best as LTR|R (13.8 s computed) and WB as L|LTR (8.3 s). An LT|R EB
+/// entry (v/c 1.01) and a one-lane SB entry (v/c 1.02) fail the capacity
+/// check. A two-lane NB entry cuts its delay to 9.0 s. Holding the as-built
+/// lane counts, the designed intersection delay is 12.33 s against 12.37 s
+/// computed for the as-built assignments (12.3 s published).
+#[test]
+fn test_roundabout_example_problem_2_lane_use_design() {
+    let template = load("case2").to_multileg();
+    let spec = LaneUseDesignSpec {
+        template: template.clone(),
+        objective: LaneUseObjective::MinDelay,
+        entry_lanes: vec![1, 2],
+        lane_assignments: vec![
+            LaneAssignment::LeftAndThroughRight,
+            LaneAssignment::LeftThroughAndRight,
+            LaneAssignment::LeftThroughAndThroughRight,
+            LaneAssignment::LeftAndAllMovements,
+            LaneAssignment::AllMovementsAndRight,
+        ],
+        bypass_types: vec![BypassType::None],
+        max_vc: 1.0,
+    };
+    let r = spec.search().unwrap();
+
+    for (name, published) in [("NB", 11.8), ("SB", 13.9), ("EB", 15.1), ("WB", 8.3)] {
+        let i = template.leg_index(name).unwrap();
+        let built = &template.legs[i].approach;
+        let e = &r.entries[i];
+        let as_built = e
+            .candidates
+            .iter()
+            .find(|c| {
+                c.entry_lanes == built.entry_lanes
+                    && c.lane_assignment.is_none_or(|a| a == built.lane_assignment)
+            })
+            .unwrap();
+        assert_close(as_built.approach_delay, published, DELAY_TOL, name);
+        let best = e.best_candidate().unwrap();
+        assert!(best.approach_delay <= as_built.approach_delay + 1e-9, "{name}");
+    }
+
+    let sb = &r.entries[template.leg_index("SB").unwrap()];
+    let best = sb.best_candidate().unwrap();
+    assert_eq!(best.lane_assignment, Some(LaneAssignment::AllMovementsAndRight));
+    assert_close(best.approach_delay, 13.8, 0.05, "SB best");
+    let one_lane = sb.candidates.iter().find(|c| c.entry_lanes == 1).unwrap();
+    assert!(!one_lane.feasible && one_lane.max_vc > 1.0);
+
+    let wb = &r.entries[template.leg_index("WB").unwrap()];
+    let best = wb.best_candidate().unwrap();
+    assert_eq!(best.lane_assignment, Some(LaneAssignment::LeftAndAllMovements));
+    assert_close(best.approach_delay, 8.3, 0.05, "WB best");
+
+    let eb = &r.entries[template.leg_index("EB").unwrap()];
+    let lt_r = eb
+        .candidates
+        .iter()
+        .find(|c| c.lane_assignment == Some(LaneAssignment::LeftThroughAndRight))
+        .unwrap();
+    assert!(!lt_r.feasible);
+
+    // With the as-built lane counts (one-lane NB, two-lane elsewhere),
+    // only the assignments change: SB and WB improve.
+    let nb_two_lane = r.entries[template.leg_index("NB").unwrap()].best_candidate().unwrap();
+    assert_eq!(nb_two_lane.entry_lanes, 2);
+    assert_close(nb_two_lane.approach_delay, 9.0, 0.05, "NB two-lane");
+    let r = LaneUseDesignSpec { entry_lanes: Vec::new(), ..spec }.search().unwrap();
+    let nb = &r.entries[template.leg_index("NB").unwrap()];
+    assert_eq!(nb.candidates.len(), 1);
+    let mut as_built = template;
+    as_built.analyze().unwrap();
+    let design = r.design.unwrap();
+    assert_close(design.intersection_delay.unwrap(), 12.33, 0.005, "designed d_I");
+    assert_close(as_built.intersection_delay.unwrap(), 12.37, 0.005, "as-built d_I");
+}
@@ -0,0 +1,32 @@
+"""Python-binding test for bicycle LOS over a facility.
+
+Rebuilds the widening example of `bicycle_widening.json` (BLOS 5.90, LOS F)
+from a two-lane highway through `analyze_bicycle_facility`, mirroring
+tests/twolanehighways_bicycle_facility_test.rs.
+"""
+
+import json
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+
+def two_lane(spl=50.0):
+    seg = {"passing_type": 0, "length": 1.0, "grade": 0.0, "spl": spl, "volume": 500.0,
+           "volume_op": 0.0, "phf": 0.90, "phv": 5.0, "vertical_class": 1, "subsegments": []}
+    return {"segments": [seg], "lane_width": 12.0, "shoulder_width": 2.0}
+
+
+def test_two_lane_facility_reproduces_the_widening_example():
+    payload = {"two_lane": two_lane(), "conditions": [{"pavement_condition": 3.0}]}
+    res = json.loads(tl.analyze_bicycle_facility(json.dumps(payload)))
+    assert res["blos_score"] == pytest.approx(5.90, abs=0.01)
+    assert res["los"] == "F"
+    assert len(res["segments"]) == 1
+
+
+def test_speed_limit_at_20_is_refused():
+    payload = {"two_lane": two_lane(spl=20.0), "conditions": [{"pavement_condition": 3.0}]}
+    with pytest.raises(ValueError, match="20 mi/h"):
+        tl.analyze_bicycle_facility(json.dumps(payload))
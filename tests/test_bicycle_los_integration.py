@@ -167,31 +167,25 @@ def test_heavy_vehicle_share_is_a_decimal_not_a_percent(fixture):
     assert as_percent["blos_score"] > 100.0
 
 
-def test_a_speed_limit_at_or_below_20_returns_a_null_score_with_an_los_letter(fixture):
-    """Known defect, pinned rather than fixed here.
-
-    Equation 15-46 takes ln(Spl - 20), so a posted limit of exactly 20 mi/h gives -inf and
-    anything below gives NaN. Neither is guarded. serde_json writes a non-finite float as
-    `null`, so the score disappears, but `determine_bicycle_los` compares the raw f64 and still
-    returns a letter: -inf sorts under the 1.5 threshold and reports LOS A, the best possible
-    grade, while NaN fails every comparison and falls through to LOS F. A caller reading only
-    the letter sees no sign that anything went wrong.
-
-    Guarding this is a change to the Chapter 15 engine, not to these bindings, and it would move
-    what the web calculator and the MCP server return, so it is Rei's call. The behaviour is
-    pinned here so that a fix has to come past this test rather than silently.
-    """
-    at_20 = json.loads(tl.analyze_bicycle_los(json.dumps(dict(fixture["current"], speed_limit=20.0))))
-    assert at_20["effective_speed_factor"] is None
-    assert at_20["blos_score"] is None
-    assert at_20["los"] == "A"
-
-    below_20 = json.loads(tl.analyze_bicycle_los(json.dumps(dict(fixture["current"], speed_limit=15.0))))
-    assert below_20["blos_score"] is None
-    assert below_20["los"] == "F"
-
-    # Just above the singularity the chain is finite again, which is what makes the two rows
-    # above a domain edge rather than a broken equation.
+def test_a_speed_limit_at_or_below_20_is_refused(fixture):
+    """Equation 15-46 takes ln(Spl - 20), so a posted limit of exactly 20 mi/h gives -inf and
+    anything below gives NaN. Both used to come back as a null score with an LOS letter (A for
+    -inf, F for NaN); `BicycleLOS::analyze` now runs `validate` and refuses them before the chain
+    runs, through every entry point."""
+    for spl in (20.0, 15.0):
+        config = dict(fixture["current"], speed_limit=spl)
+        with pytest.raises(ValueError, match="20 mi/h"):
+            tl.analyze_bicycle_los(json.dumps(config))
+        blos = tl.BicycleLOS(*(config[k] for k in (
+            "lane_width", "shoulder_width", "speed_limit", "num_lanes", "pavement_condition",
+            "hourly_volume", "phf", "heavy_vehicle_pct", "pct_on_highway_parking")))
+        with pytest.raises(ValueError, match="20 mi/h"):
+            blos.analyze()
+        with pytest.raises(ValueError, match="20 mi/h"):
+            blos.determine_bicycle_los()
+
+    # Just above the singularity the chain is finite again, which is what makes 20 and 15 mi/h
+    # a domain edge rather than a broken equation.
     above = json.loads(tl.analyze_bicycle_los(json.dumps(dict(fixture["current"], speed_limit=20.5))))
     assert above["blos_score"] == pytest.approx(3.785, abs=0.001)
 
@@ -7,6 +7,7 @@ match the Rust integration test: speeds +-0.5 mi/h, densities
 +-0.5 veh/mi/ln, exact LOS letters.
 """
 
+import json
 from pathlib import Path
 
 import pytest
@@ -143,3 +144,32 @@ class TestFreewayFacilitiesExampleProblem4:
         for p, s in enumerate(expected):
             tol = 1.2 if p == 4 else 0.6
             assert fac.facility_speed(p) == pytest.approx(s, abs=tol)
+
+
+def _mountain_facility(grades):
+    segments = [
+        {"seg_type": "Basic", "length_ft": length_mi * 5280.0, "lanes": 3,
+         "grade": None if g is None else {"grade": g, "sut_share": 1.0 / 3.0}}
+        for length_mi, g in grades
+    ]
+    return tl.FreewayFacility(json.dumps({
+        "segments": segments, "mainline_demand": [4500.0], "ffs": 65.0,
+        "heavy_vehicle_pct": 0.15, "terrain": "Mountainous",
+    }))
+
+
+class TestSpecificGrades:
+    """Graded basic segments run through the Chapter 25/26 mixed-flow model."""
+
+    def test_composite_grade_speeds(self):
+        fac = _mountain_facility([(1.5, 3.0), (2.0, 2.0), (1.0, 5.0)])
+        fac.run_analysis()
+        speeds = [row[0] for row in fac.speed()]
+        # Steeper and longer grades slow the mixed stream below the FFS.
+        assert all(s < 65.0 for s in speeds)
+        assert speeds[2] < speeds[1]
+
+    def test_mountainous_terrain_without_grades_is_rejected(self):
+        fac = _mountain_facility([(1.0, 4.0), (1.0, None)])
+        with pytest.raises(ValueError, match="no specific grade"):
+            fac.run_analysis()
@@ -91,6 +91,34 @@ class TestFreewayReliabilityExampleProblem7:
         assert "FreewayReliability" in repr(analysis)
 
 
+class TestScenarioSetInterchange:
+    """Saved scenario sets re-evaluate to the same results (versioned JSON
+    file and FREEVAL-style scenario table)."""
+
+    def test_scenario_set_file_round_trip(self, analysis):
+        saved = analysis.scenario_set_file_json()
+        doc = json.loads(saved)
+        assert doc["format"] == "transportations_library/freeway-scenario-set"
+        assert doc["version"] == 1
+        rel = tl.FreewayReliability(CASE1.read_text())
+        rel.run_scenario_set_file(saved)
+        assert rel.num_scenarios == analysis.num_scenarios
+        assert rel.scenario_tti() == analysis.scenario_tti()
+
+    def test_scenario_table_round_trip(self, analysis):
+        table = analysis.export_scenario_table()
+        assert table.splitlines()[0].startswith("scenario,month,weekday")
+        rel = tl.FreewayReliability(CASE1.read_text())
+        rel.run_scenario_table(table)
+        assert rel.num_scenarios == analysis.num_scenarios
+        assert rel.tti_mean() == pytest.approx(analysis.tti_mean(), abs=1e-9)
+
+    def test_foreign_file_is_rejected(self):
+        rel = tl.FreewayReliability(CASE1.read_text())
+        with pytest.raises(ValueError):
+            rel.run_scenario_set_file('{"format": "other", "version": 1}')
+
+
 class TestPlanningLevelReliability:
     """Chapter 25, Example Problem 10 (planning-level; Equations 11-1
     through 11-5): FFS 75 mi/h, peak speed 62 mi/h, 3 lanes, X = 0.95."""
@@ -100,3 +128,15 @@ class TestPlanningLevelReliability:
         assert tti_mean == pytest.approx(1.899, abs=0.001)
         assert tti_95 == pytest.approx(3.353, abs=0.005)
         assert pt45 == pytest.approx(0.743, abs=0.001)
+
+    def test_example_problem_10_as_a_batch(self):
+        csv = (
+            "id,aadt,lanes,ffs,k_factor,d_factor,length_mi,phf,capacity,peak_speed\n"
+            "EP10,76000,3,75,0.1,0.9,1.0,1.0,2400,62\n"
+            "bad,not-a-number,3,75,0.1,0.9,1.0,,,\n"
+        )
+        out = json.loads(tl.planning_reliability_batch(csv))
+        assert len(out["results"]) == 1
+        assert out["results"][0]["tti_mean"] == pytest.approx(1.899, abs=0.001)
+        assert out["results"][0]["unreliable"]
+        assert out["errors"][0]["line"] == 3
@@ -231,3 +231,110 @@ def test_rural_segment_los_reads_exhibit_12_15():
     assert densities["urban"] == pytest.approx(densities["rural"], abs=1e-9)
     assert letters["rural"] == "D"
     assert letters["urban"] == letters["rural"]
+
+
+def _table_spec(**overrides):
+    spec = {
+        "segment": "BasicFreeway",
+        "ffs": [65.0, 70.0],
+        "lanes": [2, 3],
+        "terrains": ["Level", "Rolling"],
+        "heavy_vehicle_pct": 0.10,
+        "phf": 0.94,
+        "factors": [{"k": 0.09, "d": 0.55}],
+    }
+    spec.update(overrides)
+    return spec
+
+
+def test_service_volume_table_reproduces_exhibit_12_37_cells():
+    table = json.loads(tl.service_volume_table(json.dumps(_table_spec())))
+    assert len(table["rows"]) == 2 * 2 * 2
+    row = table["rows"][1]
+    assert (row["terrain"], row["ffs"], row["lanes"]) == ("Level", 65.0, 3)
+    # Exhibit 12-37, FFS 65 mi/h, times three lanes, then Equations 12-24 to 12-26.
+    f_hv = 1.0 / 1.1
+    for level, msf in zip(row["levels"], [710, 1170, 1660, 2060, 2350]):
+        v = level["volumes"]
+        assert v["sfi"] == pytest.approx(3 * msf)
+        assert v["sv"] == pytest.approx(3 * msf * f_hv * 0.94)
+        assert v["dsv"] == pytest.approx(v["sv"] / (0.09 * 0.55))
+
+
+def test_service_volume_table_as_csv():
+    csv = tl.service_volume_table(json.dumps(_table_spec()), csv=True)
+    lines = csv.strip().splitlines()
+    assert lines[0] == "terrain,ffs,lanes,k_factor,d_factor,f_hv,los,sfi,sf,sv,dsv"
+    assert len(lines) == 1 + 8 * 5
+
+
+def test_service_volume_table_refuses_mountainous_terrain():
+    with pytest.raises(ValueError, match="mountainous"):
+        tl.service_volume_table(json.dumps(_table_spec(terrains=["Mountainous"])))
+
+
+def _multilane_segment(**overrides):
+    seg = {
+        "length": 1.25,
+        "lanes": 2,
+        "speed_limit": 45.0,
+        "median": "TWLTL",
+        "demand": 1500.0,
+        "phf": 0.90,
+        "heavy_vehicle_pct": 0.06,
+        "terrain": {"SpecificGrade": {"grade": 3.5, "sut_percentage": 30}},
+    }
+    seg.update(overrides)
+    return seg
+
+
+def test_multilane_facility_reproduces_ep4_westbound():
+    result = json.loads(tl.analyze_multilane_facility(json.dumps({"segments": [_multilane_segment()]})))
+    seg = result["segments"][0]
+    assert seg["ffs"] == pytest.approx(52.0)
+    assert seg["capacity"] == pytest.approx(2040.0)
+    assert seg["e_t"] == pytest.approx(3.97)
+    assert seg["density"] == pytest.approx(18.8, abs=0.1)
+    assert seg["los"] == "C"
+    assert result["los"] == "C"
+
+
+def test_multilane_facility_reports_an_over_capacity_lane_drop():
+    config = {
+        "segments": [
+            _multilane_segment(lanes=3, demand=4000.0, terrain="Level"),
+            _multilane_segment(lanes=2, demand=4000.0, terrain="Level", median="Undivided"),
+        ]
+    }
+    result = json.loads(tl.analyze_multilane_facility(json.dumps(config)))
+    assert result["governing_segment"] == 1
+    assert result["segments"][1]["los"] == "F"
+    assert result["segments"][1]["speed"] is None
+    assert result["speed"] is None
+    assert result["los"] == "F"
+
+
+def test_multilane_facility_rejects_a_single_lane():
+    config = {"segments": [_multilane_segment(lanes=1)]}
+    with pytest.raises(ValueError, match="two lanes"):
+        tl.analyze_multilane_facility(json.dumps(config))
+
+
+@pytest.mark.parametrize(
+    "name, friction, ml_speed, ml_density, ml_los",
+    [("ep7_case1.json", False, 56.3, 27.0, "D"), ("ep7_case2.json", True, 41.9, 36.3, "E")],
+)
+def test_paired_managed_lane_reproduces_ep7(name, friction, ml_speed, ml_density, ml_los):
+    result = json.loads(tl.analyze_paired_managed_lane((CH26 / name).read_text()))
+    assert result["lane_type"] == "ContinuousAccess"
+    assert result["friction_active"] is friction
+    assert result["ml"]["speed"] == pytest.approx(ml_speed, abs=0.1)
+    assert result["ml"]["density"] == pytest.approx(ml_density, abs=0.1)
+    assert result["ml"]["los"] == ml_los
+
+
+def test_paired_managed_lane_refuses_multilane_continuous_access():
+    config = json.loads((CH26 / "ep7_case1.json").read_text())
+    config["ml_lanes"] = 2
+    with pytest.raises(ValueError, match="Exhibit 12-9"):
+        tl.analyze_paired_managed_lane(json.dumps(config))
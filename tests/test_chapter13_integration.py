@@ -162,3 +162,39 @@ class TestCrossWeaveAndServiceVolumes:
         assert sf == pytest.approx(4300.0 * 0.952, abs=0.01)
         assert sv == pytest.approx(sf * 0.93, abs=0.01)
         assert dsv == pytest.approx(sv / (0.08 * 0.55), abs=0.01)
+
+
+class TestDesignSearch:
+    """Design search over configuration, lanes and length (Chapter 27, EP 4)."""
+
+    def _spec(self, **overrides):
+        template = json.loads((CASES / "case4b.json").read_text())
+        trial_2 = {
+            key: template[key]
+            for key in ("weaving_type", "num_weaving_lanes", "lc_rf", "lc_fr", "lc_rr")
+        }
+        trial_2.update(nw_rf=0, nw_fr=0, nw_rr=0)
+        trial_1 = dict(trial_2, num_weaving_lanes=2, lc_fr=2)
+        spec = {
+            "template": template,
+            "target_los": "C",
+            "versions": ["7"],
+            "configurations": [trial_1, trial_2],
+            "lanes": [5],
+        }
+        spec.update(overrides)
+        return json.dumps(spec)
+
+    def test_ep4_trials(self):
+        r = json.loads(tl.design_weaving_segment(self._spec()))
+        trial_1, trial_2 = r["candidates"]
+        assert trial_1["design"] is None
+        assert trial_1["merge_diverge"] is False
+        assert trial_1["target_not_reached"]["los"] == "F"
+        assert trial_2["l_max"] == pytest.approx(5391.0, abs=5.0)
+        assert trial_2["design"]["length_short"] <= 1000.0
+        assert trial_2["design"]["density"] <= 28.0
+
+    def test_target_f_rejected(self):
+        with pytest.raises(ValueError):
+            tl.design_weaving_segment(self._spec(target_los="F"))
@@ -150,3 +150,31 @@ class TestMergeDivergeServiceVolumes:
         seg = self._ep5_segment()
         with pytest.raises(ValueError):
             tl.ramp_service_flow_rate_ideal(seg, 28.0)
+
+
+class TestRampLaneDesign:
+    """Acceleration lane design for Example Problem 1 (740 ft, LOS D)."""
+
+    CASE1 = CASE2.with_name("case1.json")
+
+    def _spec(self, **overrides):
+        spec = {
+            "template": json.loads(self.CASE1.read_text()),
+            "target_los": "C",
+            "versions": ["7"],
+            "min_length": 500.0,
+            "length_step": 10.0,
+        }
+        spec.update(overrides)
+        return json.dumps(spec)
+
+    def test_ep1_design_length(self):
+        r = json.loads(tl.design_ramp_lane(self._spec()))
+        (c,) = r["candidates"]
+        assert len(c["curve"]) == 101
+        assert c["design"]["length"] == pytest.approx(770.0)
+        assert c["design"]["los"] == "C"
+
+    def test_two_lane_needs_second_lane_length(self):
+        with pytest.raises(ValueError):
+            tl.design_ramp_lane(self._spec(ramp_lanes=["TwoLane"]))
@@ -1,7 +1,9 @@
 """Python-binding integration test for HCM Chapter 22 (roundabouts).
 
 Runs the Chapter 33 Roundabout Example Problem 1 fixture through the PyO3
-bindings and checks the published answers.
+bindings and checks the published answers, then runs the same fixture and a
+five-leg roundabout through the N-leg MultiLegRoundabout model, and the
+fixture through the entry lane-use design search.
 """
 
 import json
@@ -77,3 +79,78 @@ class TestRoundaboutExampleProblem1:
     def test_invalid_entry(self, roundabout):
         with pytest.raises(ValueError):
             roundabout.get_lane_result("XX", 0)
+
+
+class TestMultiLegRoundabout:
+    """N-leg roundabout: the four-leg fixture as a special case, and a
+    five-leg roundabout with uniform O-D demand."""
+
+    def test_four_leg_special_case(self, roundabout):
+        multileg = roundabout.to_multileg()
+        multileg.analyze()
+        assert multileg.leg_names == ["NB", "WB", "SB", "EB"]
+        assert multileg.get_circulating_flow_pce("NB") == pytest.approx(
+            roundabout.get_circulating_flow_pce("NB"), abs=1e-9
+        )
+        _, capacity, _, delay, los, _ = multileg.get_bypass_result("WB")
+        assert capacity == pytest.approx(851.0, abs=5.0)
+        assert delay == pytest.approx(20.2, abs=0.5)
+        assert multileg.intersection_delay == pytest.approx(
+            roundabout.intersection_delay, abs=1e-9
+        )
+        assert multileg.intersection_los == "C"
+
+    def test_five_leg_uniform_demand(self):
+        if not hasattr(tl, "MultiLegRoundabout"):
+            pytest.skip("transportations_library built without MultiLegRoundabout")
+        q = 50.0
+        config = {
+            "legs": [{"name": f"leg{i}"} for i in range(5)],
+            "demand": [[0.0 if o == d else q for d in range(5)] for o in range(5)],
+        }
+        multileg = tl.MultiLegRoundabout(json.dumps(config))
+        multileg.analyze()
+        # Each entry is conflicted by 6 O-D pairs; each leg carries 4 exiting.
+        for leg in multileg.leg_names:
+            assert multileg.get_circulating_flow_pce(leg) == pytest.approx(6 * q)
+            assert multileg.get_exiting_flow_pce(leg) == pytest.approx(4 * q)
+        assert multileg.get_lane_result("2", 0)[0] == pytest.approx(4 * q)
+        with pytest.raises(ValueError):
+            multileg.get_lane_result("leg9", 0)
+
+    def test_invalid_demand_matrix(self):
+        if not hasattr(tl, "MultiLegRoundabout"):
+            pytest.skip("transportations_library built without MultiLegRoundabout")
+        config = {"legs": [{"name": "a"}, {"name": "b"}, {"name": "c"}], "demand": [[0.0]]}
+        multileg = tl.MultiLegRoundabout(json.dumps(config))
+        with pytest.raises(ValueError):
+            multileg.analyze()
+
+
+class TestLaneUseDesign:
+    """Entry lane-use design search on the Example Problem 1 fixture."""
+
+    def test_bypass_choices_for_the_wb_entry(self, roundabout):
+        if not hasattr(tl, "design_roundabout_lanes"):
+            pytest.skip("transportations_library built without design_roundabout_lanes")
+        template = json.loads(roundabout.to_multileg().to_json())
+        config = {"template": template, "bypass_types": ["None", "Yielding"]}
+        result = json.loads(tl.design_roundabout_lanes(json.dumps(config)))
+        wb = next(e for e in result["entries"] if e["name"] == "WB")
+        # One lane kept from the template, with and without a bypass
+        assert len(wb["candidates"]) == 2
+        no_bypass, yielding = wb["candidates"]
+        # Without the bypass, 610 veh/h of right turns overload the entry.
+        assert not no_bypass["feasible"]
+        assert yielding["approach_delay"] == pytest.approx(23.3, abs=0.5)
+        assert wb["candidates"][wb["best"]]["bypass"] == "Yielding"
+        assert result["design"]["intersection_delay"] is not None
+
+    def test_invalid_config(self, roundabout):
+        if not hasattr(tl, "design_roundabout_lanes"):
+            pytest.skip("transportations_library built without design_roundabout_lanes")
+        template = json.loads(roundabout.to_multileg().to_json())
+        with pytest.raises(ValueError):
+            tl.design_roundabout_lanes(json.dumps({"template": template, "entry_lanes": [3]}))
+        with pytest.raises(ValueError):
+            tl.design_roundabout_lanes(json.dumps({"template": template, "unknown": 1}))
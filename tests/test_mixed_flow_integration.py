@@ -146,6 +146,39 @@ def test_ep11_speed_is_carried_between_segments(ep11):
     assert ep11["segments"][1]["decelerating"] is False
 
 
+# ── Climbing lane on the Example Problem 11 grade ───────────────────────────
+
+
+def test_climbing_lane_warrants_and_effect():
+    study = {
+        "grade": load("Chapter25", "ep11_composite_grade.json"),
+        "lanes": 3,
+        "climbing_lane": {"start": 3.0, "end": 4.5},
+    }
+    r = json.loads(tl.analyze_climbing_lane(json.dumps(study)))
+    assert r["threshold_speed"] == pytest.approx(55.0)
+    # TTs drop below 55 mi/h on the 3%, recover on the 2%, and drop again on the 5%.
+    assert len(r["warrants"]) == 2
+    assert r["warrants"][-1]["end"] == pytest.approx(4.5)
+    without, with_lane = r["without"]["sections"], r["with"]["sections"]
+    assert [s["climbing_lane"] for s in with_lane] == [False, False, True, True]
+    for w, o in zip(with_lane[2:], without[2:]):
+        assert w["lanes"] == 4
+        assert w["speed"] > o["speed"]
+        assert w["density"] < o["density"]
+    assert r["with"]["speed"] > r["without"]["speed"]
+
+
+def test_climbing_lane_off_the_grade_raises():
+    study = {
+        "grade": load("Chapter25", "ep11_composite_grade.json"),
+        "lanes": 3,
+        "climbing_lane": {"start": 4.0, "end": 5.0},
+    }
+    with pytest.raises(ValueError, match="climbing-lane analysis failed"):
+        tl.analyze_climbing_lane(json.dumps(study))
+
+
 # ── Error surface ────────────────────────────────────────────────────────────
 
 
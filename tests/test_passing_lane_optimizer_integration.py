@@ -0,0 +1,45 @@
+"""Python-binding test for the passing lane placement optimizer.
+
+Takes the passing lane out of Chapter 26 Example Problem 3 (`case3.json`)
+and searches it back in through `optimize_passing_lanes`, mirroring
+tests/twolanehighways_passing_lanes_test.rs.
+"""
+
+import json
+from pathlib import Path
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+CASE3 = Path(__file__).parent / "ExampleCases" / "hcm" / "TwoLaneHighways" / "case3.json"
+
+
+def without_passing_lane():
+    highway = json.loads(CASE3.read_text())
+    highway["segments"][1]["passing_type"] = 0
+    return highway
+
+
+def test_single_lane_budget_ranks_by_follower_density():
+    payload = {
+        "highway": without_passing_lane(),
+        "candidates": [{"segment": 1, "lengths": [0.75, 1.5]},
+                       {"segment": 4, "lengths": [1.0, 1.75]}],
+        "budget": {"Count": 1},
+    }
+    res = json.loads(tl.optimize_passing_lanes(json.dumps(payload)))
+    assert res["evaluated"] == 4
+    fds = [p["result"]["follower_density"] for p in res["plans"]]
+    assert fds == sorted(fds)
+    assert all(p["fd_reduction"] > 0.0 for p in res["plans"])
+
+
+def test_first_segment_is_rejected():
+    payload = {
+        "highway": without_passing_lane(),
+        "candidates": [{"segment": 0, "lengths": [0.5]}],
+        "budget": {"Count": 1},
+    }
+    with pytest.raises(ValueError):
+        tl.optimize_passing_lanes(json.dumps(payload))
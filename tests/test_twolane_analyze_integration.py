@@ -0,0 +1,35 @@
+"""Python-binding test for the one-call Chapter 15 analysis.
+
+Builds Chapter 26 Example Problem 1 (`case1.json`: a 0.75-mi level Passing
+Constrained segment, 752 veh/h, PHF 0.94, 50 mi/h, 5% heavy vehicles) and
+runs `TwoLaneHighways.analyze`, mirroring `analyze_matches_step_by_step_test`
+in tests/twolanehighways_test.rs.
+"""
+
+import json
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+
+def ep1_highway():
+    seg = tl.Segment(0, 0.75, 0.0, 50.0, is_hc=False, volume=752.0, volume_op=0.0,
+                     vertical_class=1, subsegments=[], phf=0.94, phv=5.0, hor_class=0)
+    return tl.TwoLaneHighways([seg], lane_width=12.0, shoulder_width=6.0, apd=0.0,
+                              pmhvfl=0.4, l_de=0.0)
+
+
+def test_example_problem_1():
+    highway = ep1_highway()
+    res = json.loads(highway.analyze())
+    assert len(res["segments"]) == 1
+    seg = res["segments"][0]
+    assert seg["avg_speed"] == pytest.approx(53.7, abs=0.1)
+    assert seg["follower_density"] == pytest.approx(10.1, abs=0.1)
+    assert seg["follower_density_mid"] is None
+    assert seg["los"] == "D"
+    assert res["los"] == "D"
+    assert res["follower_density"] == pytest.approx(seg["follower_density"])
+    # The results are stored on the segments as well.
+    assert highway.segments[0].get_avg_speed() == pytest.approx(seg["avg_speed"])
@@ -0,0 +1,49 @@
+"""Python-binding test for two-lane highway analysis in both directions.
+
+Runs Chapter 26 Example Problem 3 (`case3.json`) as the increasing
+direction of `analyze_two_lane_bidirectional`, mirroring
+tests/twolanehighways_bidirectional_test.rs.
+"""
+
+import json
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+MI = 5280.0
+
+
+def corridor():
+    demand = [(0.0, 850.0, 0.94, 8.0), (0.75, 825.0, 0.95, 8.0), (2.25, 820.0, 0.95, 8.0),
+              (3.25, 800.0, 0.94, 7.5), (3.75, 795.0, 0.935, 8.0)]
+    return {
+        "length_ft": 5.5 * MI,
+        "increasing": {
+            "passing": [{"start_ft": 0.75 * MI, "end_ft": 2.25 * MI, "passing_type": 2},
+                        {"start_ft": 3.25 * MI, "end_ft": 3.75 * MI, "passing_type": 1}],
+            "demand": [{"start_ft": s * MI, "volume": v, "phf": phf, "phv": phv}
+                       for s, v, phf, phv in demand],
+        },
+        "decreasing": {"demand": [{"start_ft": 0.0, "volume": 500.0}]},
+        "spl": 55.0,
+        "lane_width": 12.0,
+        "shoulder_width": 6.0,
+        "apd": 0.0,
+        "pmhvfl": 0.4,
+    }
+
+
+def test_both_directions_report_los():
+    res = json.loads(tl.analyze_two_lane_bidirectional(json.dumps(corridor())))
+    assert res["increasing"]["result"]["los"] == "C"
+    assert res["increasing"]["result"]["follower_density"] == pytest.approx(7.271, abs=1e-3)
+    assert res["decreasing"]["result"]["los"] in "ABCDEF"
+    assert all(s["volume_op"] == 500.0 for s in res["increasing"]["segmentation"]["highway"]["segments"])
+
+
+def test_passing_lanes_in_both_directions_are_rejected():
+    payload = corridor()
+    payload["decreasing"]["passing"] = [{"start_ft": 1.0 * MI, "end_ft": 2.0 * MI, "passing_type": 2}]
+    with pytest.raises(ValueError, match="multilane"):
+        tl.analyze_two_lane_bidirectional(json.dumps(payload))
@@ -0,0 +1,37 @@
+"""Python-binding test for the checked two-lane analysis.
+
+Mirrors tests/twolanehighways_checked_test.rs through
+`TwoLaneHighways.try_analyze` on Chapter 26 Example Problem 1 (`case1.json`).
+"""
+
+import json
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+
+def ep1_highway(passing_type=0, apd=0.0):
+    seg = tl.Segment(passing_type, 0.75, 0.0, 50.0, is_hc=False, volume=752.0, volume_op=0.0,
+                     vertical_class=1, subsegments=[], phf=0.94, phv=5.0, hor_class=0)
+    return tl.TwoLaneHighways([seg], lane_width=12.0, shoulder_width=6.0, apd=apd,
+                              pmhvfl=0.4, l_de=0.0)
+
+
+def test_fully_specified_facility_matches_analyze():
+    checked = json.loads(ep1_highway().try_analyze())
+    assert checked["defaults"] == []
+    single = json.loads(ep1_highway().analyze())
+    assert checked["result"]["follower_density"] == pytest.approx(single["follower_density"])
+
+
+def test_missing_apd_is_listed():
+    checked = json.loads(ep1_highway(apd=None).try_analyze())
+    assert checked["defaults"] == [
+        {"segment": None, "subsegment": None, "field": "apd", "value": 5.0}
+    ]
+
+
+def test_invalid_passing_type_names_segment_and_exhibit():
+    with pytest.raises(ValueError, match=r"segment 0 passing_type .*Exhibit 15-10"):
+        ep1_highway(passing_type=3).try_analyze()
@@ -0,0 +1,46 @@
+"""Python-binding test for curve subsegments derived from geometry.
+
+Mirrors tests/twolanehighways_curves_test.rs: Example Problem 2's five curves,
+placed on a 0.75 mi, 50 mi/h segment through `Segment.set_curves`.
+"""
+
+import json
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+EP2_CURVES = [
+    {"start_ft": 280.0, "length": 432.0, "radius": 450.0, "superelevation": 3.0},
+    {"start_ft": 972.0, "length": 366.5, "radius": 300.0, "superelevation": 2.0},
+    {"start_ft": 1588.5, "length": 216.0, "radius": 275.0, "superelevation": 5.0},
+    {"start_ft": 2080.1, "length": 458.0, "radius": 750.0, "superelevation": 0.0},
+    {"start_ft": 2823.1, "length": 767.9, "radius": 1100.0, "superelevation": 4.0},
+]
+
+
+def ep2_segment():
+    return tl.Segment(1, 0.75, 0.0, 50.0, volume=656.0, volume_op=1000.0,
+                      vertical_class=1, phf=0.94, phv=5.0)
+
+
+def test_curves_become_classified_subsegments():
+    seg = ep2_segment()
+    seg.set_curves(json.dumps(EP2_CURVES))
+    subs = seg.subsegments
+    assert len(subs) == 11
+    assert [s.hor_class for s in subs if s.design_rad > 0] == [3, 4, 5, 2, 1]
+    assert seg.is_hc
+    assert seg.hor_class == 5
+
+
+def test_tight_curves_are_flagged_at_the_posted_speed():
+    flags = json.loads(ep2_segment().set_curves(json.dumps(EP2_CURVES)))
+    assert [f["curve"] for f in flags] == [0, 1, 2]
+    assert all(f["min_radius_ft"] == pytest.approx(710.0) for f in flags)
+
+
+def test_overlapping_curves_raise():
+    curves = [dict(EP2_CURVES[0]), dict(EP2_CURVES[1], start_ft=500.0)]
+    with pytest.raises(ValueError, match="overlaps"):
+        ep2_segment().set_curves(json.dumps(curves))
@@ -0,0 +1,45 @@
+"""Python-binding test for multi-period two-lane highway analysis.
+
+Runs Chapter 26 Example Problem 1 (`case1.json`: 752 veh/h, PHF 0.94)
+through `TwoLaneHighways.analyze_periods`, mirroring
+tests/twolanehighways_multiperiod_test.rs.
+"""
+
+import json
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+DESIGN_FLOW = 752.0 / 0.94
+
+
+def ep1_highway():
+    seg = tl.Segment(0, 0.75, 0.0, 50.0, is_hc=False, volume=752.0, volume_op=0.0,
+                     vertical_class=1, subsegments=[], phf=0.94, phv=5.0, hor_class=0)
+    return tl.TwoLaneHighways([seg], lane_width=12.0, shoulder_width=6.0, apd=0.0,
+                              pmhvfl=0.4, l_de=0.0)
+
+
+def profile(multiples):
+    return {
+        "period": {"duration_h": 0.25, "num_periods": len(multiples)},
+        "demand": {"period_volumes": [m * DESIGN_FLOW * 0.25 for m in multiples]},
+    }
+
+
+def test_design_period_matches_single_analysis():
+    highway = ep1_highway()
+    res = json.loads(highway.analyze_periods(json.dumps(profile([0.5, 1.0]))))
+    single = json.loads(highway.analyze())
+    assert res["periods"][1]["result"]["follower_density"] == pytest.approx(single["follower_density"])
+    assert res["periods"][0]["result"]["follower_density"] < single["follower_density"]
+    assert sum(res["hours_by_los"]) == pytest.approx(0.5)
+    assert res["worst_period"] == 1
+
+
+def test_period_count_must_match():
+    payload = profile([1.0])
+    payload["period"]["num_periods"] = 2
+    with pytest.raises(ValueError, match="num_periods"):
+        ep1_highway().analyze_periods(json.dumps(payload))
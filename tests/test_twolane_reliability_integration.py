@@ -0,0 +1,47 @@
+"""Python-binding test for two-lane highway travel time reliability.
+
+Runs Chapter 26 Example Problem 3 (`case3.json`) through
+`analyze_two_lane_reliability`, mirroring
+tests/twolanehighways_reliability_test.rs. The HCM publishes no two-lane
+reliability example, so only the method's invariants are checked.
+"""
+
+import json
+from pathlib import Path
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+CASE3 = Path(__file__).parent / "ExampleCases" / "hcm" / "TwoLaneHighways" / "case3.json"
+
+
+def run(config):
+    payload = {"highway": json.loads(CASE3.read_text()), "config": config}
+    return json.loads(tl.analyze_two_lane_reliability(json.dumps(payload)))
+
+
+def test_seed_day_is_a_single_scenario():
+    res = run({"seed_month": "May", "seed_day": "Tuesday",
+               "months": ["May"], "days": ["Tuesday"]})
+    assert len(res["scenarios"]) == 1
+    assert res["scenarios"][0]["probability"] == 1.0
+    assert res["metrics"]["tti_mean"] >= 1.0
+
+
+def test_reporting_period_distribution():
+    res = run({
+        "weather": [{"condition": "HeavyRain", "probability": 0.05}],
+        "incidents": {"incident_rate_per_100mvmt": 150.0},
+    })
+    assert sum(s["probability"] for s in res["scenarios"]) == pytest.approx(1.0)
+    m = res["metrics"]
+    assert m["tti_95"] >= m["tti_80"] >= m["tti_50"] >= 1.0
+    fd = res["follower_density"]
+    assert fd["fd_95"] >= fd["fd_50"]
+    assert sum(fd["pct_by_los"]) == pytest.approx(100.0)
+
+
+def test_invalid_config_raises():
+    with pytest.raises(ValueError):
+        run({"weather": [{"condition": "HeavyRain", "probability": 1.5}]})
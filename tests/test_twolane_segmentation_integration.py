@@ -0,0 +1,49 @@
+"""Python-binding test for two-lane highway segmentation.
+
+Rebuilds Chapter 26 Example Problem 3 (`case3.json`) from its striping and
+demand through `segment_two_lane_alignment`, mirroring
+tests/twolanehighways_segmentation_test.rs.
+"""
+
+import json
+from pathlib import Path
+
+import pytest
+
+tl = pytest.importorskip("transportations_library")
+
+CASE3 = Path(__file__).parent / "ExampleCases" / "hcm" / "TwoLaneHighways" / "case3.json"
+MI = 5280.0
+
+
+def ep3_alignment():
+    demand = [(0.0, 850.0, 0.0, 0.94), (0.75, 825.0, 0.0, 0.95), (2.25, 820.0, 0.0, 0.95),
+              (3.25, 800.0, 500.0, 0.94), (3.75, 795.0, 0.0, 0.935)]
+    return {
+        "length_ft": 5.5 * MI,
+        "passing": [{"start_ft": 0.75 * MI, "end_ft": 2.25 * MI, "passing_type": 2},
+                    {"start_ft": 3.25 * MI, "end_ft": 3.75 * MI, "passing_type": 1}],
+        "demand": [{"start_ft": s * MI, "volume": v, "opposing_volume": vo, "phf": phf,
+                    "phv": 7.5 if vo else 8.0} for s, v, vo, phf in demand],
+        "spl": 55.0,
+        "lane_width": 12.0,
+        "shoulder_width": 6.0,
+        "apd": 0.0,
+        "pmhvfl": 0.4,
+    }
+
+
+def test_example_problem_3_segments():
+    res = json.loads(tl.segment_two_lane_alignment(json.dumps(ep3_alignment())))
+    published = json.loads(CASE3.read_text())["segments"]
+    got = res["highway"]["segments"]
+    assert [s["passing_type"] for s in got] == [s["passing_type"] for s in published]
+    assert [s["length"] for s in got] == pytest.approx([s["length"] for s in published])
+    assert {d["rule"] for d in res["decisions"]} == {"PassingTypeChange", "DemandChange"}
+
+
+def test_demand_must_start_at_zero():
+    payload = ep3_alignment()
+    payload["demand"][0]["start_ft"] = 100.0
+    with pytest.raises(ValueError, match="station 0"):
+        tl.segment_two_lane_alignment(json.dumps(payload))
@@ -0,0 +1,93 @@
+//! Bicycle LOS over two-lane and multilane facilities.
+//!
+//! The widening example of `bicycle_widening.json` (BLOS 5.90, LOS F
+//! before the project) is rebuilt from a two-lane highway's motorized
+//! inputs.
+
+use transportations_library::hcm::basicfreeways::BasicFreeways;
+use transportations_library::hcm::twolanehighways::bicycle_facility::*;
+use transportations_library::hcm::twolanehighways::{BicycleLOS, Segment, TwoLaneHighways};
+
+fn segment(passing_type: usize, length: f64, spl: f64) -> Segment {
+    Segment::new(
+        passing_type, length, 0.0, spl, Some(false), Some(500.0), Some(0.0), None, None, None, None, None,
+        Some(1), Some(vec![]), Some(0.90), Some(5.0), None, None, None, Some(0),
+    )
+}
+
+fn highway(segments: Vec<Segment>) -> TwoLaneHighways {
+    TwoLaneHighways::new(segments, Some(12.0), Some(2.0), Some(0.0), Some(0.4), None)
+}
+
+fn fair(pavement_condition: f64) -> BicycleConditions {
+    BicycleConditions { pavement_condition, pct_on_highway_parking: 0.0 }
+}
+
+#[test]
+fn two_lane_inputs_reproduce_the_widening_example() {
+    let facility = BicycleFacility::from_two_lane(&highway(vec![segment(0, 1.0, 50.0)]), &[fair(3.0)]).unwrap();
+    let res = facility.analyze().unwrap();
+    assert!((res.segments[0].result.flow_rate_outside_lane - 555.6).abs() < 0.1);
+    assert!((res.segments[0].result.effective_width - 14.0).abs() < 0.01);
+    assert!((res.blos_score - 5.90).abs() < 0.01);
+    assert_eq!(res.los, 'F');
+}
+
+#[test]
+fn facility_score_is_length_weighted() {
+    let h = highway(vec![segment(0, 1.0, 50.0), segment(2, 1.5, 55.0), segment(0, 0.5, 55.0)]);
+    let facility = BicycleFacility::from_two_lane(&h, &[fair(3.0), fair(5.0), fair(4.0)]).unwrap();
+    // A passing lane gives the bicyclist's lane half the directional flow.
+    assert_eq!(facility.segments[1].inputs.num_lanes, 2);
+    assert_eq!(facility.segments[1].inputs.heavy_vehicle_pct, 0.05);
+    let res = facility.analyze().unwrap();
+    let scores: Vec<f64> = res.segments.iter().map(|s| s.result.blos_score).collect();
+    let expected = (scores[0] * 1.0 + scores[1] * 1.5 + scores[2] * 0.5) / 3.0;
+    assert!((res.blos_score - expected).abs() < 1e-12);
+    assert_eq!(res.total_length, 3.0);
+    assert!(scores[1] < scores[0]);
+}
+
+#[test]
+fn multilane_segments_use_chapter_12_inputs() {
+    let mut seg = BasicFreeways::with_rural_multilane_defaults();
+    seg.length = 2.0;
+    seg.demand_flow_i = 1200.0;
+    seg.speed_limit = 55;
+    let facility = BicycleFacility::from_multilane(&[seg.clone()], &[fair(4.0)]).unwrap();
+    let inputs = &facility.segments[0].inputs;
+    assert_eq!(inputs.num_lanes, seg.lane_count as i32);
+    assert_eq!(inputs.shoulder_width, seg.lc_r);
+    assert_eq!(inputs.heavy_vehicle_pct, seg.p_t.unwrap());
+    assert_eq!(inputs.hourly_volume, 1200.0);
+    let res = facility.analyze().unwrap();
+    assert!((res.segments[0].result.flow_rate_outside_lane - 1200.0 / (seg.phf * 2.0)).abs() < 1e-9);
+
+    // The Chapter 12 analysis turns v_p into pc/h/ln; the bicycle inputs
+    // still take the hourly demand, so analyzing first changes nothing.
+    seg.run_operational_analysis().unwrap();
+    assert!(seg.v_p != 1200.0);
+    let analyzed = BicycleFacility::from_multilane(&[seg.clone()], &[fair(4.0)]).unwrap();
+    assert_eq!(analyzed.analyze().unwrap().blos_score, res.blos_score);
+
+    let freeway = BasicFreeways::new();
+    let err = BicycleFacility::from_multilane(&[freeway], &[fair(4.0)]).unwrap_err();
+    assert!(err.contains("not a multilane highway"));
+}
+
+#[test]
+fn posted_speeds_at_or_below_20_are_refused() {
+    for spl in [20.0, 15.0] {
+        let blos = BicycleLOS { speed_limit: spl, ..BicycleLOS::default() };
+        assert!(blos.validate().unwrap_err().contains("20 mi/h"));
+        assert!(blos.analyze().unwrap_err().contains("20 mi/h"));
+        assert!(blos.determine_bicycle_los().is_err());
+        let facility = BicycleFacility::from_two_lane(&highway(vec![segment(0, 1.0, spl)]), &[fair(3.0)]).unwrap();
+        assert!(facility.analyze().unwrap_err().contains("segment 0"));
+    }
+    assert!(BicycleLOS { speed_limit: 20.5, ..BicycleLOS::default() }.validate().is_ok());
+
+    let h = highway(vec![segment(0, 1.0, 50.0), segment(0, 1.0, 50.0)]);
+    let err = BicycleFacility::from_two_lane(&h, &[fair(3.0), fair(3.0), fair(3.0)]).unwrap_err();
+    assert!(err.contains("3 sets of conditions for 2 segments"));
+}
@@ -0,0 +1,130 @@
+//! Both directions of a two-lane highway in one run.
+//!
+//! The increasing direction is Chapter 26 Example Problem 3 (`case3.json`),
+//! whose passing zone faces 500 veh/h of opposing demand. A decreasing
+//! direction carrying 500 veh/h throughout must therefore leave the
+//! increasing direction exactly as published.
+
+use transportations_library::hcm::twolanehighways::bidirectional::*;
+use transportations_library::hcm::twolanehighways::segmentation::*;
+
+mod common;
+
+const CASE3: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case3.json";
+const MI: f64 = 5280.0;
+
+fn demand(start_mi: f64, volume: f64) -> DemandStretch {
+    DemandStretch { start_ft: start_mi * MI, volume, opposing_volume: 0.0, phf: None, phv: None }
+}
+
+fn stretch(start_mi: f64, end_mi: f64, passing_type: usize) -> PassingStretch {
+    PassingStretch { start_ft: start_mi * MI, end_ft: end_mi * MI, passing_type }
+}
+
+/// EP3 increasing, 500 veh/h with no passing opportunities decreasing.
+fn ep3_corridor() -> BidirectionalTwoLane {
+    let phf = [0.94, 0.95, 0.95, 0.94, 0.935];
+    let phv = [8.0, 8.0, 8.0, 7.5, 8.0];
+    let increasing = DirectionInputs {
+        passing: vec![stretch(0.75, 2.25, 2), stretch(3.25, 3.75, 1)],
+        demand: [(0.0, 850.0), (0.75, 825.0), (2.25, 820.0), (3.25, 800.0), (3.75, 795.0)]
+            .iter()
+            .enumerate()
+            .map(|(i, &(s, v))| DemandStretch { phf: Some(phf[i]), phv: Some(phv[i]), ..demand(s, v) })
+            .collect(),
+        spl: None,
+    };
+    BidirectionalTwoLane {
+        length_ft: 5.5 * MI,
+        grades: vec![],
+        curves: vec![],
+        increasing,
+        decreasing: DirectionInputs { passing: vec![], demand: vec![demand(0.0, 500.0)], spl: None },
+        spl: 55.0,
+        phf: 0.94,
+        phv: 6.0,
+        lane_width: Some(12.0),
+        shoulder_width: Some(6.0),
+        apd: Some(0.0),
+        pmhvfl: Some(0.4),
+    }
+}
+
+#[test]
+fn increasing_direction_reproduces_example_problem_3() {
+    let res = ep3_corridor().run().unwrap();
+    let published = common::load_test_case(CASE3).unwrap().analyze();
+    let inc = &res.increasing.result;
+    assert!((inc.follower_density - published.follower_density).abs() < 1e-9);
+    assert_eq!(inc.los, published.los);
+    assert!(res.increasing.segmentation.highway.segments.iter().all(|s| s.volume_op == Some(500.0)));
+
+    // The decreasing direction is Passing Constrained throughout, divided at
+    // the 3-mi maximum, and opposed by EP3's demand length-weighted over
+    // each half.
+    let dec = &res.decreasing.segmentation.highway.segments;
+    assert_eq!(dec.len(), 2);
+    let weighted = (0.75 * 850.0 + 1.5 * 825.0 + 1.0 * 820.0 + 0.5 * 800.0 + 1.75 * 795.0) / 5.5;
+    assert!((dec.iter().map(|s| s.volume_op.unwrap() * s.length).sum::<f64>() / 5.5 - weighted).abs() < 1e-9);
+    assert!(res.decreasing.result.follower_density < inc.follower_density);
+    assert_eq!(res.critical().direction, TravelDirection::Increasing);
+}
+
+#[test]
+fn decreasing_direction_is_mirrored() {
+    let mut c = ep3_corridor();
+    c.grades = vec![
+        GradeTangent { start_ft: 0.0, grade: 5.0 },
+        GradeTangent { start_ft: 1.0 * MI, grade: 0.0 },
+    ];
+    c.decreasing.passing = vec![stretch(4.5, 5.5, 2)];
+    c.decreasing.demand = vec![demand(0.0, 450.0), demand(3.0, 550.0)];
+    let res = c.run().unwrap();
+    let dec = &res.decreasing.segmentation;
+
+    // The passing lane at shared stations 4.5-5.5 mi is where the
+    // decreasing direction enters; the 5% upgrade at 0-1 mi is its last
+    // mile, as a downgrade.
+    assert_eq!(dec.segments[0].passing_type, 2);
+    assert!((dec.segments[0].end_ft - 1.0 * MI).abs() < 1e-6);
+    let last = dec.segments.last().unwrap();
+    assert!((last.grade + 5.0).abs() < 1e-9);
+    assert!((last.end_ft - 5.5 * MI).abs() < 1e-6);
+    assert_eq!(dec.highway.segments[0].volume, Some(550.0));
+    assert_eq!(dec.highway.segments.last().unwrap().volume, Some(450.0));
+
+    // Its demand opposes the increasing direction where it is.
+    let inc = &res.increasing.segmentation;
+    let first = &inc.highway.segments[0];
+    assert_eq!(first.volume_op, Some(450.0));
+}
+
+#[test]
+fn opposing_passing_lane_constrains_a_passing_zone() {
+    let mut c = ep3_corridor();
+    c.decreasing.passing = vec![stretch(3.0, 3.6, 2)];
+    let res = c.run().unwrap();
+    let inc = &res.increasing.segmentation;
+    let rules: Vec<SegmentationRule> = inc.decisions.iter().map(|d| d.rule).collect();
+    assert!(rules.contains(&SegmentationRule::OpposingPassingLane));
+    // The 0.15 mi left of the passing zone is below its minimum.
+    assert!(rules.contains(&SegmentationRule::ShortPassingZone));
+    assert!(inc.highway.segments.iter().all(|s| s.passing_type != 1));
+    let published = common::load_test_case(CASE3).unwrap().analyze();
+    assert!(res.increasing.result.follower_density > published.follower_density);
+}
+
+#[test]
+fn invalid_corridors_are_rejected() {
+    let mut c = ep3_corridor();
+    c.decreasing.passing = vec![stretch(1.0, 2.0, 2)];
+    assert!(c.run().unwrap_err().contains("multilane"));
+
+    let mut c = ep3_corridor();
+    c.decreasing.demand[0].opposing_volume = 300.0;
+    assert!(c.run().unwrap_err().contains("derived"));
+
+    let mut c = ep3_corridor();
+    c.decreasing.demand.clear();
+    assert!(c.run().unwrap_err().contains("Decreasing"));
+}
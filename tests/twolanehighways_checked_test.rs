@@ -0,0 +1,89 @@
+//! Checked two-lane analysis: typed input errors and the applied-defaults list.
+
+mod common;
+
+use transportations_library::hcm::twolanehighways::{AppliedDefault, TwoLaneHighways};
+use transportations_library::TransportationError;
+
+type Mutation = fn(&mut TwoLaneHighways);
+
+const CASE3: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case3.json";
+
+fn case3() -> TwoLaneHighways {
+    common::load_test_case(CASE3).unwrap()
+}
+
+fn invalid_input(highway: &mut TwoLaneHighways) -> String {
+    match highway.try_analyze() {
+        Err(TransportationError::InvalidInput(msg)) => msg,
+        other => panic!("expected InvalidInput, got {other:?}"),
+    }
+}
+
+#[test]
+fn fully_specified_example_applies_no_defaults_and_matches_analyze() {
+    let checked = case3().try_analyze().unwrap();
+    assert!(checked.defaults.is_empty(), "{:?}", checked.defaults);
+    let plain = case3().analyze();
+    assert_eq!(checked.result.los, plain.los);
+    assert!((checked.result.follower_density - plain.follower_density).abs() < 1e-9);
+}
+
+#[test]
+fn missing_inputs_are_listed_with_the_values_used() {
+    let mut highway = case3();
+    highway.apd = None;
+    highway.pmhvfl = None;
+    highway.segments[3].phf = None;
+    highway.segments[3].volume_op = None;
+    // A PC segment's opposing volume is never read, so it is not a default.
+    highway.segments[0].volume_op = None;
+    // Step 3 recomputes the vertical class; only the passing lane's capacity
+    // reads it first.
+    highway.segments[0].vertical_class = None;
+    highway.segments[1].vertical_class = None;
+
+    let defaults = highway.check_inputs().unwrap();
+    let facility = |field: &str, value: f64| AppliedDefault {
+        segment: None, subsegment: None, field: field.into(), value,
+    };
+    let segment = |i: usize, field: &str, value: f64| AppliedDefault {
+        segment: Some(i), subsegment: None, field: field.into(), value,
+    };
+    assert_eq!(
+        defaults,
+        vec![
+            facility("apd", 5.0),
+            facility("pmhvfl", 0.0),
+            segment(1, "vertical_class", 1.0),
+            segment(3, "phf", 0.95),
+            segment(3, "volume_op", 1500.0),
+        ]
+    );
+}
+
+#[test]
+fn an_invalid_passing_type_names_the_segment_and_exhibit() {
+    let mut highway = case3();
+    highway.segments[2].passing_type = 3;
+    let msg = invalid_input(&mut highway);
+    assert!(msg.contains("segment 2 passing_type"), "{msg}");
+    assert!(msg.contains("Exhibit 15-10"), "{msg}");
+}
+
+#[test]
+fn out_of_domain_values_are_refused_before_the_equations() {
+    let cases: [(Mutation, &str); 5] = [
+        (|h| h.segments[1].phf = Some(0.0), "segment 1 phf"),
+        (|h| h.segments[3].volume_op = Some(1800.0), "Exhibit 15-5"),
+        (|h| h.segments[4].vertical_class = Some(6), "Exhibit 15-11"),
+        (|h| h.lane_width = Some(14.0), "lane_width = 14"),
+        (|h| h.segments[0].is_hc = Some(true), "segment 0 has is_hc set but no subsegments"),
+    ];
+    for (mutate, expected) in cases {
+        let mut highway = case3();
+        mutate(&mut highway);
+        let msg = invalid_input(&mut highway);
+        assert!(msg.contains(expected), "{msg}");
+    }
+}
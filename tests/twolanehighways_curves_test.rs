@@ -0,0 +1,90 @@
+//! Horizontal curve subsegments derived from placed curves.
+//!
+//! Example Problem 2 (`case2.json`) gives its one segment as eleven hand-made
+//! subsegments, five of them curves. Placing the same five curves on the
+//! segment reproduces them and the published result.
+
+mod common;
+
+use transportations_library::hcm::twolanehighways::{PlacedCurve, TwoLaneHighways};
+use transportations_library::hcm::utils::geometric::HorizontalCurve;
+
+const CASE2: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case2.json";
+
+fn case2() -> TwoLaneHighways {
+    common::load_test_case(CASE2).unwrap()
+}
+
+/// Example Problem 2's curves: start, arc length, radius, superelevation.
+fn ep2_curves() -> Vec<PlacedCurve> {
+    [
+        (280.0, 432.0, 450.0, 3.0),
+        (972.0, 366.5, 300.0, 2.0),
+        (1588.5, 216.0, 275.0, 5.0),
+        (2080.1, 458.0, 750.0, 0.0),
+        (2823.1, 767.9, 1100.0, 4.0),
+    ]
+    .into_iter()
+    .map(|(start_ft, length, radius, e)| PlacedCurve {
+        start_ft,
+        curve: HorizontalCurve::new(radius, length, e, 0.0).unwrap(),
+    })
+    .collect()
+}
+
+#[test]
+fn placed_curves_rebuild_example_problem_2() {
+    let mut highway = case2();
+    let given = highway.segments[0].get_subsegments().clone();
+    highway.segments[0].subsegments = None;
+    highway.segments[0].is_hc = None;
+    highway.segments[0].set_curves(&ep2_curves()).unwrap();
+
+    let derived = highway.segments[0].get_subsegments();
+    assert_eq!(derived.len(), given.len());
+    for (d, g) in derived.iter().zip(&given) {
+        assert!((d.get_length() - g.get_length()).abs() < 1e-6);
+        assert_eq!(d.get_design_rad(), g.get_design_rad());
+        assert_eq!(d.get_sup_ele(), g.get_sup_ele());
+        assert_eq!(d.get_hor_class(), g.get_hor_class());
+    }
+    assert_eq!(highway.segments[0].get_hor_class(), 5);
+
+    let res = highway.analyze();
+    let expected = case2().analyze();
+    assert!((res.segments[0].avg_speed - expected.segments[0].avg_speed).abs() < 1e-9);
+    assert_eq!(res.los, expected.los);
+}
+
+#[test]
+fn curves_below_the_minimum_radius_for_the_posted_speed_are_flagged() {
+    let mut highway = case2();
+    let flags = highway.segments[0].set_curves(&ep2_curves()).unwrap();
+    // 50 mi/h needs 710 ft: the 450, 300 and 275 ft curves fall short.
+    let flagged: Vec<usize> = flags.iter().map(|f| f.curve).collect();
+    assert_eq!(flagged, vec![0, 1, 2]);
+    assert!(flags.iter().all(|f| f.min_radius_ft == 710.0 && f.speed_limit == 50.0));
+}
+
+#[test]
+fn curves_slow_the_segment() {
+    let mut highway = case2();
+    highway.segments[0].set_curves(&[]).unwrap();
+    let tangent = highway.clone().analyze().segments[0].avg_speed;
+    highway.segments[0].set_curves(&ep2_curves()).unwrap();
+    assert!(highway.analyze().segments[0].avg_speed < tangent);
+}
+
+#[test]
+fn overlapping_or_overlong_curves_are_rejected() {
+    let mut highway = case2();
+    let mut curves = ep2_curves();
+    curves[1].start_ft = 500.0;
+    let err = highway.segments[0].set_curves(&curves).unwrap_err();
+    assert!(err.contains("overlaps"), "{err}");
+
+    let mut curves = ep2_curves();
+    curves[4].start_ft = 3500.0;
+    let err = highway.segments[0].set_curves(&curves).unwrap_err();
+    assert!(err.contains("past the"), "{err}");
+}
@@ -0,0 +1,108 @@
+//! Multi-period analysis of Chapter 26 Example Problem 3 (`case3.json`).
+//!
+//! A period whose entering flow rate equals the design flow rate
+//! (850 veh/h / 0.94) must reproduce the single-period analysis.
+
+use transportations_library::hcm::common::time_period::{AnalysisPeriod, DemandProfile};
+use transportations_library::hcm::common::LevelOfService;
+use transportations_library::hcm::twolanehighways::multiperiod::*;
+use transportations_library::hcm::twolanehighways::TwoLaneHighways;
+
+mod common;
+
+const CASE3: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case3.json";
+const DESIGN_FLOW: f64 = 850.0 / 0.94;
+
+fn ep3() -> TwoLaneHighways {
+    common::load_test_case(CASE3).unwrap()
+}
+
+/// Quarter-hour counts at the given multiples of the design flow rate.
+fn profile(multiples: &[f64]) -> TwoLaneDemandProfile {
+    TwoLaneDemandProfile {
+        period: AnalysisPeriod { duration_h: 0.25, num_periods: multiples.len() as u32 },
+        demand: DemandProfile { period_volumes: multiples.iter().map(|m| m * DESIGN_FLOW * 0.25).collect() },
+        opposing: None,
+        start_h: 16.0,
+    }
+}
+
+#[test]
+fn design_period_reproduces_single_period_analysis() {
+    let res = ep3().analyze_periods(&profile(&[0.5, 1.0, 0.8])).unwrap();
+    let single = ep3().analyze();
+    let design = &res.periods[1];
+    assert!((design.demand_multiplier - 1.0).abs() < 1e-12);
+    assert!((design.result.follower_density - single.follower_density).abs() < 1e-9);
+    assert_eq!(design.result.los, single.los);
+    assert_eq!(design.start_h, 16.25);
+
+    // Lighter periods are better, and the design period is the worst.
+    assert!(res.periods[0].result.follower_density < res.periods[2].result.follower_density);
+    assert!(res.periods[2].result.follower_density < design.result.follower_density);
+    assert_eq!(res.worst_period, 1);
+    assert!((res.hours_by_los.iter().sum::<f64>() - 0.75).abs() < 1e-12);
+    assert!(res.hours_at(LevelOfService::C) >= 0.25);
+
+    // VMT is segment flow rate x period x length.
+    let vmt: f64 = single.segments.iter().map(|s| s.flow_rate * 0.25 * s.length).sum();
+    assert!((design.vmt - vmt).abs() < 1e-6);
+    assert!((design.vht - 0.25 * single.segments.iter().map(|s| s.flow_rate * s.length / s.avg_speed).sum::<f64>()).abs() < 1e-6);
+}
+
+#[test]
+fn daily_profile_weights_periods_by_vmt() {
+    // 96 quarter hours from 0.1 to 1.9 times the design flow rate.
+    let multiples: Vec<f64> =
+        (0..96).map(|i| 1.0 - 0.9 * (2.0 * std::f64::consts::PI * i as f64 / 96.0).cos()).collect();
+    let res = ep3().analyze_periods(&profile(&multiples)).unwrap();
+    assert_eq!(res.periods.len(), 96);
+    assert!((res.hours_by_los.iter().sum::<f64>() - 24.0).abs() < 1e-9);
+    assert!((res.total_vmt - res.periods.iter().map(|p| p.vmt).sum::<f64>()).abs() < 1e-6);
+
+    let speeds: Vec<f64> = res.periods.iter().map(|p| p.result.average_speed).collect();
+    let (lo, hi) = speeds.iter().fold((f64::MAX, f64::MIN), |(lo, hi), &s| (lo.min(s), hi.max(s)));
+    assert!(res.average_speed > lo && res.average_speed < hi);
+
+    // Heavy periods carry more VMT and more followers, so the VMT weighting
+    // pulls the daily density above the plain period mean.
+    let mean = res.periods.iter().map(|p| p.result.follower_density).sum::<f64>() / 96.0;
+    assert!(res.vmt_weighted_follower_density > mean);
+    assert!(res.vmt_weighted_percent_followers > 0.0 && res.vmt_weighted_percent_followers < 100.0);
+
+    // 1.9 x 904 veh/h exceeds the 1,500 veh/h passing lane capacity.
+    assert!(res.hours_over_capacity > 0.0);
+    assert!(res.hours_at(LevelOfService::F) >= res.hours_over_capacity);
+    assert_eq!(res.periods[res.worst_period].result.los, LevelOfService::F);
+}
+
+#[test]
+fn opposing_profile_sets_passing_zone_opposing_flow() {
+    let mut p = profile(&[1.0, 1.0]);
+    p.opposing = Some(DemandProfile { period_volumes: vec![100.0, 300.0] });
+    let res = ep3().analyze_periods(&p).unwrap();
+    // Segment 4 is the passing zone.
+    let opposing: Vec<f64> = res.periods.iter().map(|p| p.result.segments[3].flow_rate_opposing).collect();
+    assert!((opposing[0] - 400.0).abs() < 1e-9);
+    assert!((opposing[1] - 1200.0).abs() < 1e-9);
+    assert!(res.periods[1].result.segments[3].percent_followers > res.periods[0].result.segments[3].percent_followers);
+}
+
+#[test]
+fn invalid_profiles_are_rejected() {
+    let mut p = profile(&[1.0, 1.0]);
+    p.period.num_periods = 3;
+    assert!(ep3().analyze_periods(&p).unwrap_err().contains("num_periods is 3"));
+
+    let mut p = profile(&[1.0]);
+    p.demand.period_volumes[0] = -1.0;
+    assert!(ep3().analyze_periods(&p).unwrap_err().contains("period 0"));
+
+    let mut highway = ep3();
+    for s in &mut highway.segments {
+        s.volume_op = Some(0.0);
+    }
+    let mut p = profile(&[1.0]);
+    p.opposing = Some(DemandProfile { period_volumes: vec![100.0] });
+    assert!(highway.analyze_periods(&p).unwrap_err().contains("volume_op"));
+}
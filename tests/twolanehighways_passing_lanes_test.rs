@@ -0,0 +1,140 @@
+//! Passing lane placement optimizer tests.
+//!
+//! Chapter 26 Example Problem 3 (`case3.json`) with its passing lane taken
+//! out is the study facility: 5.5 mi of level road whose published design
+//! puts a 1.5-mi passing lane in segment 2. The search runs that facility
+//! back through `TwoLaneHighways::analyze` for every placement, so these
+//! tests check the bookkeeping around it: budgets, spacing, segment
+//! splitting and ranking.
+
+use transportations_library::hcm::common::LevelOfService;
+use transportations_library::hcm::twolanehighways::passing_lanes::*;
+use transportations_library::hcm::twolanehighways::TwoLaneHighways;
+
+mod common;
+
+const CASE3: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case3.json";
+
+/// EP3 with segment 2 as Passing Constrained instead of a passing lane.
+fn without_passing_lane() -> TwoLaneHighways {
+    let mut highway = common::load_test_case(CASE3).unwrap();
+    highway.segments[1].passing_type = 0;
+    highway
+}
+
+fn candidate(segment: usize, lengths: &[f64]) -> PassingLaneCandidate {
+    PassingLaneCandidate { segment, lengths: lengths.to_vec() }
+}
+
+#[test]
+fn published_placement_matches_example_problem_3() {
+    let opt = PassingLaneOptimizer::new(
+        without_passing_lane(),
+        vec![candidate(1, &[1.5])],
+        PassingLaneBudget::Count(1),
+    );
+    let res = opt.run().unwrap();
+    assert_eq!(res.evaluated, 1);
+
+    // Restoring the published passing lane reproduces EP3 (Exhibit 26-27).
+    let plan = &res.plans[0];
+    let ep3 = common::load_test_case(CASE3).unwrap().analyze();
+    assert_eq!(plan.result.follower_density, ep3.follower_density);
+    assert_eq!(plan.result.los, LevelOfService::C);
+    assert!(res.baseline.follower_density > plan.result.follower_density);
+    assert!((plan.fd_reduction - (res.baseline.follower_density - ep3.follower_density)).abs() < 1e-12);
+    assert_eq!(plan.los_improvement, res.baseline.los as i32 - LevelOfService::C as i32);
+}
+
+#[test]
+fn budgets_bound_the_search_and_plans_are_ranked() {
+    let candidates = vec![candidate(1, &[0.75, 1.5]), candidate(2, &[1.0]), candidate(4, &[0.5, 1.0, 1.75])];
+
+    // One lane: 2 + 1 + 3 single placements.
+    let one = PassingLaneOptimizer::new(without_passing_lane(), candidates.clone(), PassingLaneBudget::Count(1));
+    let res = one.run().unwrap();
+    assert_eq!(res.evaluated, 6);
+    assert!(res.plans.iter().all(|p| p.placements.len() == 1 && p.fd_reduction > 0.0));
+    assert!(res
+        .plans
+        .windows(2)
+        .all(|w| w[0].result.follower_density <= w[1].result.follower_density));
+
+    // Two lanes add 2x1 + 2x3 + 1x3 pairs.
+    let two = PassingLaneOptimizer::new(without_passing_lane(), candidates.clone(), PassingLaneBudget::Count(2));
+    let res = two.run().unwrap();
+    assert_eq!(res.evaluated, 6 + 11);
+    assert_eq!(res.plans.len(), 10);
+    assert!(res.plans[0].result.follower_density <= res.plans[9].result.follower_density);
+
+    // 1.75 lane-miles admits every single placement and the pairs that fit.
+    let miles = PassingLaneOptimizer::new(without_passing_lane(), candidates, PassingLaneBudget::LaneMiles(1.75));
+    let res = miles.run().unwrap();
+    assert!(res.plans.iter().all(|p| p.lane_miles <= 1.75 + 1e-9));
+    // Segment 2's 0.75 with segment 3's 1.0 or segment 5's 0.5 or 1.0, and
+    // segment 3's 1.0 with segment 5's 0.5, are the pairs within budget.
+    assert_eq!(res.evaluated, 6 + 4);
+}
+
+#[test]
+fn short_passing_lane_splits_its_segment() {
+    let highway = without_passing_lane();
+    let placed = apply_placements(&highway, &[PassingLanePlacement { segment: 4, length: 0.5 }]);
+    assert_eq!(placed.segments.len(), 6);
+    assert_eq!(placed.segments[4].passing_type, 2);
+    assert_eq!(placed.segments[4].length, 0.5);
+    assert_eq!(placed.segments[5].passing_type, 0);
+    assert!((placed.segments[5].length - 1.25).abs() < 1e-12);
+
+    let before: f64 = highway.segments.iter().map(|s| s.length).sum();
+    let after: f64 = placed.segments.iter().map(|s| s.length).sum();
+    assert!((before - after).abs() < 1e-12);
+
+    // The remainder lies within the passing lane's effective length.
+    let result = placed.clone().analyze();
+    assert!(result.segments[5].adjusted_follower_density.is_some());
+}
+
+#[test]
+fn level_of_service_objective_and_spacing() {
+    let candidates = vec![candidate(1, &[1.5]), candidate(2, &[1.0]), candidate(4, &[1.75])];
+    let mut opt = PassingLaneOptimizer::new(without_passing_lane(), candidates, PassingLaneBudget::Count(2));
+    opt.objective = PassingLaneObjective::LevelOfService;
+    let res = opt.run().unwrap();
+    assert!(res.plans.windows(2).all(|w| w[0].result.los <= w[1].result.los));
+
+    // Segments 2 and 3 are adjacent, so a 0.5-mi spacing rules out pairing
+    // them; segment 5 starts 0.5 mi after segment 3 ends.
+    opt.constraints.min_spacing_mi = 0.5;
+    let spaced = opt.run().unwrap();
+    assert_eq!(spaced.evaluated, res.evaluated - 1);
+    assert!(spaced
+        .plans
+        .iter()
+        .all(|p| !(p.placements.iter().any(|x| x.segment == 1) && p.placements.iter().any(|x| x.segment == 2))));
+}
+
+#[test]
+fn invalid_candidates_are_rejected() {
+    let run = |candidates: Vec<PassingLaneCandidate>, budget| {
+        PassingLaneOptimizer::new(without_passing_lane(), candidates, budget).run().unwrap_err()
+    };
+    assert!(run(vec![candidate(0, &[0.5])], PassingLaneBudget::Count(1)).contains("first segment"));
+    assert!(run(vec![candidate(2, &[1.2])], PassingLaneBudget::Count(1)).contains("does not fit"));
+    assert!(run(vec![candidate(7, &[0.5])], PassingLaneBudget::Count(1)).contains("segment 7"));
+    assert!(run(vec![candidate(2, &[0.5])], PassingLaneBudget::Count(0)).contains("no passing lanes"));
+
+    let published = common::load_test_case(CASE3).unwrap();
+    let err = PassingLaneOptimizer::new(published, vec![candidate(1, &[1.0])], PassingLaneBudget::Count(1))
+        .run()
+        .unwrap_err();
+    assert!(err.contains("already a passing lane"));
+
+    let mut capped = PassingLaneOptimizer::new(
+        without_passing_lane(),
+        vec![candidate(1, &[0.5, 1.0]), candidate(2, &[0.5, 1.0])],
+        PassingLaneBudget::Count(2),
+    );
+    capped.constraints.max_evaluations = 3;
+    assert!(capped.run().unwrap_err().contains("max_evaluations"));
+}
@@ -0,0 +1,192 @@
+//! Two-lane highway travel time reliability tests.
+//!
+//! The HCM publishes no two-lane reliability example, so these tests run
+//! Chapter 26 Example Problem 3 (`case3.json`, the 5.5-mi level facility)
+//! through the scenario loop and check the properties the method has to
+//! hold: a single seed-day scenario reproduces the plain Chapter 15
+//! analysis, probabilities sum to one, and each source of unreliability
+//! moves the distribution the way it should.
+
+use transportations_library::hcm::common::adjustment_factors::{DayOfWeek, Month, WeatherCondition};
+use transportations_library::hcm::common::LevelOfService;
+use transportations_library::hcm::twolanehighways::reliability::*;
+
+mod common;
+
+const CASE3: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case3.json";
+
+fn seed_only_config() -> TwoLaneReliabilityConfig {
+    TwoLaneReliabilityConfig {
+        seed_month: Month::May,
+        seed_day: DayOfWeek::Tuesday,
+        months: vec![Month::May],
+        days: vec![DayOfWeek::Tuesday],
+        ..Default::default()
+    }
+}
+
+fn run(config: TwoLaneReliabilityConfig) -> TwoLaneReliabilityResults {
+    let highway = common::load_test_case(CASE3).unwrap();
+    TwoLaneReliability::new(highway, config).run().unwrap()
+}
+
+#[test]
+fn seed_scenario_reproduces_chapter_15() {
+    let res = run(seed_only_config());
+    assert_eq!(res.scenarios.len(), 1);
+    let s = &res.scenarios[0];
+    assert_eq!(s.probability, 1.0);
+    assert_eq!(s.demand_multiplier, 1.0);
+
+    let mut highway = common::load_test_case(CASE3).unwrap();
+    let summary = common::analyze_facility(&mut highway);
+    let travel_time: f64 = summary.segments.iter().map(|g| g.length / g.speed).sum();
+    assert!((s.travel_time_h - travel_time).abs() < 1e-12);
+    assert!((s.follower_density - summary.follower_density).abs() < 1e-12);
+    assert_eq!(s.los, LevelOfService::from(summary.level_of_service));
+    assert!(s.tti >= 1.0);
+    assert!((res.metrics.tti_mean - s.tti).abs() < 1e-12);
+}
+
+#[test]
+fn unit_factors_match_analyze() {
+    // With a passing lane, so the Step 9 adjustment is part of the match.
+    let mut highway = common::load_test_case(CASE3).unwrap();
+    highway.segments[3].passing_type = 2;
+    let facility = highway.clone().analyze();
+    assert!(facility.segments[4].adjusted_follower_density.is_some());
+
+    let res = TwoLaneReliability::new(highway, seed_only_config()).run().unwrap();
+    let s = &res.scenarios[0];
+    assert!(!facility.over_capacity);
+    assert!((s.travel_time_h - facility.travel_time_h).abs() < 1e-12);
+    assert!((s.follower_density - facility.follower_density).abs() < 1e-12);
+    assert_eq!(s.los, facility.los);
+    assert_eq!(res.follower_density.pct_at(facility.los), 100.0);
+}
+
+#[test]
+fn reporting_period_probabilities_and_distributions() {
+    let config = TwoLaneReliabilityConfig {
+        weather: vec![
+            WeatherShare { condition: WeatherCondition::HeavyRain, probability: 0.03 },
+            WeatherShare { condition: WeatherCondition::LightSnow, probability: 0.02 },
+        ],
+        incidents: Some(TwoLaneIncidentInputs {
+            incident_rate_per_100mvmt: 150.0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let res = run(config);
+    // 12 months x 5 weekdays x 3 weather x (1 + 2 types x 5 segments).
+    assert_eq!(res.scenarios.len(), 12 * 5 * 3 * 11);
+    let total: f64 = res.scenarios.iter().map(|s| s.probability).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+
+    let m = &res.metrics;
+    assert!(m.tti_95 >= m.tti_80 && m.tti_80 >= m.tti_50 && m.tti_50 >= 1.0);
+    assert!(m.tti_max >= m.tti_95);
+    let fd = &res.follower_density;
+    assert!(fd.fd_95 >= fd.fd_80 && fd.fd_80 >= fd.fd_50);
+    assert!(fd.max >= fd.fd_95);
+    assert!((fd.pct_by_los.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+
+    // Summer weekday demand is above the seed (January Monday) demand.
+    let july_friday = res
+        .scenarios
+        .iter()
+        .find(|s| s.month == Month::July && s.day == DayOfWeek::Friday)
+        .unwrap();
+    assert!(july_friday.demand_multiplier > 1.5);
+
+    // Every scenario shares the seed-condition free-flow travel time, so a
+    // higher-demand pattern cannot lower its own TTI base through Step 4.
+    let base = res.scenarios[0].free_flow_travel_time_h;
+    assert!(res.scenarios.iter().all(|s| s.free_flow_travel_time_h == base));
+    assert!(res.scenarios.iter().all(|s| (s.tti - s.travel_time_h / base).abs() < 1e-12));
+}
+
+#[test]
+fn weather_and_lane_blocking_raise_travel_time() {
+    let base = run(seed_only_config());
+    let snow = run(TwoLaneReliabilityConfig {
+        weather: vec![WeatherShare { condition: WeatherCondition::HeavySnow, probability: 0.5 }],
+        ..seed_only_config()
+    });
+    assert!(snow.metrics.tti_mean > base.metrics.tti_mean);
+    assert_eq!(snow.scenarios.len(), 2);
+
+    let res = run(TwoLaneReliabilityConfig {
+        incidents: Some(TwoLaneIncidentInputs {
+            incident_rate_per_100mvmt: 500.0,
+            lane_blocked_caf: 0.3,
+            ..Default::default()
+        }),
+        ..seed_only_config()
+    });
+    let blocked: Vec<_> = res
+        .scenarios
+        .iter()
+        .filter(|s| matches!(s.incident, Some((TwoLaneIncidentType::LaneBlocked, _))))
+        .collect();
+    assert_eq!(blocked.len(), 5);
+    assert!(blocked.iter().all(|s| s.over_capacity && s.los == LevelOfService::F));
+    assert!(blocked.iter().all(|s| s.tti > base.scenarios[0].tti));
+    assert!(res.pct_over_capacity > 0.0 && res.pct_over_capacity < 100.0);
+}
+
+#[test]
+fn work_zone_applies_in_its_months_only() {
+    let res = run(TwoLaneReliabilityConfig {
+        work_zones: vec![TwoLaneWorkZone {
+            months: vec![Month::August],
+            segments: vec![2],
+            caf: 0.9,
+            saf: 0.8,
+        }],
+        ..Default::default()
+    });
+    for s in &res.scenarios {
+        assert_eq!(s.work_zone, s.month == Month::August);
+    }
+    let aug = res.scenarios.iter().find(|s| s.month == Month::August).unwrap();
+    let sep = res.scenarios.iter().find(|s| s.month == Month::September && s.day == aug.day).unwrap();
+    assert!(aug.tti > sep.tti);
+}
+
+#[test]
+fn invalid_configurations_are_rejected() {
+    let highway = common::load_test_case(CASE3).unwrap();
+    let bad_weather = TwoLaneReliabilityConfig {
+        weather: vec![
+            WeatherShare { condition: WeatherCondition::HeavyRain, probability: 0.7 },
+            WeatherShare { condition: WeatherCondition::HeavySnow, probability: 0.7 },
+        ],
+        ..Default::default()
+    };
+    let err = TwoLaneReliability::new(highway.clone(), bad_weather).run().unwrap_err();
+    assert!(err.contains("weather"));
+
+    let listed_remainder = TwoLaneReliabilityConfig {
+        weather: vec![
+            WeatherShare { condition: WeatherCondition::NonSevereWeather, probability: 0.9 },
+            WeatherShare { condition: WeatherCondition::HeavyRain, probability: 0.1 },
+        ],
+        ..Default::default()
+    };
+    let err = TwoLaneReliability::new(highway.clone(), listed_remainder).run().unwrap_err();
+    assert!(err.contains("non-severe weather"));
+
+    let bad_wz = TwoLaneReliabilityConfig {
+        work_zones: vec![TwoLaneWorkZone {
+            months: vec![Month::June],
+            segments: vec![9],
+            caf: 0.9,
+            saf: 1.0,
+        }],
+        ..Default::default()
+    };
+    let err = TwoLaneReliability::new(highway, bad_wz).run().unwrap_err();
+    assert!(err.contains("segment 9"));
+}
@@ -0,0 +1,183 @@
+//! Segmentation from alignment and passing zone data.
+//!
+//! Chapter 26 Example Problem 3 (`case3.json`) is rebuilt from its striping
+//! and demand and must analyze the same as the published segments. The
+//! other tests each drive one segmentation rule.
+
+use transportations_library::hcm::twolanehighways::segmentation::*;
+
+mod common;
+
+const CASE3: &str = "tests/ExampleCases/hcm/TwoLaneHighways/case3.json";
+const MI: f64 = 5280.0;
+
+fn demand(start_mi: f64, volume: f64) -> DemandStretch {
+    DemandStretch { start_ft: start_mi * MI, volume, opposing_volume: 0.0, phf: None, phv: None }
+}
+
+fn stretch(start_mi: f64, end_mi: f64, passing_type: usize) -> PassingStretch {
+    PassingStretch { start_ft: start_mi * MI, end_ft: end_mi * MI, passing_type }
+}
+
+fn grade(start_mi: f64, grade: f64) -> GradeTangent {
+    GradeTangent { start_ft: start_mi * MI, grade }
+}
+
+fn alignment(length_mi: f64) -> TwoLaneAlignment {
+    TwoLaneAlignment {
+        length_ft: length_mi * MI,
+        grades: vec![],
+        curves: vec![],
+        passing: vec![],
+        demand: vec![demand(0.0, 800.0)],
+        spl: 55.0,
+        phf: 0.94,
+        phv: 6.0,
+        lane_width: Some(12.0),
+        shoulder_width: Some(6.0),
+        apd: Some(0.0),
+        pmhvfl: Some(0.4),
+    }
+}
+
+fn rules(seg: &TwoLaneSegmentation) -> Vec<SegmentationRule> {
+    seg.decisions.iter().map(|d| d.rule).collect()
+}
+
+#[test]
+fn example_problem_3_is_rebuilt_from_striping() {
+    let mut a = alignment(5.5);
+    a.passing = vec![stretch(0.75, 2.25, 2), stretch(3.25, 3.75, 1)];
+    let phf = [0.94, 0.95, 0.95, 0.94, 0.935];
+    let phv = [8.0, 8.0, 8.0, 7.5, 8.0];
+    a.demand = [(0.0, 850.0), (0.75, 825.0), (2.25, 820.0), (3.25, 800.0), (3.75, 795.0)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(s, v))| DemandStretch {
+            opposing_volume: if i == 3 { 500.0 } else { 0.0 },
+            phf: Some(phf[i]),
+            phv: Some(phv[i]),
+            ..demand(s, v)
+        })
+        .collect();
+
+    let seg = a.build().unwrap();
+    let published = common::load_test_case(CASE3).unwrap();
+    assert_eq!(seg.highway.segments.len(), published.segments.len());
+    for (got, want) in seg.highway.segments.iter().zip(&published.segments) {
+        assert_eq!(got.passing_type, want.passing_type);
+        assert!((got.length - want.length).abs() < 1e-12);
+        assert_eq!(got.vertical_class, want.vertical_class);
+    }
+    let (got, want) = (seg.highway.clone().analyze(), published.clone().analyze());
+    assert!((got.follower_density - want.follower_density).abs() < 1e-9);
+    assert_eq!(got.los, want.los);
+    assert!(rules(&seg).contains(&SegmentationRule::PassingTypeChange));
+    assert!(rules(&seg).contains(&SegmentationRule::DemandChange));
+}
+
+#[test]
+fn grades_combine_within_a_class_and_split_across_classes() {
+    // 1.0 mi at 1% and 1.0 mi at 2% share class 1; 1.0 mi at 6% does not.
+    let mut a = alignment(3.0);
+    a.grades = vec![grade(0.0, 1.0), grade(1.0, 2.0), grade(2.0, 6.0)];
+    let seg = a.build().unwrap();
+    assert_eq!(seg.segments.len(), 2);
+    assert!((seg.segments[0].grade - 1.5).abs() < 1e-12);
+    assert_eq!(seg.segments[0].vertical_class, 1);
+    assert_eq!(seg.segments[1].vertical_class, 5);
+    assert!(rules(&seg).contains(&SegmentationRule::GradesCombined));
+    assert!(rules(&seg).contains(&SegmentationRule::VerticalClassChange));
+}
+
+#[test]
+fn short_pieces_join_the_closer_grade_and_short_zones_are_constrained() {
+    // 0.2 mi of 6% between 1.0 mi of 8% and 1.0 mi of 0%: each is its own
+    // class, and the 6% joins the 8%.
+    let mut a = alignment(2.2);
+    a.grades = vec![grade(0.0, 8.0), grade(1.0, 6.0), grade(1.2, 0.0)];
+    let seg = a.build().unwrap();
+    assert!(rules(&seg).contains(&SegmentationRule::ShortSegmentCombined));
+    assert_eq!(seg.segments.len(), 2);
+    assert!((seg.segments[0].end_ft - 1.2 * MI).abs() < 1e-6);
+    assert!((seg.segments[0].grade - 9.2 / 1.2).abs() < 1e-9);
+
+    // A 0.2-mi passing zone is below the 0.25-mi minimum.
+    let mut a = alignment(2.0);
+    a.passing = vec![stretch(1.0, 1.2, 1)];
+    let seg = a.build().unwrap();
+    assert_eq!(seg.segments.len(), 1);
+    assert_eq!(seg.segments[0].passing_type, 0);
+    assert_eq!(rules(&seg), vec![SegmentationRule::ShortPassingZone]);
+}
+
+#[test]
+fn long_segments_are_divided_but_passing_lanes_are_not() {
+    let mut a = alignment(7.0);
+    a.passing = vec![stretch(3.5, 7.0, 2)];
+    let seg = a.build().unwrap();
+    // 3.5 mi of Passing Constrained exceeds 3.0 mi: two 1.75-mi segments.
+    assert_eq!(seg.segments.len(), 3);
+    assert!((seg.highway.segments[0].length - 1.75).abs() < 1e-12);
+    assert!((seg.highway.segments[2].length - 3.5).abs() < 1e-12);
+    assert!(rules(&seg).contains(&SegmentationRule::LongSegmentDivided));
+    assert!(rules(&seg).contains(&SegmentationRule::LongPassingLane));
+}
+
+#[test]
+fn curves_become_subsegments_and_are_divided_at_boundaries() {
+    let mut a = alignment(2.0);
+    a.passing = vec![stretch(1.0, 2.0, 1)];
+    a.demand[0].opposing_volume = 400.0;
+    a.curves = vec![
+        StationedCurve { start_ft: 1000.0, end_ft: 1500.0, radius_ft: 800.0, superelevation: 4.0 },
+        StationedCurve { start_ft: 5000.0, end_ft: 6000.0, radius_ft: 1200.0, superelevation: 6.0 },
+    ];
+    let seg = a.build().unwrap();
+    assert_eq!(seg.segments.iter().map(|s| s.curves).collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(rules(&seg).iter().filter(|&&r| r == SegmentationRule::CurveDivided).count(), 1);
+
+    let first = seg.highway.segments[0].subsegments.as_ref().unwrap();
+    let lengths: Vec<f64> = first.iter().map(|s| s.get_length()).collect();
+    assert_eq!(lengths, vec![1000.0, 500.0, 3500.0, 280.0]);
+    assert_eq!(first[1].get_design_rad(), 800.0);
+    assert_eq!(first[2].get_design_rad(), 0.0);
+    let second = seg.highway.segments[1].subsegments.as_ref().unwrap();
+    assert_eq!(second[0].get_length(), 720.0);
+    assert_eq!(seg.highway.segments[0].is_hc, Some(true));
+
+    // The curved facility still runs through the engine.
+    let result = seg.highway.clone().analyze();
+    assert!(result.follower_density > 0.0);
+
+    // The report stays in station order when a decision downstream of the
+    // divided curve was made before the curves were placed.
+    a.demand.push(demand(1.5, 900.0));
+    let seg = a.build().unwrap();
+    let stations: Vec<f64> = seg.decisions.iter().map(|d| d.station_ft).collect();
+    assert!(stations.windows(2).all(|w| w[0] <= w[1]), "{stations:?}");
+}
+
+#[test]
+fn invalid_alignments_are_rejected() {
+    let mut a = alignment(1.0);
+    a.demand[0].start_ft = 100.0;
+    assert!(a.build().unwrap_err().contains("station 0"));
+
+    let mut a = alignment(1.0);
+    a.passing = vec![stretch(0.2, 0.6, 1), stretch(0.5, 0.9, 2)];
+    assert!(a.build().unwrap_err().contains("overlap"));
+
+    let mut a = alignment(1.0);
+    a.passing = vec![stretch(0.2, 0.6, 3)];
+    assert!(a.build().unwrap_err().contains("passing lane (2)"));
+
+    let mut a = alignment(1.0);
+    a.curves = vec![StationedCurve {
+        start_ft: 0.0,
+        end_ft: 100.0,
+        radius_ft: 0.0,
+        superelevation: 2.0,
+    }];
+    assert!(a.build().unwrap_err().contains("curve"));
+}